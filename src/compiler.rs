@@ -1,23 +1,23 @@
 // Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use rlg::LogLevel::ERROR;
+use rlg::LogLevel::{ERROR, WARNING};
 
 use crate::{
     macro_cleanup_directories, macro_create_directories,
     macro_log_info, macro_metadata_option, macro_set_rss_data_fields,
-    models::data::{FileData, PageData, RssData},
+    models::data::{BuildOptions, FileData, PageData, RssData},
     modules::{
-        cname::create_cname_data, html::generate_html, human::create_human_data, json::{cname, human, sitemap, txt}, manifest::create_manifest_data, metadata::extract_and_prepare_metadata, navigation::NavigationGenerator, pdf::PdfGenerationParams, plaintext::generate_plain_text, rss::generate_rss, sitemap::create_site_map_data, tags::*, txt::create_txt_data
+        archives::{archive_data, collect_archives, generate_archive_files}, authors::{apply_authors, author_data, author_feeds, author_json_ld, collect_author_pages, generate_author_files}, bundles::{bundle_assets, copy_assets}, cascade::apply_defaults, cname::create_cname_data, collections::{group_pages, page_entry, run_queries}, config::{config_path, load_config}, datafiles::{data_path, load_data}, dates::{check_dates, date_data, normalize_date, parse_date, site_offset, to_rfc2822, to_w3c}, frontmatter::extract, formats::{html_body, is_content_page, is_html_page, normalize_pages, renders_layout}, generators::{generate_data_files, record_data}, git::{apply_git_info, collect_git_info, edit_url, repository_prefix}, html::generate_html, human::create_human_data, i18n::{default_language, generate_hreflang_links, generate_translations, interface_strings, language_feeds, language_menus, language_urls, resolve_localized_routes, split_language}, json::{cname, human, sitemap, sitemap_from_urls, txt}, manifest::create_manifest_data, metadata::extract_and_prepare_metadata, metatags::{generate_all_meta_tags, generate_breadcrumb_json_ld}, navigation::{NavigationGenerator, MAIN_MENU}, pagination::{list_items, paginate, paginator_data}, pdf::PdfGenerationParams, permalinks::output_name, plaintext::generate_plain_text, redirects::{collect_redirects, normalize_alias, write_redirects}, references::resolve_references, related::{find_related, related_document, related_entries}, sections::{apply_section_layout, document_name, section_data}, publication::{filter_published, publication_status, status_marker, today}, rss::{generate_rss, generate_rss_feed}, series::{collect_series, generate_overview_files, series_data}, sitemap::create_site_map_data, summary::{apply_summary, summarize}, tags::*, txt::create_txt_data, urls::{absolute_url, path_prefix, rewrite_site_links, site_root}
     },
     utilities::{
-        file::add,
+        file::{add_matching, list_files},
         template::{render_page, PageOptions},
         write::write_files_to_build_directory,
     },
 };
 use crate::modules::pdf::generate_pdf;
-use std::{collections::HashMap, error::Error, fs, path::Path};
+use std::{collections::{HashMap, HashSet}, error::Error, fs, path::Path, sync::Arc};
 
 /// Compiles files in a source directory, generates HTML pages from them, and
 /// writes the resulting pages to an output directory. Also generates an index
@@ -43,55 +43,269 @@ pub fn compile(
     content_path: &Path,   // The path to the content directory
     site_path: &Path,      // The path to the site directory
     template_path: &Path,  // The path to the template directory
+) -> Result<(), Box<dyn Error>> {
+    compile_with_options(
+        build_dir_path,
+        content_path,
+        site_path,
+        template_path,
+        &BuildOptions::default(),
+    )
+}
+
+/// Compiles the site like `compile`, with the given build options.
+///
+/// Drafts, pages scheduled for a future date and expired pages are left
+/// out of the build, navigation, feeds, tags and sitemap. The `drafts` and
+/// `future` options include drafts and scheduled pages, which are then
+/// rendered with a visible status marker.
+///
+/// # Arguments
+///
+/// * `build_dir_path` - The path to the temporary build directory.
+/// * `content_path` - The path to the content directory.
+/// * `site_path` - The path to the output site directory.
+/// * `template_path` - The path to the template directory.
+/// * `options` - The options of the build.
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the compilation is successful, otherwise returns an error
+/// wrapped in a `Box<dyn Error>`.
+pub fn compile_with_options(
+    build_dir_path: &Path,
+    content_path: &Path,
+    site_path: &Path,
+    template_path: &Path,
+    options: &BuildOptions,
 ) -> Result<(), Box<dyn Error>> {
     // Create build and site directories
     macro_create_directories!(build_dir_path, site_path)?;
 
-    // Read files in the source directory
-    let source_files = add(content_path)?;
-
-    // Generate navigation bar HTML
-    let navigation =
-        NavigationGenerator::generate_navigation(&source_files);
+    // Load the site configuration, if any
+    let config = load_config(&config_path(content_path))?;
+
+    // Load the global data files, if any
+    let global_data = Arc::new(serde_json::Value::Object(load_data(&data_path(content_path))?));
+
+    // Parse the timezone of dates written without one
+    let offset = site_offset(&config.timezone)?;
+
+    // Read the pages in the source directory, with the pages written in
+    // data formats converted to Markdown, generate the pages of the data
+    // files and apply the section layout and front matter defaults,
+    // leaving out unpublished pages. The other files are the assets of
+    // the page bundles
+    let today = today(offset);
+    let mut content_files =
+        normalize_pages(add_matching(content_path, is_content_page)?)?;
+    let page_names: Vec<String> =
+        content_files.iter().map(|file| file.name.clone()).collect();
+    let assets: Vec<String> = list_files(content_path)?
+        .into_iter()
+        .filter(|name| !is_content_page(name))
+        .collect();
+    content_files.extend(generate_data_files(&content_files, &config, &global_data)?);
+    let page_fields: HashMap<String, HashSet<String>> = content_files
+        .iter()
+        .map(|file| (file.name.clone(), extract(&file.content).into_keys().collect()))
+        .collect();
+    let content_files =
+        apply_defaults(apply_section_layout(content_files), &config)?;
+    let records = record_data(&config, &global_data)?;
+    let mut source_files = filter_published(content_files, options, today);
+
+    // Read the git history of the content files, when enabled, and the
+    // path of the content directory in its repository for edit links
+    let git_info = collect_git_info(content_path, &source_files, &config.git);
+    let repository_prefix = if config.git.edit_url.is_empty() {
+        None
+    } else {
+        repository_prefix(content_path)
+    };
+    let source_pages: HashSet<&str> =
+        page_names.iter().map(String::as_str).collect();
+
+    // Add the overview page of every series, the page of every author
+    // and the archive pages
+    let source_routes = resolve_localized_routes(&source_files, &config)?;
+    let author_pages = collect_author_pages(&source_files, &config);
+    let generated_files = [
+        generate_overview_files(&source_files, &source_routes, &config),
+        generate_author_files(&source_files, &author_pages, &config),
+        generate_archive_files(&source_files, &source_routes, &config),
+    ]
+    .concat();
+    source_files.extend(generated_files);
+
+    // Resolve the output path of every page from the permalink patterns
+    // and its language
+    let routes = resolve_localized_routes(&source_files, &config)?;
+
+    // Point the cross-references between pages to their final URLs
+    let source_files = resolve_references(source_files, &routes)?;
+
+    // Group the translations of every page and the pages of every
+    // language
+    let default_language = default_language(&config);
+    let translations =
+        generate_translations(&source_files, &routes, &config);
+    let language_urls = language_urls(&source_files, &routes, &config);
+
+    // Place the assets of every page bundle beside its page
+    let assets = bundle_assets(&assets, &page_names, &routes);
+
+    // Collect the redirects from the old URLs of the pages
+    let mut redirects = collect_redirects(&source_files, &routes)?;
+
+    // Generate the named menus of every language from the front matter
+    // and configuration
+    let language_menus = language_menus(&source_files, &routes, &config)?;
+
+    // Generate the breadcrumbs and previous/next links of every page
+    let breadcrumbs =
+        NavigationGenerator::generate_breadcrumbs(&source_files, &routes);
+    let adjacent_pages =
+        NavigationGenerator::generate_adjacent_pages(&source_files, &routes);
 
     let mut global_tags_data: HashMap<String, Vec<PageData>> =
         HashMap::new();
 
-    // Process source files and store results in 'compiled_files' vector
-    let compiled_files: Vec<FileData> = source_files
+    // First pass: parse the front matter of every page
+    let parsed_files: Vec<_> = source_files
         .into_iter()
         .map(|file| {
-            let (metadata, keywords, all_meta_tags) =
+            let (mut metadata, keywords, _) =
                 extract_and_prepare_metadata(&file.content);
-
-            // Generate HTML
-            let html_content = generate_html(
-                &file.content,
-                &macro_metadata_option!(metadata, "title"),
-                &macro_metadata_option!(metadata, "description"),
-                Some(&macro_metadata_option!(metadata, "content")),
-            )
-            .unwrap_or_else(|err| {
-                let description =
-                    format!("Error generating HTML: {:?}", err);
+            if let Some(info) = git_info.get(&file.name) {
+                let fields = &page_fields[&file.name];
+                apply_git_info(&mut metadata, fields, info, &config.git);
+            }
+            apply_authors(&mut metadata, &config);
+            for warning in check_dates(&file.name, &metadata) {
                 macro_log_info!(
-                    &ERROR,
-                    "compiler.rs - Line 81",
-                    &description,
+                    &WARNING,
+                    "compiler.rs - Line 150",
+                    &warning,
                     &LogFormat::CLF
                 );
-                String::from("Fallback HTML content")
-            });
+            }
 
-            // Generate PDF
-            let (plain_text, plain_title, plain_description, plain_author, plain_creator, plain_keywords) = match generate_plain_text(
+            // Extract the plain text, used for the summary and the PDF
+            let plain = generate_plain_text(
                 &file.content,
                 &macro_metadata_option!(metadata, "title"),
                 &macro_metadata_option!(metadata, "description"),
                 &macro_metadata_option!(metadata, "author"),
                 &macro_metadata_option!(metadata, "generator"),
                 &keywords.join(", "),
-            ) {
+            );
+            if let Ok((plain_text, ..)) = &plain {
+                let summary =
+                    summarize(&file.content, plain_text, &config.summary);
+                apply_summary(&mut metadata, &summary);
+            }
+
+            // Generate the meta tags from the completed front matter
+            let all_meta_tags = generate_all_meta_tags(&metadata);
+            (file, metadata, keywords, all_meta_tags, plain)
+        })
+        .collect();
+
+    // Build the collections of pages exposed to templates
+    let page_entries: Vec<serde_json::Value> = parsed_files
+        .iter()
+        .map(|(file, metadata, ..)| {
+            page_entry(&file.name, metadata, &routes.url(&file.name))
+        })
+        .collect();
+    // Read the last modified date of every page for the sitemaps
+    let page_lastmods: HashMap<String, String> = parsed_files
+        .iter()
+        .filter_map(|(file, metadata, ..)| {
+            let date = ["lastmod", "last_build_date"]
+                .iter()
+                .filter_map(|key| metadata.get(*key))
+                .find_map(|date| parse_date(date, offset))?;
+            Some((routes.url(&file.name), to_w3c(&date)))
+        })
+        .collect();
+    let site_data = Arc::new(group_pages(&page_entries));
+    let collections_data =
+        Arc::new(run_queries(&page_entries, &config.collections));
+
+    // Suggest related pages from shared tags, keywords and text
+    let related_documents: Vec<_> = parsed_files
+        .iter()
+        .map(|(file, metadata, _, _, plain)| {
+            let text = plain
+                .as_ref()
+                .map(|(text, ..)| text.as_str())
+                .unwrap_or_default();
+            related_document(&file.name, metadata, text)
+        })
+        .collect();
+    let related = find_related(&related_documents, &config.related);
+
+    // List the pages and subsections of every section list page
+    let section_data = section_data(&page_entries);
+
+    // Group the parts of every series
+    let series = collect_series(&page_entries, &config);
+    let series_data = series_data(&series, &page_entries, &config);
+
+    // Group the dated pages by year and month
+    let archive_data = archive_data(
+        &collect_archives(&page_entries, &config),
+        &page_entries,
+        &config,
+    );
+
+    // Collect the feed items of every author page and language home page
+    let author_feeds =
+        author_feeds(&author_pages, &page_entries, &config, offset);
+    let language_feeds = language_feeds(&page_entries, &config, offset);
+
+    // Collect the items of every list page
+    let list_items = list_items(
+        &page_entries,
+        &section_data,
+        &archive_data,
+        &series_data,
+        &author_pages,
+        &config,
+    );
+
+    // Second pass: render the pages and store results in 'compiled_files' vector
+    let compiled_files: Vec<FileData> = parsed_files
+        .into_iter()
+        .map(|(file, metadata, keywords, all_meta_tags, plain)| {
+
+            // Generate HTML, keeping the body of HTML pages as is
+            let html_content = if is_html_page(&file.name) {
+                html_body(&file.content).to_string()
+            } else {
+                generate_html(
+                    &file.content,
+                    &macro_metadata_option!(metadata, "title"),
+                    &macro_metadata_option!(metadata, "description"),
+                    Some(&macro_metadata_option!(metadata, "content")),
+                )
+                .unwrap_or_else(|err| {
+                    let description =
+                        format!("Error generating HTML: {:?}", err);
+                    macro_log_info!(
+                        &ERROR,
+                        "compiler.rs - Line 81",
+                        &description,
+                        &LogFormat::CLF
+                    );
+                    String::from("Fallback HTML content")
+                })
+            };
+
+            // Generate PDF
+            let (plain_text, plain_title, plain_description, plain_author, plain_creator, plain_keywords) = match plain {
                 Ok((plain_text, plain_title, plain_description, plain_author, plain_creator, plain_keywords)) => (plain_text, plain_title, plain_description, plain_author, plain_creator, plain_keywords),
                 Err(err) => {
                     let description = format!("Error generating Plain Text: {:?}", err);
@@ -106,16 +320,15 @@ pub fn compile(
                 }
             };
 
-            // Determine the filename without the extension
-            let filename_without_extension = Path::new(&file.name)
-                    .file_stem()
-                    .and_then(|stem| stem.to_str())
-                    .unwrap_or(&file.name);
+            // Determine the filename without the extension, named after
+            // the section for section list pages
+            let filename_without_extension = document_name(&file.name);
                 let common_path = build_dir_path.to_str().unwrap();
-                let pdf_path = if filename_without_extension == "index" {
+                let page_path = routes.path(&file.name);
+                let pdf_path = if page_path.is_empty() {
                     format!("{}/", common_path)
                 } else {
-                    format!("{}/{}/", common_path, filename_without_extension)
+                    format!("{}/{}/", common_path, page_path)
                 };
                 if let Err(err) = generate_pdf(PdfGenerationParams {
                     plain_title: &plain_title,
@@ -125,7 +338,7 @@ pub fn compile(
                     plain_creator: &plain_creator,
                     plain_keywords: &plain_keywords,
                     output_dir: &pdf_path,
-                    filename: filename_without_extension,
+                    filename: &filename_without_extension,
                 }) {
                 let description = format!("Error generating PDF: {:?}", err);
                 macro_log_info!(&ERROR, "compiler.rs - Line 81", &description, &LogFormat::CLF);
@@ -133,6 +346,20 @@ pub fn compile(
             }
 
 
+            // Determine the language of the page
+            let (language, translation_key) =
+                split_language(&file.name, &config);
+            let is_home = Path::new(&translation_key).with_extension("")
+                == Path::new("index");
+            let is_language_home = !config.languages.is_empty() && is_home;
+
+            // Mark drafts and scheduled pages built for preview
+            let page_content = format!(
+                "{}{}",
+                status_marker(publication_status(&metadata, today)),
+                html_content
+            );
+
             // Create page options
             let mut page_options = PageOptions::new();
             for (key, value) in metadata.iter() {
@@ -141,20 +368,195 @@ pub fn compile(
 
             // Set various meta tags
             page_options.set("apple", &all_meta_tags.apple);
-            page_options.set("content", &html_content);
+            page_options.set("content", &page_content);
             page_options.set("microsoft", &all_meta_tags.ms);
-            page_options.set("navigation", &navigation);
             page_options.set("opengraph", &all_meta_tags.og);
             page_options.set("primary", &all_meta_tags.primary);
             page_options.set("twitter", &all_meta_tags.twitter);
 
-            // Render page content
-            let content = render_page(
-                &page_options,
-                &template_path.to_str().unwrap().to_string(),
-                &metadata.get("layout").cloned().unwrap_or_default(),
+            // Expose the menus, with the current page marked as active,
+            // and the main menu as the navigation bar of older templates
+            let page_menus = language_menus
+                .get(&language)
+                .map(|menus| {
+                    NavigationGenerator::mark_active(menus, &routes.url(&file.name))
+                })
+                .unwrap_or_default();
+            let navigation = NavigationGenerator::render_navigation(
+                page_menus.get(MAIN_MENU).map(Vec::as_slice).unwrap_or_default(),
+            );
+            page_options.set("navigation", &navigation);
+            page_options.set_data(
+                "menus",
+                serde_json::to_value(&page_menus).unwrap_or_default(),
+            );
+
+            // Expose the breadcrumbs and previous/next page links
+            let page_breadcrumbs =
+                breadcrumbs.get(&file.name).cloned().unwrap_or_default();
+            let adjacent =
+                adjacent_pages.get(&file.name).cloned().unwrap_or_default();
+            page_options.set_data(
+                "breadcrumbs",
+                serde_json::to_value(&page_breadcrumbs).unwrap_or_default(),
+            );
+            page_options.set_data(
+                "previous",
+                serde_json::to_value(&adjacent.previous).unwrap_or_default(),
+            );
+            page_options.set_data(
+                "next",
+                serde_json::to_value(&adjacent.next).unwrap_or_default(),
+            );
+            let breadcrumbs_json_ld = generate_breadcrumb_json_ld(
+                &page_breadcrumbs,
+                if config.base_url.is_empty() {
+                    metadata.get("url").map(String::as_str).unwrap_or_default()
+                } else {
+                    &config.base_url
+                },
+            );
+            page_options.set("breadcrumbs_json_ld", &breadcrumbs_json_ld);
+
+            // Expose the translations of the page and the interface strings
+            let page_translations =
+                translations.get(&file.name).cloned().unwrap_or_default();
+            let alternates = generate_hreflang_links(
+                &page_translations,
+                &config.base_url,
+                &default_language,
+            );
+            page_options.set("alternates", &alternates);
+            if !metadata.contains_key("language") && !language.is_empty() {
+                page_options.set("language", &language);
+            }
+            page_options.set_data(
+                "translations",
+                serde_json::to_value(&page_translations).unwrap_or_default(),
+            );
+            page_options.set_data(
+                "i18n",
+                serde_json::to_value(interface_strings(&config, &language))
+                    .unwrap_or_default(),
+            );
+
+            // Expose the git history of the page and the edit link of
+            // its content file, which generated pages do not have
+            let page_edit_url = if config.git.edit_url.is_empty()
+                || !source_pages.contains(file.name.as_str())
+            {
+                String::new()
+            } else {
+                edit_url(
+                    &config.git.edit_url,
+                    &format!(
+                        "{}{}",
+                        repository_prefix.as_deref().unwrap_or_default(),
+                        file.name.replace('\\', "/")
+                    ),
+                )
+            };
+            page_options.set("edit_url", &page_edit_url);
+            let mut page_git = serde_json::to_value(
+                git_info.get(&file.name).cloned().unwrap_or_default(),
             )
-            .unwrap();
+            .unwrap_or_default();
+            page_git["edit_url"] = serde_json::Value::String(page_edit_url.clone());
+            page_options.set_data("git", page_git);
+
+            // Expose the dates of the page in every output format
+            let date_language = if language.is_empty() {
+                metadata.get("language").cloned().unwrap_or_default()
+            } else {
+                language.clone()
+            };
+            page_options.set_data(
+                "dates",
+                date_data(&metadata, offset, &config.date_format, &date_language),
+            );
+
+            // Expose the pages of the site and the configured collections
+            page_options.set_shared_data("site", Arc::clone(&site_data));
+            page_options.set_shared_data("data", Arc::clone(&global_data));
+            page_options.set_data(
+                "record",
+                records.get(&file.name).cloned().unwrap_or_default(),
+            );
+            page_options.set_shared_data(
+                "collections",
+                Arc::clone(&collections_data),
+            );
+            page_options.set_data(
+                "related",
+                related_entries(
+                    related.get(&file.name).map(Vec::as_slice).unwrap_or_default(),
+                    &page_entries,
+                ),
+            );
+            page_options.set_data(
+                "series",
+                series_data.get(&file.name).cloned().unwrap_or_default(),
+            );
+            page_options.set_data(
+                "archive",
+                archive_data.get(&file.name).cloned().unwrap_or_default(),
+            );
+            page_options.set_data(
+                "section",
+                section_data.get(&file.name).cloned().unwrap_or_default(),
+            );
+            page_options.set_data(
+                "authors",
+                author_data(&metadata, &config, &routes, &author_pages),
+            );
+            let author_json_ld = author_json_ld(&metadata, &config);
+            page_options.set("author_json_ld", &author_json_ld);
+
+            // Split the items of a list page into pages, the first of
+            // which is rendered at the path of the page itself
+            let mut list_pages = list_items
+                .get(&file.name)
+                .map(|items| {
+                    paginate(
+                        items,
+                        config.pagination.per_page,
+                        routes.url(&file.name).trim_end_matches("index.html"),
+                    )
+                })
+                .unwrap_or_default()
+                .into_iter();
+            if let Some((paginator, items)) = list_pages.next() {
+                page_options
+                    .set_data("paginator", paginator_data(&paginator, &items));
+            }
+
+            // Render page content, unless an HTML page opts out of its layout
+            let template_dir = template_path.to_str().unwrap().to_string();
+            let layout = metadata.get("layout").cloned().unwrap_or_default();
+            let renders_page = !is_html_page(&file.name) || renders_layout(&metadata);
+            let content = if renders_page {
+                render_page(&page_options, &template_dir, &layout)
+                    .map_err(|e| format!("{}: {}", file.name, e))?
+            } else {
+                html_content
+            };
+
+            // Render the following pages of a list page
+            let following_pages = list_pages
+                .filter(|_| renders_page)
+                .map(|(paginator, items)| {
+                    page_options
+                        .set_data("paginator", paginator_data(&paginator, &items));
+                    let path = if page_path.is_empty() {
+                        format!("page/{}", paginator.current)
+                    } else {
+                        format!("{}/page/{}", page_path, paginator.current)
+                    };
+                    render_page(&page_options, &template_dir, &layout)
+                        .map(|content| (path, content))
+                        .map_err(|e| format!("{}: {}", file.name, e))
+                })
+                .collect::<Result<Vec<_>, String>>()?;
 
             // Generate RSS data
             let mut rss_data = RssData::new();
@@ -261,12 +663,57 @@ pub fn compile(
                 macro_metadata_option!(metadata, "ttl")
             );
 
-            // Generate RSS
-            let rss = generate_rss(&rss_data);
+            // Write the dates of the feed in the RFC 2822 format
+            rss_data.pub_date =
+                normalize_date(&rss_data.pub_date, offset, to_rfc2822);
+            rss_data.last_build_date =
+                normalize_date(&rss_data.last_build_date, offset, to_rfc2822);
+            rss_data.item_pub_date =
+                normalize_date(&rss_data.item_pub_date, offset, to_rfc2822);
+
+            // Tag the feed with the language of the page
+            if rss_data.language.is_empty() {
+                rss_data.language = language.clone();
+            }
+
+            // Point the feed to the configured base URL
+            if !config.base_url.is_empty() {
+                rss_data.link = config.base_url.clone();
+                if rss_data.atom_link.is_empty() {
+                    rss_data.atom_link =
+                        absolute_url(&config.base_url, "/rss.xml");
+                }
+            }
+
+            // Generate RSS, with the pages of the author on author pages
+            // and the pages of the language on the home page of each
+            // language
+            let feed_items = author_feeds.get(&file.name).or_else(|| {
+                language_feeds.get(&language).filter(|_| is_language_home)
+            });
+            let rss = match feed_items {
+                Some(items) => {
+                    if !config.base_url.is_empty() {
+                        let path = routes.path(&file.name);
+                        rss_data.atom_link = absolute_url(
+                            &config.base_url,
+                            &if path.is_empty() {
+                                "/rss.xml".to_string()
+                            } else {
+                                format!("/{}/rss.xml", path)
+                            },
+                        );
+                    }
+                    generate_rss_feed(&rss_data, items)
+                }
+                None => generate_rss(&rss_data),
+            };
             let rss_data = rss.unwrap();
 
             // Generate a manifest data structure by extracting relevant information from the metadata.
-            let json = create_manifest_data(&metadata);
+            let mut json = create_manifest_data(&metadata);
+            json.start_url = site_root(&config);
+            json.scope = json.start_url.clone();
 
             // Create a structure to hold CNAME-related options, populated with values from the metadata.
             let cname_options = create_cname_data(&metadata);
@@ -275,7 +722,18 @@ pub fn compile(
             let human_options = create_human_data(&metadata);
 
             // Initialize a structure to store sitemap-related information, using values from the metadata.
-            let sitemap_options = create_site_map_data(&metadata);
+            let mut sitemap_options = create_site_map_data(&metadata);
+            // Read the last build date in the site timezone
+            if let Some(date) = metadata
+                .get("last_build_date")
+                .and_then(|date| parse_date(date, offset))
+            {
+                sitemap_options.lastmod = to_w3c(&date);
+            }
+            if !config.base_url.is_empty() {
+                sitemap_options.loc =
+                    config.base_url.trim_end_matches('/').to_string();
+            }
 
             let tags_data = generate_tags(&file, &metadata);
             // println!("Tags: {:?}", tags_data);
@@ -317,26 +775,53 @@ pub fn compile(
             let txt_data = txt(&txt_options);
             let cname_data = cname(&cname_options);
             let human_data = human(&human_options);
-            let sitemap_data = sitemap(sitemap_options, site_path);
+            // The home page of each language lists the pages of the
+            // language, or of the site without languages
+            let sitemap_data = if is_home {
+                let urls: Vec<(String, String)> = language_urls
+                    .get(&language)
+                    .into_iter()
+                    .flatten()
+                    .map(|url| {
+                        let lastmod = page_lastmods.get(url).cloned();
+                        (url.clone(), lastmod.unwrap_or_default())
+                    })
+                    .collect();
+                sitemap_from_urls(&sitemap_options, &urls)
+            } else {
+                sitemap(sitemap_options, site_path)
+            };
             let json_data = serde_json::to_string(&json)
                 .unwrap_or_else(|e| {
                     eprintln!("Error serializing JSON: {}", e);
                     String::new()
                 });
 
-            // Return FileData
-            FileData {
+            // Return FileData, followed by the pages of a list page
+            let page = FileData {
                 cname: cname_data,
                 content,
                 keyword: keywords.join(", "),
                 human: human_data,
                 json: json_data,
-                name: file.name,
+                name: output_name(&routes.path(&file.name)),
                 rss: rss_data,
                 sitemap: sitemap_data,
                 txt: txt_data,
-            }
+            };
+            let following_files: Vec<FileData> = following_pages
+                .into_iter()
+                .map(|(path, content)| FileData {
+                    content,
+                    name: output_name(&path),
+                    ..page.clone()
+                })
+                .collect();
+            Ok(std::iter::once(page).chain(following_files).collect::<Vec<_>>())
         })
+        .collect::<Result<Vec<_>, String>>()?
+        .into_iter()
+        .flatten()
         .collect();
 
     // Write compiled files to output directory
@@ -365,8 +850,41 @@ pub fn compile(
         )?;
     }
 
-    let tags_html_content = generate_tags_html(&global_tags_data);
-    write_tags_html_to_file(&tags_html_content, build_dir_path)?;
+    // Redirect the first page of every paginated list to the list
+    let per_page = config.pagination.per_page;
+    for (name, items) in &list_items {
+        if per_page > 0 && items.len() > per_page {
+            let alias =
+                normalize_alias(&format!("{}/page/1", routes.path(name)));
+            if redirects.insert(alias.clone(), routes.url(name)).is_some() {
+                return Err(format!(
+                    "Alias `/{}/` is the first page of `{}`",
+                    alias, name
+                )
+                .into());
+            }
+        }
+    }
+
+    // Write the redirect pages and manifests
+    write_redirects(
+        build_dir_path,
+        &redirects,
+        &path_prefix(&config.base_url),
+    )?;
+
+    // Copy the assets of the content directory
+    copy_assets(content_path, build_dir_path, &assets)?;
+
+    // Write the tags page, split into pages
+    write_paginated_tags_html(
+        &global_tags_data,
+        build_dir_path,
+        config.pagination.per_page,
+    )?;
+
+    // Apply the base URL path, or relative links, to every page
+    rewrite_site_links(build_dir_path, &config)?;
 
     // Cleanup site directory
     macro_cleanup_directories!(site_path);
@@ -226,13 +226,19 @@ macro_rules! macro_metadata_option {
 ///
 /// * `$context`: The context to be rendered into the template.
 ///
+/// * `$data` (optional): A `serde_json::Map` of structured values made
+///   available to the template's `{{#each}}`, `{{#if}}` and dotted tags.
+///
 /// ## Behaviour:
 ///
 /// 1. If a file named `$layout.html` exists in `$template_path`, it will be used as the template.
 /// 2. If no such file exists, the macro checks for predefined layout names:
+///     * "archive" maps to "page.html"
 ///     * "contact" maps to "contact.html"
 ///     * "index" maps to "index.html"
 ///     * "page" maps to "page.html"
+///     * "post" maps to "post.html"
+///     * "section" maps to "page.html"
 /// 3. If `$layout` is unrecognized and doesn't correspond to a file in the template directory,
 ///    the macro defaults to using "index.html".
 ///
@@ -243,28 +249,19 @@ macro_rules! macro_metadata_option {
 #[macro_export]
 macro_rules! macro_render_layout {
     ($layout:expr, $template_path:expr, $context:expr) => {{
-        let layout_str: &str = &$layout;
-
-        // Check if a file with the name of the layout exists in the template directory
-        let file_path = Path::new($template_path).join(format!("{}.html", layout_str));
-        let template_file = if file_path.exists() {
-            format!("{}.html", layout_str)
-        } else {
-            match layout_str {
-                "contact" => "contact.html",
-                "index" => "index.html",
-                "page" => "page.html",
-                "post" => "post.html",
-                _ => "index.html",
-            }.to_string()
-        };
-
         let template_content = fs::read_to_string(
-            Path::new($template_path).join(&template_file),
+            $crate::utilities::template::layout_file(Path::new($template_path), &$layout),
         )
         .unwrap();
         render_template(&template_content, &$context)
     }};
+    ($layout:expr, $template_path:expr, $context:expr, $data:expr, $shared:expr) => {{
+        let template_content = fs::read_to_string(
+            $crate::utilities::template::layout_file(Path::new($template_path), &$layout),
+        )
+        .unwrap();
+        render_template_with_shared_data(&template_content, &$context, &$data, &$shared)
+    }};
 }
 
 /// # `macro_serve` Macro
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use serde::{Deserialize, Serialize};
-use std::fmt;
+use std::{collections::BTreeMap, fmt};
 
 #[derive(
     Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize,
@@ -492,3 +492,622 @@ impl MetaTag {
         metatags.iter().map(MetaTag::generate).collect()
     }
 }
+
+/// The `MenuItem` struct holds a single entry of a navigation menu.
+///
+/// Entries are either declared in the front matter of a page (`menu`,
+/// `weight`, `nav_title` and `parent`) or in the `[menus]` section of
+/// the site configuration. Entries whose `parent` matches the
+/// `identifier` of another entry of the same menu are nested in its
+/// `children`, which lets templates render submenus.
+#[derive(
+    Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct MenuItem {
+    /// The unique identifier of the entry within its menu.
+    pub identifier: String,
+    /// The label displayed for the entry.
+    pub title: String,
+    /// The URL the entry links to.
+    pub url: String,
+    /// The sort weight of the entry; lighter entries come first.
+    pub weight: i64,
+    /// The identifier of the parent entry, if any.
+    pub parent: String,
+    /// Whether the entry links to the page being rendered.
+    pub active: bool,
+    /// Whether the entry or one of its descendants is active.
+    pub active_trail: bool,
+    /// The nested entries of the submenu.
+    pub children: Vec<MenuItem>,
+}
+
+impl MenuItem {
+    /// Creates a new `MenuItem` struct with the given identifier, title and url.
+    pub fn new(identifier: String, title: String, url: String) -> Self {
+        MenuItem {
+            identifier,
+            title,
+            url,
+            ..Default::default()
+        }
+    }
+}
+
+/// The `PageLink` struct holds a link to a page of the site.
+///
+/// It is used for breadcrumb trails and previous/next page links.
+#[derive(
+    Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize,
+)]
+pub struct PageLink {
+    /// The title of the linked page.
+    pub title: String,
+    /// The URL of the linked page. Empty when the page does not exist,
+    /// such as a directory without an index page.
+    pub url: String,
+}
+
+impl PageLink {
+    /// Creates a new `PageLink` struct with the given title and url.
+    pub fn new(title: String, url: String) -> Self {
+        PageLink { title, url }
+    }
+}
+
+/// The `AdjacentPages` struct holds the previous and next pages of a
+/// page within its section.
+#[derive(
+    Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize,
+)]
+pub struct AdjacentPages {
+    /// The page before the current page, if any.
+    pub previous: Option<PageLink>,
+    /// The page after the current page, if any.
+    pub next: Option<PageLink>,
+}
+
+/// The `Paginator` struct describes one page of a paginated list.
+///
+/// Page URLs are empty when the page does not exist, e.g. `prev_url`
+/// on the first page.
+#[derive(
+    Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize,
+)]
+pub struct Paginator {
+    /// The number of the current page, starting at 1.
+    pub current: usize,
+    /// The total number of pages.
+    pub total_pages: usize,
+    /// The total number of items across all pages.
+    pub total_items: usize,
+    /// The maximum number of items on a page.
+    pub per_page: usize,
+    /// The URL of the current page.
+    pub url: String,
+    /// The URL of the first page.
+    pub first_url: String,
+    /// The URL of the last page.
+    pub last_url: String,
+    /// The URL of the previous page.
+    pub prev_url: String,
+    /// The URL of the next page.
+    pub next_url: String,
+}
+
+/// The `PaginationConfig` struct holds the pagination settings of list
+/// pages.
+///
+/// Pagination is opt-in: by default every list page holds all its
+/// items.
+#[derive(
+    Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct PaginationConfig {
+    /// The maximum number of items on a page, or `0` for no pagination.
+    pub per_page: usize,
+}
+
+/// The `SummaryConfig` struct holds the settings of page summaries and
+/// reading times.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SummaryConfig {
+    /// The number of words of a summary taken from the start of a page.
+    pub words: usize,
+    /// The reading speed used to estimate reading times.
+    pub words_per_minute: usize,
+}
+
+impl Default for SummaryConfig {
+    fn default() -> Self {
+        SummaryConfig {
+            words: 70,
+            words_per_minute: 200,
+        }
+    }
+}
+
+/// The `ContentSummary` struct holds the statistics and summary of the
+/// text of a page.
+#[derive(
+    Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize,
+)]
+pub struct ContentSummary {
+    /// The number of words of the page.
+    pub word_count: usize,
+    /// The estimated reading time, in minutes.
+    pub reading_time: usize,
+    /// The summary of the page, as plain text.
+    pub summary: String,
+    /// Whether the summary is shorter than the page.
+    pub truncated: bool,
+}
+
+/// The `RelatedConfig` struct holds the settings of related content
+/// suggestions.
+///
+/// The score of a pair of pages is the weighted sum of the overlap of
+/// their tags, the overlap of their keywords and the similarity of their
+/// text, each between 0 and 1.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RelatedConfig {
+    /// The maximum number of related pages of a page, or `0` to disable
+    /// suggestions.
+    pub count: usize,
+    /// The weight of shared tags.
+    pub tags_weight: u32,
+    /// The weight of shared keywords.
+    pub keywords_weight: u32,
+    /// The weight of text similarity.
+    pub text_weight: u32,
+}
+
+impl Default for RelatedConfig {
+    fn default() -> Self {
+        RelatedConfig {
+            count: 5,
+            tags_weight: 3,
+            keywords_weight: 2,
+            text_weight: 1,
+        }
+    }
+}
+
+/// The `RelatedDocument` struct holds what related content suggestions
+/// compare of a page.
+#[derive(
+    Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize,
+)]
+pub struct RelatedDocument {
+    /// The name of the content file.
+    pub name: String,
+    /// The tags of the page.
+    pub tags: Vec<String>,
+    /// The keywords of the page.
+    pub keywords: Vec<String>,
+    /// The plain text of the page.
+    pub text: String,
+}
+
+/// The `Series` struct holds the pages of a multi-part series.
+#[derive(
+    Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize,
+)]
+pub struct Series {
+    /// The name of the series, as written in the front matter.
+    pub name: String,
+    /// The slug of the series, used in the URL of its overview page.
+    pub slug: String,
+    /// The language of the pages of the series.
+    pub language: String,
+    /// The names of the content files of the parts, in order.
+    pub parts: Vec<String>,
+}
+
+/// The `AuthorProfile` struct holds the profile of an author.
+///
+/// Pages reference profiles by ID from their `author` or `authors`
+/// front matter field.
+#[derive(
+    Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct AuthorProfile {
+    /// The full name of the author.
+    pub name: String,
+    /// The email address of the author.
+    pub email: String,
+    /// A short biography of the author.
+    pub bio: String,
+    /// The URL of the picture of the author.
+    pub avatar: String,
+    /// The URL of the website of the author.
+    pub website: String,
+    /// The handles of the author on social networks, keyed by network
+    /// (e.g. `twitter`).
+    pub social: BTreeMap<String, String>,
+}
+
+/// The `ArchiveConfig` struct holds the settings of the date-based
+/// archive pages.
+#[derive(
+    Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct ArchiveConfig {
+    /// Whether the year and month archive pages are generated.
+    pub enabled: bool,
+    /// The section of the archived pages (e.g. `posts`), or empty for
+    /// every dated page.
+    pub section: String,
+}
+
+/// The `ArchivePeriod` struct holds the pages of a year or a month.
+#[derive(
+    Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize,
+)]
+pub struct ArchivePeriod {
+    /// The year.
+    pub year: i32,
+    /// The month, from 1 to 12, or `None` for the whole year.
+    pub month: Option<u32>,
+    /// The names of the content files of the period, newest first.
+    pub pages: Vec<String>,
+}
+
+/// The `CollectionQuery` struct selects, sorts and limits the pages of a
+/// named collection.
+///
+/// Empty criteria match every page.
+#[derive(
+    Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct CollectionQuery {
+    /// The section (content directory) of the pages.
+    pub section: String,
+    /// The layout of the pages.
+    pub layout: String,
+    /// A tag the pages must have.
+    pub tag: String,
+    /// Front matter fields the pages must have, with their values.
+    pub filter: BTreeMap<String, String>,
+    /// The front matter field to sort the pages by.
+    pub sort: String,
+    /// The sort order, `asc` (the default) or `desc`.
+    pub order: String,
+    /// The maximum number of pages, or `0` for all of them.
+    pub limit: usize,
+}
+
+/// The `CascadeRule` struct sets default front matter values for the
+/// pages matching a path.
+///
+/// The values only fill the fields a page does not set itself.
+#[derive(
+    Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct CascadeRule {
+    /// The glob of the content file names of the pages (e.g.
+    /// `posts/**`), where `*` matches within a directory and `**` across
+    /// directories.
+    pub path: String,
+    /// The default front matter values.
+    pub values: BTreeMap<String, String>,
+}
+
+/// The `PageGenerator` struct declares pages generated from the records
+/// of a global data file.
+///
+/// Every record of the data becomes a page, with its fields mapped to
+/// the front matter of the page.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PageGenerator {
+    /// The dotted path of the records in the global data (e.g.
+    /// `products` for `data/products.json`).
+    pub data: String,
+    /// The content file name of the pages, where a `:field` segment is
+    /// replaced by the slug of that field of the record (e.g.
+    /// `products/:id`). Defaults to the generator name followed by the
+    /// title field.
+    pub path: String,
+    /// The layout of the pages.
+    pub layout: String,
+    /// The record field of the title of the pages.
+    pub title: String,
+    /// The record field of the description of the pages.
+    pub description: String,
+    /// The record field of the Markdown body of the pages.
+    pub content: String,
+    /// Other front matter fields of the pages, mapped to record fields.
+    pub fields: BTreeMap<String, String>,
+}
+
+impl Default for PageGenerator {
+    fn default() -> Self {
+        PageGenerator {
+            data: String::new(),
+            path: String::new(),
+            layout: "page".to_string(),
+            title: "title".to_string(),
+            description: "description".to_string(),
+            content: "content".to_string(),
+            fields: BTreeMap::new(),
+        }
+    }
+}
+
+/// The `BuildOptions` struct holds the options of a single build.
+///
+/// By default, drafts and pages scheduled for a future date are left
+/// out of the build; these options include them for local preview.
+#[derive(
+    Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize,
+)]
+pub struct BuildOptions {
+    /// Whether to build pages marked with `draft: true`.
+    pub drafts: bool,
+    /// Whether to build pages with a future publication date.
+    pub future: bool,
+}
+
+/// The `PublicationStatus` enum describes whether a page is published.
+#[derive(
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+)]
+pub enum PublicationStatus {
+    /// The page is published.
+    #[default]
+    Published,
+    /// The page is a draft.
+    Draft,
+    /// The page is scheduled for a future date.
+    Scheduled,
+    /// The expiry date of the page has passed.
+    Expired,
+}
+
+/// The `PageDate` struct holds a date and time parsed from the front
+/// matter.
+#[derive(
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+)]
+pub struct PageDate {
+    /// The year.
+    pub year: i32,
+    /// The month, from 1 to 12.
+    pub month: u32,
+    /// The day of the month, from 1 to 31.
+    pub day: u32,
+    /// The hour, from 0 to 23.
+    pub hour: u32,
+    /// The minute, from 0 to 59.
+    pub minute: u32,
+    /// The second, from 0 to 59.
+    pub second: u32,
+    /// The offset from UTC, in minutes.
+    pub offset: i32,
+    /// Whether the date was written with a time of day.
+    pub has_time: bool,
+}
+
+/// The `GitConfig` struct holds the settings of the information taken
+/// from the git history of the content files.
+#[derive(
+    Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct GitConfig {
+    /// Whether the created and last modified dates of the pages are
+    /// taken from the git history.
+    pub dates: bool,
+    /// Whether the contributors of the pages are taken from the git
+    /// history.
+    pub contributors: bool,
+    /// The URL of the page editing a content file, where `{path}` is the
+    /// path of the file in the repository (e.g.
+    /// `https://github.com/org/site/edit/main/{path}`).
+    pub edit_url: String,
+}
+
+/// The `GitInfo` struct holds the information taken from the git history
+/// of a content file.
+#[derive(
+    Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize,
+)]
+pub struct GitInfo {
+    /// The date of the first commit of the file, in the RFC 3339 format.
+    pub created: String,
+    /// The date of the last commit of the file, in the RFC 3339 format.
+    pub modified: String,
+    /// The authors of the commits of the file, from the first one.
+    pub contributors: Vec<String>,
+}
+
+/// The `LanguageConfig` struct describes a language of a multilingual
+/// site.
+#[derive(
+    Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct LanguageConfig {
+    /// The name of the language, as shown in the language switcher.
+    pub name: String,
+    /// The position of the language in the language switcher. Lower
+    /// weights come first.
+    pub weight: i64,
+    /// The translations of the interface strings used by templates.
+    pub strings: BTreeMap<String, String>,
+}
+
+/// The `Translation` struct holds a link to a translation of a page.
+///
+/// It is used for language switchers and `hreflang` alternates.
+#[derive(
+    Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize,
+)]
+pub struct Translation {
+    /// The code of the language (e.g. `fr`).
+    pub language: String,
+    /// The name of the language.
+    pub name: String,
+    /// The URL of the translated page.
+    pub url: String,
+    /// Whether this is the translation being rendered.
+    pub active: bool,
+}
+
+/// The `SiteConfig` struct holds the site-wide configuration.
+///
+/// The configuration is read from an optional `ssg.toml` file located
+/// next to the content directory. Every section is optional, so an
+/// absent file yields the defaults.
+///
+/// ```toml
+/// base_url = "https://example.com/product/docs/"
+/// relative_links = false
+/// default_language = "en"
+/// timezone = "+02:00"
+/// date_format = "%e %B %Y"
+///
+/// [git]
+/// dates = true
+/// contributors = true
+/// edit_url = "https://github.com/org/site/edit/main/{path}"
+///
+/// [languages.en]
+/// name = "English"
+///
+/// [languages.fr]
+/// name = "Français"
+/// strings = { read_more = "Lire la suite" }
+///
+/// [authors.jane]
+/// name = "Jane Doe"
+/// email = "jane.doe@example.com"
+/// bio = "Jane writes the tutorials."
+/// avatar = "https://example.com/images/jane.webp"
+/// social = { twitter = "@janedoe", github = "janedoe" }
+///
+/// [[menus.main]]
+/// identifier = "docs"
+/// title = "Docs"
+/// url = "/docs/index.html"
+/// weight = 10
+///
+/// [pagination]
+/// per_page = 20
+///
+/// [archives]
+/// enabled = true
+/// section = "posts"
+///
+/// [summary]
+/// words = 50
+/// words_per_minute = 230
+///
+/// [related]
+/// count = 3
+/// tags_weight = 3
+/// keywords_weight = 2
+/// text_weight = 1
+///
+/// [collections.recent_posts]
+/// section = "posts"
+/// sort = "date"
+/// order = "desc"
+/// limit = 5
+///
+/// [[cascade]]
+/// path = "posts/**"
+/// values = { layout = "post", robots = "index, follow" }
+///
+/// [generators.products]
+/// data = "products"
+/// path = "products/:id"
+/// layout = "page"
+/// title = "name"
+/// description = "summary"
+/// fields = { date = "released", tags = "categories" }
+///
+/// [permalinks]
+/// posts = "/:year/:month/:slug/"
+/// ```
+#[derive(
+    Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct SiteConfig {
+    /// The absolute URL the site is deployed at, which may include a
+    /// path prefix (e.g. `https://example.com/product/docs/`).
+    pub base_url: String,
+    /// Whether root-relative links are rewritten as relative links, so
+    /// that the site can be browsed from a file share.
+    pub relative_links: bool,
+    /// The timezone of dates written without one, as an offset from UTC
+    /// (e.g. `+02:00`) or an abbreviation (e.g. `UTC`, `PST`). IANA
+    /// names such as `Europe/Paris` are not supported.
+    pub timezone: String,
+    /// The format of the dates displayed by templates, using `%Y`, `%m`,
+    /// `%d`, `%e`, `%B`, `%b`, `%A`, `%a`, `%H`, `%M` and `%S`. Defaults
+    /// to the customary format of the page language.
+    pub date_format: String,
+    /// The information taken from the git history of the content files.
+    pub git: GitConfig,
+    /// The language of pages without a language suffix or directory.
+    /// Defaults to the first of `languages` by weight.
+    pub default_language: String,
+    /// The languages of a multilingual site, keyed by language code.
+    pub languages: BTreeMap<String, LanguageConfig>,
+    /// The profiles of the authors, keyed by the ID used in the front
+    /// matter.
+    pub authors: BTreeMap<String, AuthorProfile>,
+    /// Menu entries declared in the configuration, keyed by menu name.
+    pub menus: BTreeMap<String, Vec<MenuItem>>,
+    /// The pagination settings of list pages.
+    pub pagination: PaginationConfig,
+    /// The settings of the date-based archive pages.
+    pub archives: ArchiveConfig,
+    /// The settings of page summaries and reading times.
+    pub summary: SummaryConfig,
+    /// The settings of related content suggestions.
+    pub related: RelatedConfig,
+    /// Named page queries exposed to templates as `collections`.
+    pub collections: BTreeMap<String, CollectionQuery>,
+    /// The front matter defaults of the pages matching a path, from the
+    /// lowest to the highest precedence.
+    pub cascade: Vec<CascadeRule>,
+    /// The pages generated from global data files, keyed by name.
+    pub generators: BTreeMap<String, PageGenerator>,
+    /// Permalink patterns, keyed by section (e.g. `posts`).
+    pub permalinks: BTreeMap<String, String>,
+}
+
+impl SiteConfig {
+    /// Creates a new `SiteConfig` struct with default values for all fields.
+    pub fn new() -> Self {
+        SiteConfig::default()
+    }
+}
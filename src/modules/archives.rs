@@ -0,0 +1,331 @@
+// Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::models::data::{
+    ArchivePeriod, FileData, PageDate, SiteConfig,
+};
+use crate::modules::collections::page_entry;
+use crate::modules::dates::{date_key, format_date};
+use crate::modules::frontmatter::extract;
+use crate::modules::i18n::default_language;
+use crate::modules::navigation::NavigationGenerator;
+use crate::modules::permalinks::Routes;
+use crate::modules::series::{field, generated_page};
+use serde_json::{json, Value};
+use std::{cmp::Reverse, collections::HashMap};
+
+/// The content file name of the archive index page.
+pub const ARCHIVE_INDEX: &str = "archives/index.md";
+
+/// The layout of the archive pages, rendered with `page.html` by themes
+/// without an `archive.html` template.
+pub const ARCHIVE_LAYOUT: &str = "archive";
+
+/// Groups the dated pages of the site by year and by month.
+///
+/// Only the pages of the configured section are archived, and section
+/// `index` and `_index` pages are left out.
+///
+/// # Arguments
+///
+/// * `entries` - The entries of all pages, as created by `page_entry`.
+/// * `config` - The site configuration.
+///
+/// # Returns
+///
+/// The periods with pages, newest first, each year followed by its
+/// months. Nothing is archived unless archives are enabled.
+pub fn collect_archives(
+    entries: &[Value],
+    config: &SiteConfig,
+) -> Vec<ArchivePeriod> {
+    if !config.archives.enabled {
+        return Vec::new();
+    }
+
+    let section = config.archives.section.trim_matches('/');
+    let mut pages: Vec<_> = entries
+        .iter()
+        .filter(|entry| {
+            let page_section = field(entry, "section");
+            section.is_empty()
+                || page_section == section
+                || page_section.starts_with(&format!("{}/", section))
+        })
+        .filter(|entry| {
+            !NavigationGenerator::is_index_page(field(entry, "name"))
+        })
+        .filter_map(|entry| {
+            let date = date_key(field(entry, "date"))?;
+            Some((Reverse(date), field(entry, "name")))
+        })
+        .collect();
+    pages.sort();
+
+    let mut periods: Vec<ArchivePeriod> = Vec::new();
+    for (Reverse((year, month, _)), name) in pages {
+        for month in [None, Some(month)] {
+            match periods.iter_mut().find(|period| {
+                period.year == year && period.month == month
+            }) {
+                Some(period) => period.pages.push(name.to_string()),
+                None => periods.push(ArchivePeriod {
+                    year,
+                    month,
+                    pages: vec![name.to_string()],
+                }),
+            }
+        }
+    }
+    periods
+}
+
+/// Returns the content file name of the archive page of a period.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::models::data::ArchivePeriod;
+/// use ssg::modules::archives::archive_name;
+///
+/// let mut period = ArchivePeriod { year: 2024, ..Default::default() };
+/// assert_eq!(archive_name(&period), "2024/index.md");
+/// period.month = Some(7);
+/// assert_eq!(archive_name(&period), "2024/07/index.md");
+/// ```
+pub fn archive_name(period: &ArchivePeriod) -> String {
+    match period.month {
+        Some(month) => format!("{}/{:02}/index.md", period.year, month),
+        None => format!("{}/index.md", period.year),
+    }
+}
+
+/// Returns the title of the archive page of a period.
+///
+/// # Arguments
+///
+/// * `period` - The period.
+/// * `language` - The language of the month names.
+///
+/// # Returns
+///
+/// The year, or the month name and year (e.g. `July 2024`).
+pub fn archive_title(period: &ArchivePeriod, language: &str) -> String {
+    match period.month {
+        Some(month) => format_date(
+            &PageDate {
+                year: period.year,
+                month,
+                day: 1,
+                ..Default::default()
+            },
+            "%B %Y",
+            language,
+        ),
+        None => period.year.to_string(),
+    }
+}
+
+/// Generates the year and month archive pages and the archive index.
+///
+/// The archive pages have the front matter of the newest page of their
+/// period and the `archive` layout. Their template lists the pages of
+/// the period, newest first, from their `paginator`, and a year page
+/// links to its months. A content file at the name of an archive page
+/// replaces the generated one.
+///
+/// # Arguments
+///
+/// * `files` - The content files.
+/// * `routes` - The output paths of the pages.
+/// * `config` - The site configuration.
+///
+/// # Returns
+///
+/// The Markdown content files of the archive pages.
+pub fn generate_archive_files(
+    files: &[FileData],
+    routes: &Routes,
+    config: &SiteConfig,
+) -> Vec<FileData> {
+    let entries: Vec<Value> = files
+        .iter()
+        .map(|file| {
+            page_entry(
+                &file.name,
+                &extract(&file.content),
+                &routes.url(&file.name),
+            )
+        })
+        .collect();
+    let periods = collect_archives(&entries, config);
+    let language = default_language(config);
+    let front_matter_of = |name: &str| {
+        files
+            .iter()
+            .find(|file| file.name == name)
+            .map(|file| extract(&file.content))
+            .unwrap_or_default()
+    };
+    let period_link = |period: &ArchivePeriod| {
+        format!(
+            "[{}]({}) ({})",
+            archive_title(period, &language),
+            NavigationGenerator::page_url(&archive_name(period)),
+            period.pages.len()
+        )
+    };
+
+    // The name, title, description, body and newest page of every
+    // archive page
+    let mut pages: Vec<(String, String, String, String, &str)> =
+        periods
+            .iter()
+            .map(|period| {
+                let title = archive_title(period, &language);
+                let body = match period.month {
+                    Some(_) => String::new(),
+                    None => periods
+                        .iter()
+                        .filter(|month| {
+                            month.year == period.year
+                                && month.month.is_some()
+                        })
+                        .map(|month| {
+                            format!("- {}", period_link(month))
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                };
+                let description = format!(
+                    "The {} pages of {}.",
+                    period.pages.len(),
+                    title
+                );
+                (
+                    archive_name(period),
+                    title,
+                    description,
+                    body,
+                    period.pages[0].as_str(),
+                )
+            })
+            .collect();
+
+    if let Some(newest) = periods.first() {
+        let body: Vec<String> = periods
+            .iter()
+            .map(|period| match period.month {
+                Some(_) => format!("  - {}", period_link(period)),
+                None => format!("- {}", period_link(period)),
+            })
+            .collect();
+        pages.push((
+            ARCHIVE_INDEX.to_string(),
+            "Archives".to_string(),
+            "The pages of the site, by year and month.".to_string(),
+            body.join("\n"),
+            newest.pages[0].as_str(),
+        ));
+    }
+
+    pages
+        .into_iter()
+        .filter(|(name, ..)| {
+            !files.iter().any(|file| &file.name == name)
+        })
+        .map(|(name, title, description, body, newest)| FileData {
+            content: generated_page(
+                front_matter_of(newest),
+                &[
+                    ("title", &title),
+                    ("description", &description),
+                    ("item_description", &description),
+                    ("layout", ARCHIVE_LAYOUT),
+                ],
+                &body,
+            ),
+            name,
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Generates the template data of the archive pages.
+///
+/// Every year and month page gets its period as a JSON object with its
+/// `title`, `year`, `month` (or `null`), `url`, the `count` of pages and
+/// their entries as `pages`. The archive index gets the `count` of all
+/// archived pages and the `years`, each with their `months`.
+///
+/// # Arguments
+///
+/// * `periods` - The periods, as returned by `collect_archives`.
+/// * `entries` - The entries of all pages, as created by `page_entry`.
+/// * `config` - The site configuration.
+///
+/// # Returns
+///
+/// A map of the content file names of the archive pages to their data.
+pub fn archive_data(
+    periods: &[ArchivePeriod],
+    entries: &[Value],
+    config: &SiteConfig,
+) -> HashMap<String, Value> {
+    let language = default_language(config);
+    let summary = |period: &ArchivePeriod| {
+        json!({
+            "title": archive_title(period, &language),
+            "year": period.year,
+            "month": period.month,
+            "url": NavigationGenerator::page_url(&archive_name(period)),
+            "count": period.pages.len(),
+        })
+    };
+
+    let mut data: HashMap<String, Value> = periods
+        .iter()
+        .map(|period| {
+            let mut value = summary(period);
+            value["pages"] = period
+                .pages
+                .iter()
+                .filter_map(|name| {
+                    entries
+                        .iter()
+                        .find(|entry| field(entry, "name") == name)
+                        .cloned()
+                })
+                .collect();
+            (archive_name(period), value)
+        })
+        .collect();
+
+    if !periods.is_empty() {
+        let years: Vec<Value> = periods
+            .iter()
+            .filter(|year| year.month.is_none())
+            .map(|year| {
+                let mut value = summary(year);
+                value["months"] = periods
+                    .iter()
+                    .filter(|month| {
+                        month.year == year.year && month.month.is_some()
+                    })
+                    .map(summary)
+                    .collect();
+                value
+            })
+            .collect();
+        let count: usize = periods
+            .iter()
+            .filter(|year| year.month.is_none())
+            .map(|year| year.pages.len())
+            .sum();
+        let _ = data.insert(
+            ARCHIVE_INDEX.to_string(),
+            json!({ "title": "Archives", "count": count, "years": years }),
+        );
+    }
+    data
+}
@@ -0,0 +1,434 @@
+// Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::models::data::{
+    AuthorProfile, FileData, RssData, SiteConfig,
+};
+use crate::modules::dates::{date_key, normalize_date, to_rfc2822};
+use crate::modules::frontmatter::{extract, split_list};
+use crate::modules::permalinks::Routes;
+use crate::modules::series::{field, generated_page};
+use crate::modules::urls::absolute_url;
+use serde_json::{json, Map, Value};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+
+/// The directory of the generated author pages.
+pub const AUTHORS_DIR: &str = "authors";
+
+/// Returns the IDs of the author profiles of a page.
+///
+/// The profiles are referenced from the `authors` field, or else from
+/// the `author` field. Values that are not the ID of a configured
+/// profile, such as a free-form author, are left out.
+///
+/// # Arguments
+///
+/// * `metadata` - The front matter of the page.
+/// * `config` - The site configuration.
+///
+/// # Returns
+///
+/// The IDs of the profiles, in order and without duplicates.
+pub fn page_authors(
+    metadata: &HashMap<String, String>,
+    config: &SiteConfig,
+) -> Vec<String> {
+    let ids = match metadata
+        .get("authors")
+        .filter(|authors| !authors.trim().is_empty())
+    {
+        Some(authors) => split_list(authors),
+        None => metadata
+            .get("author")
+            .map(|author| vec![author.trim().to_string()])
+            .unwrap_or_default(),
+    };
+
+    let mut authors: Vec<String> = Vec::new();
+    for id in ids {
+        if config.authors.contains_key(&id) && !authors.contains(&id) {
+            authors.push(id);
+        }
+    }
+    authors
+}
+
+/// Formats an author profile like the `author` field of RSS feeds.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::models::data::AuthorProfile;
+/// use ssg::modules::authors::format_author;
+///
+/// let profile = AuthorProfile {
+///     name: "Jane Doe".to_string(),
+///     email: "jane@example.com".to_string(),
+///     ..Default::default()
+/// };
+/// assert_eq!(format_author(&profile), "jane@example.com (Jane Doe)");
+/// ```
+pub fn format_author(profile: &AuthorProfile) -> String {
+    match (profile.email.is_empty(), profile.name.is_empty()) {
+        (false, false) => {
+            format!("{} ({})", profile.email, profile.name)
+        }
+        (false, true) => profile.email.clone(),
+        (true, _) => profile.name.clone(),
+    }
+}
+
+/// Returns the content file name of the page of an author.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::authors::author_page_name;
+///
+/// assert_eq!(author_page_name("jane"), "authors/jane.md");
+/// ```
+pub fn author_page_name(id: &str) -> String {
+    format!("{}/{}.md", AUTHORS_DIR, id)
+}
+
+/// Fills the author fields of the front matter of a page from the
+/// author profiles it references.
+///
+/// The `author` field is replaced by the formatted first author when it
+/// is an ID or empty, and `authors` is set to the IDs of all authors.
+/// The `twitter_creator`, `author_twitter` and `author_website` fields
+/// are taken from the first author when missing.
+///
+/// # Arguments
+///
+/// * `metadata` - The front matter of the page.
+/// * `config` - The site configuration.
+pub fn apply_authors(
+    metadata: &mut HashMap<String, String>,
+    config: &SiteConfig,
+) {
+    let ids = page_authors(metadata, config);
+    let Some(first) = ids.first().and_then(|id| config.authors.get(id))
+    else {
+        return;
+    };
+
+    let author = metadata.entry("author".to_string()).or_default();
+    if author.trim().is_empty()
+        || config.authors.contains_key(author.trim())
+    {
+        *author = format_author(first);
+    }
+    let _ = metadata.insert("authors".to_string(), ids.join(", "));
+
+    let mut set_default = |key: &str, value: String| {
+        let entry = metadata.entry(key.to_string()).or_default();
+        if entry.trim().is_empty() && !value.is_empty() {
+            *entry = value;
+        }
+    };
+    let twitter = first
+        .social
+        .get("twitter")
+        .map(|handle| format!("@{}", handle.trim_start_matches('@')))
+        .unwrap_or_default();
+    set_default("twitter_creator", twitter.clone());
+    set_default("author_twitter", twitter);
+    set_default("author_website", first.website.clone());
+}
+
+/// Generates the JSON-LD `author` value of a page.
+///
+/// # Arguments
+///
+/// * `metadata` - The front matter of the page.
+/// * `config` - The site configuration.
+///
+/// # Returns
+///
+/// A JSON-LD `Person` built from the author profile, an array of them
+/// for a page with several authors, or a `Person` named after the
+/// `author` field for a page without profile.
+pub fn author_json_ld(
+    metadata: &HashMap<String, String>,
+    config: &SiteConfig,
+) -> String {
+    let mut people: Vec<Value> = page_authors(metadata, config)
+        .iter()
+        .filter_map(|id| config.authors.get(id))
+        .map(person)
+        .collect();
+
+    let value = match people.len() {
+        0 => json!({
+            "@type": "Person",
+            "name": metadata.get("author").cloned().unwrap_or_default(),
+        }),
+        1 => people.remove(0),
+        _ => Value::Array(people),
+    };
+    // Keep the JSON from closing its `<script>` element
+    value.to_string().replace("</", "<\\/")
+}
+
+/// Returns the author profiles of a page, for templates.
+///
+/// # Arguments
+///
+/// * `metadata` - The front matter of the page.
+/// * `config` - The site configuration.
+/// * `routes` - The output paths of the pages.
+/// * `author_pages` - The pages of every author, as returned by
+///   `collect_author_pages`.
+///
+/// # Returns
+///
+/// A JSON array of the profiles, each with its `id` and the `url` of
+/// the page of the author, or an empty string when there is none.
+pub fn author_data(
+    metadata: &HashMap<String, String>,
+    config: &SiteConfig,
+    routes: &Routes,
+    author_pages: &BTreeMap<String, Vec<String>>,
+) -> Value {
+    Value::Array(
+        page_authors(metadata, config)
+            .iter()
+            .filter_map(|id| {
+                let mut profile =
+                    serde_json::to_value(config.authors.get(id)?)
+                        .ok()?;
+                let url = if author_pages.contains_key(id) {
+                    routes.url(&author_page_name(id))
+                } else {
+                    String::new()
+                };
+                profile["id"] = Value::String(id.clone());
+                profile["url"] = Value::String(url);
+                Some(profile)
+            })
+            .collect(),
+    )
+}
+
+/// Groups the pages of the site by author.
+///
+/// # Arguments
+///
+/// * `files` - The content files.
+/// * `config` - The site configuration.
+///
+/// # Returns
+///
+/// A map of author IDs to the names of their pages, newest first.
+/// Authors without pages are left out.
+pub fn collect_author_pages(
+    files: &[FileData],
+    config: &SiteConfig,
+) -> BTreeMap<String, Vec<String>> {
+    let mut pages: BTreeMap<String, Vec<_>> = BTreeMap::new();
+    for file in files {
+        if Path::new(&file.name).starts_with(AUTHORS_DIR) {
+            continue;
+        }
+        let metadata = extract(&file.content);
+        let date = metadata.get("date").and_then(|date| date_key(date));
+        for id in page_authors(&metadata, config) {
+            pages
+                .entry(id)
+                .or_default()
+                .push((Reverse(date), file.name.clone()));
+        }
+    }
+
+    pages
+        .into_iter()
+        .map(|(id, mut pages)| {
+            pages.sort();
+            (id, pages.into_iter().map(|(_, name)| name).collect())
+        })
+        .collect()
+}
+
+/// Generates the page of every author.
+///
+/// The page of an author has the biography of the author as description
+/// and the front matter of their newest page. Its template lists the
+/// pages of the author, newest first, from its `paginator`. A content
+/// file at the name of the author page replaces the generated one.
+///
+/// # Arguments
+///
+/// * `files` - The content files.
+/// * `author_pages` - The pages of every author, as returned by
+///   `collect_author_pages`.
+/// * `config` - The site configuration.
+///
+/// # Returns
+///
+/// The Markdown content files of the author pages.
+pub fn generate_author_files(
+    files: &[FileData],
+    author_pages: &BTreeMap<String, Vec<String>>,
+    config: &SiteConfig,
+) -> Vec<FileData> {
+    let content_of = |name: &str| {
+        files
+            .iter()
+            .find(|file| file.name == name)
+            .map(|file| extract(&file.content))
+            .unwrap_or_default()
+    };
+
+    author_pages
+        .iter()
+        .filter(|(id, _)| {
+            let name = author_page_name(id);
+            !files.iter().any(|file| file.name == name)
+        })
+        .filter_map(|(id, names)| {
+            let profile = config.authors.get(id)?;
+            let title = if profile.name.is_empty() {
+                id.clone()
+            } else {
+                profile.name.clone()
+            };
+            let description = if profile.bio.is_empty() {
+                format!("The pages of {}.", title)
+            } else {
+                profile.bio.clone()
+            };
+
+            let base = names
+                .first()
+                .map(|name| content_of(name))
+                .unwrap_or_default();
+            Some(FileData {
+                name: author_page_name(id),
+                content: generated_page(
+                    base,
+                    &[
+                        ("title", &title),
+                        ("description", &description),
+                        ("item_description", &description),
+                        ("layout", "page"),
+                        ("author", id),
+                        ("authors", id),
+                    ],
+                    "",
+                ),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+/// Generates the feed items of every author page.
+///
+/// # Arguments
+///
+/// * `author_pages` - The pages of every author, as returned by
+///   `collect_author_pages`.
+/// * `entries` - The entries of all pages, as created by `page_entry`.
+/// * `config` - The site configuration.
+/// * `offset` - The offset of dates written without a timezone, in
+///   minutes.
+///
+/// # Returns
+///
+/// A map of the content file names of the author pages to the items of
+/// their feed, newest first.
+pub fn author_feeds(
+    author_pages: &BTreeMap<String, Vec<String>>,
+    entries: &[Value],
+    config: &SiteConfig,
+    offset: i32,
+) -> HashMap<String, Vec<RssData>> {
+    author_pages
+        .iter()
+        .filter_map(|(id, names)| {
+            let profile = config.authors.get(id)?;
+            let items = names
+                .iter()
+                .filter_map(|name| {
+                    entries
+                        .iter()
+                        .find(|entry| field(entry, "name") == name)
+                })
+                .map(|entry| {
+                    feed_item(
+                        entry,
+                        format_author(profile),
+                        config,
+                        offset,
+                    )
+                })
+                .collect();
+            Some((author_page_name(id), items))
+        })
+        .collect()
+}
+
+/// Returns the feed item of a page entry, credited to an author.
+pub(crate) fn feed_item(
+    entry: &Value,
+    author: String,
+    config: &SiteConfig,
+    offset: i32,
+) -> RssData {
+    let url = field(entry, "url");
+    let link = if config.base_url.is_empty() {
+        url.to_string()
+    } else {
+        absolute_url(&config.base_url, url)
+    };
+    let date = match field(entry, "date") {
+        "" => field(entry, "pub_date"),
+        date => date,
+    };
+
+    RssData {
+        author,
+        item_description: field(entry, "description").to_string(),
+        item_guid: link.clone(),
+        item_link: link,
+        item_pub_date: normalize_date(date, offset, to_rfc2822),
+        item_title: field(entry, "title").to_string(),
+        ..Default::default()
+    }
+}
+
+/// Returns the JSON-LD `Person` of an author profile.
+fn person(profile: &AuthorProfile) -> Value {
+    let mut person = Map::new();
+    let mut set = |key: &str, value: &str| {
+        if !value.is_empty() {
+            let _ = person.insert(
+                key.to_string(),
+                Value::String(value.to_string()),
+            );
+        }
+    };
+    set("@type", "Person");
+    set("name", &profile.name);
+    set("email", &profile.email);
+    set("description", &profile.bio);
+    set("image", &profile.avatar);
+    set("url", &profile.website);
+
+    let same_as: Vec<Value> = profile
+        .social
+        .values()
+        .filter(|url| url.starts_with("http"))
+        .map(|url| Value::String(url.clone()))
+        .collect();
+    if !same_as.is_empty() {
+        let _ =
+            person.insert("sameAs".to_string(), Value::Array(same_as));
+    }
+    Value::Object(person)
+}
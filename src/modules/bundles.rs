@@ -0,0 +1,159 @@
+// Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::modules::formats::is_content_page;
+use crate::modules::permalinks::Routes;
+use crate::modules::sections::SECTION_INDEX;
+use std::{fs, io, path::Path};
+
+/// The file stem of the page of a page bundle.
+pub const BUNDLE_INDEX: &str = "index";
+
+/// Returns whether a content file is the page of a page bundle, an
+/// `index` or `_index` page, or one of its translations (e.g.
+/// `index.fr.md`), with its assets beside it.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::bundles::is_bundle_page;
+///
+/// assert!(is_bundle_page("launch/index.md"));
+/// assert!(is_bundle_page("launch/index.fr.md"));
+/// assert!(is_bundle_page("index.html"));
+/// assert!(!is_bundle_page("launch/faq.md"));
+/// ```
+pub fn is_bundle_page(name: &str) -> bool {
+    is_content_page(name)
+        && Path::new(name)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.split('.').next())
+            .is_some_and(|stem| {
+                stem == BUNDLE_INDEX || stem == SECTION_INDEX
+            })
+}
+
+/// Returns the pages of the page bundle an asset belongs to.
+///
+/// The bundle of an asset is the closest directory, from the one of the
+/// asset up to the content directory, with an `index` page. Its pages
+/// are the `index` page and its translations.
+///
+/// # Arguments
+///
+/// * `asset` - The name of the asset, relative to the content
+///   directory.
+/// * `pages` - The names of the content pages.
+///
+/// # Returns
+///
+/// The names of the pages of the bundle, empty if the asset is not in
+/// a bundle.
+pub fn bundle_pages<'p>(
+    asset: &str,
+    pages: &'p [String],
+) -> Vec<&'p str> {
+    Path::new(asset)
+        .ancestors()
+        .skip(1)
+        .map(|directory| {
+            pages
+                .iter()
+                .filter(|page| {
+                    is_bundle_page(page)
+                        && Path::new(page).parent() == Some(directory)
+                })
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+        })
+        .find(|bundle| !bundle.is_empty())
+        .unwrap_or_default()
+}
+
+/// Returns where the assets of the content directory are copied.
+///
+/// An asset is copied to the output directory of every page of its
+/// bundle, at the same place relative to the page, so relative
+/// references from the page keep working when its permalink or language
+/// changes its output path. An asset without bundle keeps its path, and
+/// the assets of a bundle whose pages are all unpublished are left out.
+///
+/// # Arguments
+///
+/// * `assets` - The names of the assets, relative to the content
+///   directory.
+/// * `pages` - The names of the content pages, published or not.
+/// * `routes` - The output paths of the published pages.
+///
+/// # Returns
+///
+/// The name of every copied asset and its path relative to the build
+/// directory, once for every destination.
+pub fn bundle_assets(
+    assets: &[String],
+    pages: &[String],
+    routes: &Routes,
+) -> Vec<(String, String)> {
+    let mut copies = Vec::new();
+    for asset in assets {
+        let bundle = bundle_pages(asset, pages);
+        if bundle.is_empty() {
+            copies.push((asset.clone(), asset.clone()));
+            continue;
+        }
+
+        let mut destinations: Vec<String> = bundle
+            .into_iter()
+            .filter(|page| routes.contains(page))
+            .filter_map(|page| {
+                let directory = Path::new(page).parent()?;
+                let relative =
+                    Path::new(asset).strip_prefix(directory).ok()?;
+                let relative =
+                    relative.to_string_lossy().replace('\\', "/");
+                let path = routes.path(page);
+                Some(if path.is_empty() {
+                    relative
+                } else {
+                    format!("{}/{}", path, relative)
+                })
+            })
+            .collect();
+        destinations.sort();
+        destinations.dedup();
+        copies.extend(
+            destinations
+                .into_iter()
+                .map(|destination| (asset.clone(), destination)),
+        );
+    }
+    copies
+}
+
+/// Copies the assets of the content directory to the build directory.
+///
+/// # Arguments
+///
+/// * `content_path` - The path to the content directory.
+/// * `build_dir_path` - The path to the build directory.
+/// * `assets` - The assets and their destination, as returned by
+///   `bundle_assets`.
+///
+/// # Returns
+///
+/// Returns an `io::Result<()>` which is `Ok` if all assets were copied.
+pub fn copy_assets(
+    content_path: &Path,
+    build_dir_path: &Path,
+    assets: &[(String, String)],
+) -> io::Result<()> {
+    for (asset, destination) in assets {
+        let target = build_dir_path.join(destination);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let _ = fs::copy(content_path.join(asset), target)?;
+    }
+    Ok(())
+}
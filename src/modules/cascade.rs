@@ -0,0 +1,401 @@
+// Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::models::data::{FileData, SiteConfig};
+use crate::modules::config::{
+    config_path, load_config, CONFIG_FILE_NAME,
+};
+use crate::modules::formats::{is_content_page, normalize_pages};
+use crate::modules::frontmatter::{
+    extract, extract_front_matter_str, extract_json_object_str,
+    parse_yaml_values,
+};
+use crate::utilities::file::add_matching;
+use regex::Regex;
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    path::Path,
+};
+use toml::Value as TomlValue;
+use yaml_rust::{Yaml, YamlLoader};
+
+/// The name of the files of directory front matter defaults.
+pub const DEFAULTS_FILE: &str = "_defaults.yml";
+
+/// The source of the values of the page's own front matter.
+pub const PAGE_SOURCE: &str = "page";
+
+/// Returns whether a glob matches a content file name.
+///
+/// In the glob, `*` matches any part of a file or directory name, `**`
+/// matches any number of directories and `?` matches one character.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::cascade::glob_match;
+///
+/// assert!(glob_match("posts/**", "posts/2024/hello.md"));
+/// assert!(glob_match("**/*.md", "index.md"));
+/// assert!(!glob_match("posts/*.md", "posts/2024/hello.md"));
+/// ```
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let mut regex = String::from("^");
+    let mut chars = pattern.trim_start_matches('/').chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                let _ = chars.next();
+                if chars.peek() == Some(&'/') {
+                    let _ = chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            _ => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+
+    Regex::new(&regex).is_ok_and(|regex| regex.is_match(name))
+}
+
+/// Returns whether a content file holds directory front matter defaults.
+pub fn is_defaults_file(name: &str) -> bool {
+    Path::new(name)
+        .file_name()
+        .is_some_and(|file_name| file_name == DEFAULTS_FILE)
+}
+
+/// Reads the directory front matter defaults of the content files.
+///
+/// # Arguments
+///
+/// * `files` - The content files.
+///
+/// # Returns
+///
+/// A map of directories, relative to the content directory, to the
+/// values of their `_defaults.yml` file, or an error naming the file
+/// that is not a valid YAML mapping.
+pub fn collect_defaults(
+    files: &[FileData],
+) -> Result<BTreeMap<String, HashMap<String, String>>, String> {
+    files
+        .iter()
+        .filter(|file| is_defaults_file(&file.name))
+        .map(|file| {
+            let values = match YamlLoader::load_from_str(&file.content)
+                .map_err(|e| {
+                    format!("Error parsing {}: {}", file.name, e)
+                })?
+                .first()
+            {
+                None | Some(Yaml::Null) => HashMap::new(),
+                Some(Yaml::Hash(hash)) => parse_yaml_values(hash),
+                Some(_) => {
+                    return Err(format!(
+                        "Error parsing {}: not a mapping",
+                        file.name
+                    ))
+                }
+            };
+            let directory = Path::new(&file.name)
+                .parent()
+                .map(|parent| {
+                    parent.to_string_lossy().replace('\\', "/")
+                })
+                .unwrap_or_default();
+            Ok((directory, values))
+        })
+        .collect()
+}
+
+/// Returns the front matter defaults of a page, by source.
+///
+/// The defaults are, from the lowest to the highest precedence:
+///
+/// 1. The `[[cascade]]` rules of the site configuration matching the
+///    page, in order.
+/// 2. The `_defaults.yml` files of the content directory and of every
+///    directory down to the one of the page.
+///
+/// The page's own front matter takes precedence over all of them.
+///
+/// # Arguments
+///
+/// * `name` - The name of the content file of the page.
+/// * `config` - The site configuration.
+/// * `defaults` - The directory defaults, as returned by
+///   `collect_defaults`.
+///
+/// # Returns
+///
+/// The source and values of every set of defaults applying to the page,
+/// from the lowest to the highest precedence.
+pub fn cascade_layers(
+    name: &str,
+    config: &SiteConfig,
+    defaults: &BTreeMap<String, HashMap<String, String>>,
+) -> Vec<(String, HashMap<String, String>)> {
+    let mut layers: Vec<(String, HashMap<String, String>)> = config
+        .cascade
+        .iter()
+        .filter(|rule| glob_match(&rule.path, name))
+        .map(|rule| {
+            (
+                format!(
+                    "{} [[cascade]] {}",
+                    CONFIG_FILE_NAME, rule.path
+                ),
+                rule.values
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect(),
+            )
+        })
+        .collect();
+
+    let mut directories: Vec<&str> = Path::new(name)
+        .ancestors()
+        .skip(1)
+        .filter_map(|directory| directory.to_str())
+        .collect();
+    directories.reverse();
+    for directory in directories {
+        if let Some(values) = defaults.get(directory) {
+            let source = if directory.is_empty() {
+                DEFAULTS_FILE.to_string()
+            } else {
+                format!("{}/{}", directory, DEFAULTS_FILE)
+            };
+            layers.push((source, values.clone()));
+        }
+    }
+    layers
+}
+
+/// Returns the effective front matter of a page with the source of
+/// every value.
+///
+/// # Arguments
+///
+/// * `file` - The content file of the page.
+/// * `config` - The site configuration.
+/// * `defaults` - The directory defaults, as returned by
+///   `collect_defaults`.
+///
+/// # Returns
+///
+/// A map of the front matter fields to their value and source: `page`,
+/// a `_defaults.yml` file or a `[[cascade]]` rule.
+pub fn effective_front_matter(
+    file: &FileData,
+    config: &SiteConfig,
+    defaults: &BTreeMap<String, HashMap<String, String>>,
+) -> BTreeMap<String, (String, String)> {
+    let mut layers = cascade_layers(&file.name, config, defaults);
+    layers.push((PAGE_SOURCE.to_string(), extract(&file.content)));
+
+    let mut front_matter = BTreeMap::new();
+    for (source, values) in layers {
+        for (key, value) in values {
+            let _ = front_matter.insert(key, (value, source.clone()));
+        }
+    }
+    front_matter
+}
+
+/// Merges the front matter defaults into the content files.
+///
+/// The `_defaults.yml` files are removed from the content files, and
+/// every page gets the default values of the fields it does not set.
+///
+/// # Arguments
+///
+/// * `files` - The content files.
+/// * `config` - The site configuration.
+///
+/// # Returns
+///
+/// The pages with their defaults, or an error if a `_defaults.yml`
+/// file cannot be parsed.
+pub fn apply_defaults(
+    files: Vec<FileData>,
+    config: &SiteConfig,
+) -> Result<Vec<FileData>, String> {
+    let defaults = collect_defaults(&files)?;
+
+    Ok(files
+        .into_iter()
+        .filter(|file| !is_defaults_file(&file.name))
+        .map(|mut file| {
+            let mut values = BTreeMap::new();
+            for (_, layer) in
+                cascade_layers(&file.name, config, &defaults)
+            {
+                values.extend(layer);
+            }
+            if !values.is_empty() {
+                file.content =
+                    merge_front_matter(&file.content, &values);
+            }
+            file
+        })
+        .collect())
+}
+
+/// Adds default values to the front matter of a page.
+///
+/// Only the fields the front matter does not set are added, in the
+/// format of the front matter: YAML, TOML or JSON. A page without front
+/// matter gets a YAML front matter.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::cascade::merge_front_matter;
+/// use std::collections::BTreeMap;
+///
+/// let defaults = BTreeMap::from([
+///     ("layout".to_string(), "post".to_string()),
+///     ("title".to_string(), "Untitled".to_string()),
+/// ]);
+/// let content = merge_front_matter("---\ntitle: Hello\n---\nBody", &defaults);
+/// assert_eq!(content, "---\nlayout: \"post\"\ntitle: Hello\n---\nBody");
+/// ```
+pub fn merge_front_matter(
+    content: &str,
+    defaults: &BTreeMap<String, String>,
+) -> String {
+    if let Some(front_matter) =
+        extract_front_matter_str(content, "---\n", "\n---\n")
+    {
+        let keys: Vec<String> = YamlLoader::load_from_str(front_matter)
+            .ok()
+            .and_then(|docs| docs.into_iter().next())
+            .and_then(|doc| doc.into_hash())
+            .map(|hash| {
+                hash.keys()
+                    .filter_map(|key| key.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let lines = missing_lines(defaults, &keys, |key, value| {
+            format!("{}: {}", key, Value::String(value.to_string()))
+        });
+        format!("---\n{}{}", lines, &content["---\n".len()..])
+    } else if let Some(front_matter) =
+        extract_front_matter_str(content, "+++\n", "\n+++\n")
+    {
+        let keys: Vec<String> = front_matter
+            .parse::<TomlValue>()
+            .ok()
+            .and_then(|value| {
+                value
+                    .as_table()
+                    .map(|table| table.keys().cloned().collect())
+            })
+            .unwrap_or_default();
+        let lines = missing_lines(defaults, &keys, |key, value| {
+            format!(
+                "{} = {}",
+                key,
+                TomlValue::String(value.to_string())
+            )
+        });
+        format!("+++\n{}{}", lines, &content["+++\n".len()..])
+    } else if let Some(json) = extract_json_object_str(content) {
+        let Ok(mut document) = serde_json::from_str::<Value>(json)
+        else {
+            return content.to_string();
+        };
+        let Some(front_matter) = document
+            .get_mut("frontmatter")
+            .and_then(Value::as_object_mut)
+        else {
+            return content.to_string();
+        };
+        for (key, value) in defaults {
+            let _ = front_matter
+                .entry(key.clone())
+                .or_insert_with(|| Value::String(value.clone()));
+        }
+        format!(
+            "{}{}",
+            serde_json::to_string_pretty(&document)
+                .unwrap_or_else(|_| json.to_string()),
+            &content[json.len()..]
+        )
+    } else {
+        let lines = missing_lines(defaults, &[], |key, value| {
+            format!("{}: {}", key, Value::String(value.to_string()))
+        });
+        format!("---\n{}---\n\n{}", lines, content)
+    }
+}
+
+/// Formats the defaults missing from a front matter, one per line.
+fn missing_lines(
+    defaults: &BTreeMap<String, String>,
+    keys: &[String],
+    format_line: impl Fn(&str, &str) -> String,
+) -> String {
+    defaults
+        .iter()
+        .filter(|(key, _)| !keys.contains(key))
+        .map(|(key, value)| format!("{}\n", format_line(key, value)))
+        .collect()
+}
+
+/// Describes the effective front matter of a page, for debugging.
+///
+/// # Arguments
+///
+/// * `content_path` - The path to the content directory.
+/// * `page` - The name of the content file of the page, relative to the
+///   content directory (e.g. `posts/hello.md`).
+///
+/// # Returns
+///
+/// One line per front matter field with its value and source, or an
+/// error if the page does not exist or the defaults cannot be read.
+pub fn explain_front_matter(
+    content_path: &Path,
+    page: &str,
+) -> Result<String, Box<dyn Error>> {
+    let config = load_config(&config_path(content_path))?;
+    let files =
+        normalize_pages(add_matching(content_path, is_content_page)?)?;
+    let defaults = collect_defaults(&files)?;
+    let page = page.trim_start_matches("./").replace('\\', "/");
+    let file = files
+        .iter()
+        .find(|file| file.name == page)
+        .ok_or_else(|| format!("Page not found: {}", page))?;
+
+    let fields: Vec<(String, String)> =
+        effective_front_matter(file, &config, &defaults)
+            .into_iter()
+            .map(|(key, (value, source))| {
+                (format!("{} = {}", key, Value::String(value)), source)
+            })
+            .collect();
+    // Align the sources, unless a field is too long
+    let width = fields
+        .iter()
+        .map(|(field, _)| field.chars().count())
+        .filter(|length| *length <= 48)
+        .max()
+        .unwrap_or_default();
+    let mut lines = vec![format!("Front matter of {}:", page)];
+    for (field, source) in &fields {
+        lines.push(format!("  {:<2$}  ({})", field, source, width));
+    }
+    Ok(lines.join("\n"))
+}
@@ -0,0 +1,211 @@
+// Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::models::data::CollectionQuery;
+use crate::modules::dates::date_key;
+use crate::modules::frontmatter::split_list;
+use crate::modules::navigation::NavigationGenerator;
+use serde_json::{Map, Value};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+};
+
+/// Creates the collection entry of a page.
+///
+/// The entry holds the front matter of the page, along with its `name`
+/// (the content file name), `url` and `section` (the content directory
+/// the page lives in, empty at the root).
+///
+/// # Arguments
+///
+/// * `name` - The name of the content file.
+/// * `metadata` - The front matter of the page.
+/// * `url` - The URL of the page.
+///
+/// # Returns
+///
+/// A JSON object describing the page.
+pub fn page_entry(
+    name: &str,
+    metadata: &HashMap<String, String>,
+    url: &str,
+) -> Value {
+    let mut entry: Map<String, Value> = metadata
+        .iter()
+        .map(|(key, value)| (key.clone(), Value::String(value.clone())))
+        .collect();
+
+    let path = NavigationGenerator::page_path(name);
+    let section = path
+        .rsplit_once('/')
+        .map(|(section, _)| section.to_string())
+        .unwrap_or_default();
+
+    entry.insert("name".to_string(), Value::String(name.to_string()));
+    entry.insert("url".to_string(), Value::String(url.to_string()));
+    entry.insert("section".to_string(), Value::String(section));
+    Value::Object(entry)
+}
+
+/// Groups the pages of the site.
+///
+/// # Arguments
+///
+/// * `entries` - The entries of all pages, as created by `page_entry`.
+///
+/// # Returns
+///
+/// A JSON object with every page under `pages`, and the pages grouped by
+/// section under `sections`, by layout under `layouts` and by tag under
+/// `tags`.
+pub fn group_pages(entries: &[Value]) -> Value {
+    let mut sections: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+    let mut layouts: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+    let mut tags: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+
+    for entry in entries {
+        let section = field(entry, "section");
+        if !section.is_empty() {
+            sections
+                .entry(section.to_string())
+                .or_default()
+                .push(entry.clone());
+        }
+        let layout = field(entry, "layout");
+        if !layout.is_empty() {
+            layouts
+                .entry(layout.to_string())
+                .or_default()
+                .push(entry.clone());
+        }
+        for tag in split_list(field(entry, "tags")) {
+            tags.entry(tag).or_default().push(entry.clone());
+        }
+    }
+
+    serde_json::json!({
+        "pages": entries,
+        "sections": sections,
+        "layouts": layouts,
+        "tags": tags,
+    })
+}
+
+/// Selects, sorts and limits pages according to a query.
+///
+/// Pages are sorted by the `sort` field: dates for the `date` field,
+/// numbers when both values are numeric, text otherwise. Pages without
+/// the field come last. Without a `sort` field, the pages keep their
+/// original order.
+///
+/// # Arguments
+///
+/// * `entries` - The entries of all pages, as created by `page_entry`.
+/// * `query` - The query to run.
+///
+/// # Returns
+///
+/// The entries of the selected pages.
+pub fn query_pages(
+    entries: &[Value],
+    query: &CollectionQuery,
+) -> Vec<Value> {
+    let mut pages: Vec<Value> = entries
+        .iter()
+        .filter(|entry| matches(entry, query))
+        .cloned()
+        .collect();
+
+    if !query.sort.is_empty() {
+        let descending = query.order.eq_ignore_ascii_case("desc");
+        pages.sort_by(|a, b| {
+            match (field(a, &query.sort), field(b, &query.sort)) {
+                ("", "") => Ordering::Equal,
+                ("", _) => Ordering::Greater,
+                (_, "") => Ordering::Less,
+                (a, b) => {
+                    let ordering = compare_values(&query.sort, a, b);
+                    if descending {
+                        ordering.reverse()
+                    } else {
+                        ordering
+                    }
+                }
+            }
+        });
+    }
+
+    if query.limit > 0 {
+        pages.truncate(query.limit);
+    }
+    pages
+}
+
+/// Runs the named queries of the site configuration.
+///
+/// # Arguments
+///
+/// * `entries` - The entries of all pages, as created by `page_entry`.
+/// * `queries` - The named queries.
+///
+/// # Returns
+///
+/// A JSON object mapping each query name to its pages.
+pub fn run_queries(
+    entries: &[Value],
+    queries: &BTreeMap<String, CollectionQuery>,
+) -> Value {
+    Value::Object(
+        queries
+            .iter()
+            .map(|(name, query)| {
+                (
+                    name.clone(),
+                    Value::Array(query_pages(entries, query)),
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Returns a text field of a page entry, or an empty string.
+fn field<'a>(entry: &'a Value, key: &str) -> &'a str {
+    entry.get(key).and_then(Value::as_str).unwrap_or("")
+}
+
+/// Returns whether a field of a page entry is, or contains, a value.
+fn has_value(entry: &Value, key: &str, value: &str) -> bool {
+    let actual = field(entry, key);
+    actual == value
+        || split_list(actual).iter().any(|item| item == value)
+}
+
+/// Returns whether a page entry matches the criteria of a query.
+fn matches(entry: &Value, query: &CollectionQuery) -> bool {
+    (query.section.is_empty()
+        || field(entry, "section") == query.section)
+        && (query.layout.is_empty()
+            || field(entry, "layout") == query.layout)
+        && (query.tag.is_empty()
+            || has_value(entry, "tags", &query.tag))
+        && query
+            .filter
+            .iter()
+            .all(|(key, value)| has_value(entry, key, value))
+}
+
+/// Compares two values of a front matter field.
+fn compare_values(key: &str, a: &str, b: &str) -> Ordering {
+    if key == "date" {
+        if let (Some(a), Some(b)) = (date_key(a), date_key(b)) {
+            return a.cmp(&b);
+        }
+    }
+    if let (Ok(a), Ok(b)) =
+        (a.trim().parse::<f64>(), b.trim().parse::<f64>())
+    {
+        return a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+    }
+    a.cmp(b)
+}
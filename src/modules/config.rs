@@ -0,0 +1,67 @@
+// Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::models::data::SiteConfig;
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The name of the site configuration file.
+pub const CONFIG_FILE_NAME: &str = "ssg.toml";
+
+/// Returns the location of the site configuration file for a content directory.
+///
+/// The configuration file lives next to the content directory, so that
+/// it is not picked up as a page.
+///
+/// # Arguments
+///
+/// * `content_path` - The path to the content directory.
+///
+/// # Returns
+///
+/// The path to the `ssg.toml` file.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::config::config_path;
+/// use std::path::Path;
+///
+/// let path = config_path(Path::new("site/content"));
+/// assert_eq!(path, Path::new("site/ssg.toml"));
+/// ```
+pub fn config_path(content_path: &Path) -> PathBuf {
+    content_path
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join(CONFIG_FILE_NAME)
+}
+
+/// Loads the site configuration from a TOML file.
+///
+/// A missing file is not an error: the default configuration is
+/// returned instead, so sites without an `ssg.toml` keep working.
+///
+/// # Arguments
+///
+/// * `path` - The path to the configuration file.
+///
+/// # Returns
+///
+/// A `Result` containing the `SiteConfig`, or an error if the file
+/// exists but cannot be read or parsed.
+pub fn load_config(path: &Path) -> Result<SiteConfig, Box<dyn Error>> {
+    if !path.is_file() {
+        return Ok(SiteConfig::default());
+    }
+
+    let content = fs::read_to_string(path)?;
+    let config: SiteConfig = toml::from_str(&content).map_err(|e| {
+        format!("Error parsing configuration {}: {}", path.display(), e)
+    })?;
+
+    Ok(config)
+}
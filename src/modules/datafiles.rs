@@ -0,0 +1,321 @@
+// Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use serde_json::{Map, Number, Value};
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+use toml::Value as TomlValue;
+use yaml_rust::{Yaml, YamlLoader};
+
+/// The name of the directory of the global data files.
+pub const DATA_DIR: &str = "data";
+
+/// The extensions of the data files.
+pub const DATA_EXTENSIONS: [&str; 5] =
+    ["json", "toml", "yaml", "yml", "csv"];
+
+/// Returns the location of the data directory for a content directory.
+///
+/// Like the site configuration, the data directory lives next to the
+/// content directory, so that its files are not picked up as pages.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::datafiles::data_path;
+/// use std::path::Path;
+///
+/// let path = data_path(Path::new("site/content"));
+/// assert_eq!(path, Path::new("site/data"));
+/// ```
+pub fn data_path(content_path: &Path) -> PathBuf {
+    content_path
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join(DATA_DIR)
+}
+
+/// Returns the data files of a data directory.
+///
+/// # Arguments
+///
+/// * `path` - The path to the data directory.
+///
+/// # Returns
+///
+/// The paths of the JSON, TOML, YAML and CSV files of the directory and
+/// its subdirectories, sorted. A missing directory has no data files.
+fn data_files(path: &Path) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut files = Vec::new();
+    if !path.is_dir() {
+        return Ok(files);
+    }
+
+    for entry in fs::read_dir(path)? {
+        let entry_path = entry?.path();
+        if entry_path.is_dir() {
+            files.extend(data_files(&entry_path)?);
+        } else if data_extension(&entry_path).is_some() {
+            files.push(entry_path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Loads the global data files of the site.
+///
+/// Every data file becomes a key of the data named after the file, and
+/// every subdirectory a nested object, so `data/team/members.yaml` is
+/// available to templates as `{{data.team.members}}`. Files of other
+/// types are ignored.
+///
+/// # Arguments
+///
+/// * `path` - The path to the data directory.
+///
+/// # Returns
+///
+/// A `Result` containing the data, which is empty when the directory
+/// does not exist, or an error if a data file cannot be read or parsed,
+/// or if two data files have the same key.
+pub fn load_data(
+    path: &Path,
+) -> Result<Map<String, Value>, Box<dyn Error>> {
+    let mut data = Map::new();
+
+    for file in data_files(path)? {
+        let extension = data_extension(&file).unwrap_or_default();
+        let content = fs::read_to_string(&file)?;
+        let value = parse_data(&content, extension).map_err(|e| {
+            format!("Error parsing data file {}: {}", file.display(), e)
+        })?;
+
+        let relative = file.strip_prefix(path)?.with_extension("");
+        let keys: Vec<String> = relative
+            .components()
+            .map(|component| {
+                component.as_os_str().to_string_lossy().to_string()
+            })
+            .collect();
+        insert_value(&mut data, &keys, value).map_err(|key| {
+            format!(
+                "Duplicate data key `{}` in data file {}",
+                key,
+                file.display()
+            )
+        })?;
+    }
+
+    Ok(data)
+}
+
+/// Parses the content of a data file.
+///
+/// # Arguments
+///
+/// * `content` - The content of the data file.
+/// * `extension` - The extension of the data file: `json`, `toml`,
+///   `yaml`, `yml` or `csv`.
+///
+/// # Returns
+///
+/// The data as a JSON value, or a message describing why the content
+/// could not be parsed. The rows of a CSV file become an array of
+/// objects keyed by the column headers.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::datafiles::parse_data;
+/// use serde_json::json;
+///
+/// let data = parse_data("name,price\nBasic,9\n", "csv").unwrap();
+/// assert_eq!(data, json!([{ "name": "Basic", "price": "9" }]));
+/// ```
+pub fn parse_data(
+    content: &str,
+    extension: &str,
+) -> Result<Value, String> {
+    match extension.to_lowercase().as_str() {
+        "json" => {
+            serde_json::from_str(content).map_err(|e| e.to_string())
+        }
+        "toml" => content
+            .parse::<TomlValue>()
+            .map(|value| toml_to_json(&value))
+            .map_err(|e| e.to_string()),
+        "yaml" | "yml" => YamlLoader::load_from_str(content)
+            .map(|docs| {
+                docs.first().map(yaml_to_json).unwrap_or_default()
+            })
+            .map_err(|e| e.to_string()),
+        "csv" => parse_csv(content),
+        other => Err(format!("Unsupported data format `{}`", other)),
+    }
+}
+
+/// Returns the extension of a data file, if it is one.
+fn data_extension(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_lowercase();
+    DATA_EXTENSIONS
+        .iter()
+        .find(|candidate| **candidate == extension)
+        .copied()
+}
+
+/// Inserts a value at a path of keys, creating the nested objects.
+///
+/// Returns the conflicting key when the path is already taken.
+fn insert_value(
+    data: &mut Map<String, Value>,
+    keys: &[String],
+    value: Value,
+) -> Result<(), String> {
+    let Some((last, parents)) = keys.split_last() else {
+        return Ok(());
+    };
+
+    let mut current = data;
+    for key in parents {
+        current = match current
+            .entry(key.clone())
+            .or_insert_with(|| Value::Object(Map::new()))
+        {
+            Value::Object(map) => map,
+            _ => return Err(key.clone()),
+        };
+    }
+    if current.contains_key(last) {
+        return Err(last.clone());
+    }
+    let _ = current.insert(last.clone(), value);
+    Ok(())
+}
+
+/// Converts a TOML value to a JSON value.
+fn toml_to_json(value: &TomlValue) -> Value {
+    match value {
+        TomlValue::String(s) => Value::String(s.clone()),
+        TomlValue::Integer(i) => Value::from(*i),
+        TomlValue::Float(f) => {
+            Number::from_f64(*f).map(Value::Number).unwrap_or_default()
+        }
+        TomlValue::Boolean(b) => Value::Bool(*b),
+        TomlValue::Datetime(d) => Value::String(d.to_string()),
+        TomlValue::Array(items) => {
+            Value::Array(items.iter().map(toml_to_json).collect())
+        }
+        TomlValue::Table(table) => Value::Object(
+            table
+                .iter()
+                .map(|(k, v)| (k.clone(), toml_to_json(v)))
+                .collect(),
+        ),
+    }
+}
+
+/// Converts a YAML value to a JSON value.
+fn yaml_to_json(value: &Yaml) -> Value {
+    match value {
+        Yaml::String(s) => Value::String(s.clone()),
+        Yaml::Integer(i) => Value::from(*i),
+        Yaml::Real(s) => s
+            .parse::<f64>()
+            .ok()
+            .and_then(Number::from_f64)
+            .map(Value::Number)
+            .unwrap_or_else(|| Value::String(s.clone())),
+        Yaml::Boolean(b) => Value::Bool(*b),
+        Yaml::Array(items) => {
+            Value::Array(items.iter().map(yaml_to_json).collect())
+        }
+        Yaml::Hash(hash) => Value::Object(
+            hash.iter()
+                .filter_map(|(k, v)| {
+                    let key = match k {
+                        Yaml::String(s) | Yaml::Real(s) => s.clone(),
+                        Yaml::Integer(i) => i.to_string(),
+                        Yaml::Boolean(b) => b.to_string(),
+                        _ => return None,
+                    };
+                    Some((key, yaml_to_json(v)))
+                })
+                .collect(),
+        ),
+        _ => Value::Null,
+    }
+}
+
+/// Parses CSV content into an array of objects keyed by the header.
+///
+/// Fields may be quoted with `"`, in which case they can contain
+/// commas, line breaks and doubled quotes. Empty lines are skipped.
+fn parse_csv(content: &str) -> Result<Value, String> {
+    let mut records: Vec<Vec<String>> = Vec::new();
+    let mut record: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                field.push('"');
+                let _ = chars.next();
+            }
+            (true, '"') => quoted = false,
+            (true, _) => field.push(c),
+            (false, '"') if field.is_empty() => quoted = true,
+            (false, ',') => record.push(std::mem::take(&mut field)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            (false, _) => field.push(c),
+        }
+    }
+    if quoted {
+        return Err("Unclosed quoted field".to_string());
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records.retain(|record| {
+        !(record.len() == 1 && record[0].trim().is_empty())
+    });
+
+    let mut rows = records.into_iter();
+    let header: Vec<String> = rows
+        .next()
+        .unwrap_or_default()
+        .iter()
+        .map(|name| name.trim().to_string())
+        .collect();
+    Ok(Value::Array(
+        rows.map(|row| {
+            Value::Object(
+                header
+                    .iter()
+                    .enumerate()
+                    .map(|(index, name)| {
+                        (
+                            name.clone(),
+                            Value::String(
+                                row.get(index)
+                                    .cloned()
+                                    .unwrap_or_default(),
+                            ),
+                        )
+                    })
+                    .collect(),
+            )
+        })
+        .collect(),
+    ))
+}
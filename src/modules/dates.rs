@@ -0,0 +1,767 @@
+// Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::models::data::PageDate;
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// The front matter fields holding dates.
+pub const DATE_FIELDS: [&str; 7] = [
+    "date",
+    "pub_date",
+    "last_build_date",
+    "item_pub_date",
+    "publish_date",
+    "expiry_date",
+    "lastmod",
+];
+
+/// The English abbreviations of the months, as used by RFC 2822.
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep",
+    "Oct", "Nov", "Dec",
+];
+
+/// The English abbreviations of the days of the week, from Sunday.
+const WEEKDAYS: [&str; 7] =
+    ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+lazy_static::lazy_static! {
+    /// Matches the ISO 8601 and RFC 3339 formats.
+    static ref ISO_DATE: Regex = Regex::new(
+        r"^(\d{4})[-/](\d{1,2})[-/](\d{1,2})(?:[T ](\d{1,2}):(\d{2})(?::(\d{2})(?:\.\d+)?)?\s*(Z|[+-]\d{2}(?::?\d{2}){0,2}|[A-Za-z]{1,4})?)?$",
+    )
+    .unwrap();
+}
+
+/// Parses a timezone into an offset from UTC, in minutes.
+///
+/// Understands `Z`, `UTC`, `GMT`, offsets such as `+02:00`, `-0500`,
+/// `+02` and `+02:00:00` (whose seconds are ignored), and the North American and European abbreviations.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::dates::parse_offset;
+///
+/// assert_eq!(parse_offset("+02:00"), Some(120));
+/// assert_eq!(parse_offset("-0530"), Some(-330));
+/// assert_eq!(parse_offset("PST"), Some(-480));
+/// assert_eq!(parse_offset("Mars/Olympus"), None);
+/// ```
+pub fn parse_offset(timezone: &str) -> Option<i32> {
+    let timezone = timezone.trim();
+    let offset = match timezone.to_uppercase().as_str() {
+        "Z" | "UT" | "UTC" | "GMT" => 0,
+        "EST" => -300,
+        "EDT" => -240,
+        "CST" => -360,
+        "CDT" => -300,
+        "MST" => -420,
+        "MDT" => -360,
+        "PST" => -480,
+        "PDT" => -420,
+        "WET" => 0,
+        "BST" | "CET" | "WEST" => 60,
+        "CEST" | "EET" => 120,
+        "EEST" => 180,
+        _ => {
+            let sign = match timezone.chars().next()? {
+                '+' => 1,
+                '-' => -1,
+                _ => return None,
+            };
+            let digits = timezone[1..].replace(':', "");
+            if !digits.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            let (hours, minutes) = match digits.len() {
+                2 => (digits.parse::<i32>().ok()?, 0),
+                4 | 6 => (
+                    digits[..2].parse::<i32>().ok()?,
+                    digits[2..4].parse::<i32>().ok()?,
+                ),
+                _ => return None,
+            };
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+            sign * (hours * 60 + minutes)
+        }
+    };
+    Some(offset)
+}
+
+/// Parses the timezone of the site configuration into an offset from
+/// UTC, in minutes.
+///
+/// Only fixed offsets and the abbreviations understood by
+/// `parse_offset` are supported. IANA names such as `Europe/Paris` are
+/// rejected, as their offset changes with daylight saving time.
+///
+/// # Arguments
+///
+/// * `timezone` - The `timezone` of the site configuration.
+///
+/// # Returns
+///
+/// The offset, `0` when no timezone is set, or an error naming the
+/// unsupported timezone.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::dates::site_offset;
+///
+/// assert_eq!(site_offset("+02:00"), Ok(120));
+/// assert_eq!(site_offset(""), Ok(0));
+/// assert!(site_offset("Europe/Paris").is_err());
+/// ```
+pub fn site_offset(timezone: &str) -> Result<i32, String> {
+    if timezone.trim().is_empty() {
+        return Ok(0);
+    }
+    parse_offset(timezone).ok_or_else(|| {
+        let hint = if timezone.contains('/') {
+            "IANA timezone names are not supported, "
+        } else {
+            ""
+        };
+        format!(
+            "Unknown timezone `{}` in the site configuration: {}use a \
+             fixed offset such as `+02:00` or an abbreviation such as \
+             `CET`",
+            timezone, hint
+        )
+    })
+}
+
+/// Parses a date from the front matter.
+///
+/// Understands ISO 8601 and RFC 3339 (`2023-07-12`,
+/// `2023-07-12T09:30:00+02:00`), RFC 2822 (`Wed, 12 Jul 2023 09:30:00
+/// GMT`) and common human formats (`July 12, 2023`, `12 July 2023`,
+/// `2023/07/12`). Dates without a timezone are in `default_offset`.
+///
+/// # Arguments
+///
+/// * `text` - The date as written in the front matter.
+/// * `default_offset` - The offset from UTC of the site, in minutes.
+///
+/// # Returns
+///
+/// The parsed `PageDate`, or `None` if the text is not a valid date.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::dates::parse_date;
+///
+/// let date = parse_date("July 12, 2023", 0).unwrap();
+/// assert_eq!((date.year, date.month, date.day), (2023, 7, 12));
+/// assert!(parse_date("February 30, 2023", 0).is_none());
+/// ```
+pub fn parse_date(text: &str, default_offset: i32) -> Option<PageDate> {
+    let text = text.trim();
+    let date = parse_iso(text, default_offset)
+        .or_else(|| parse_text(text, default_offset))?;
+    let valid = (1..=12).contains(&date.month)
+        && date.day >= 1
+        && date.day <= days_in_month(date.year, date.month)
+        && date.hour < 24
+        && date.minute < 60
+        && date.second < 61;
+    valid.then_some(date)
+}
+
+/// Returns a sortable `(year, month, day)` key for a front matter date.
+///
+/// # Arguments
+///
+/// * `text` - The date as written in the front matter.
+///
+/// # Returns
+///
+/// The calendar date, or `None` if the text is not a valid date.
+pub fn date_key(text: &str) -> Option<(i32, u32, u32)> {
+    parse_date(text, 0).map(|date| (date.year, date.month, date.day))
+}
+
+/// Rewrites a date in another format.
+///
+/// # Arguments
+///
+/// * `text` - The date as written in the front matter.
+/// * `default_offset` - The offset from UTC of the site, in minutes.
+/// * `format` - The function formatting the parsed date.
+///
+/// # Returns
+///
+/// The formatted date, or the original text if it cannot be parsed.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::dates::{normalize_date, to_rfc2822};
+///
+/// assert_eq!(
+///     normalize_date("July 12, 2023", 0, to_rfc2822),
+///     "Wed, 12 Jul 2023 00:00:00 +0000"
+/// );
+/// assert_eq!(normalize_date("soon", 0, to_rfc2822), "soon");
+/// ```
+pub fn normalize_date(
+    text: &str,
+    default_offset: i32,
+    format: fn(&PageDate) -> String,
+) -> String {
+    parse_date(text, default_offset)
+        .map(|date| format(&date))
+        .unwrap_or_else(|| text.to_string())
+}
+
+/// Formats a date for sitemaps, in the W3C Datetime format.
+///
+/// Dates written without a time of day keep the `YYYY-MM-DD` form.
+pub fn to_w3c(date: &PageDate) -> String {
+    if date.has_time {
+        to_rfc3339(date)
+    } else {
+        format!("{:04}-{:02}-{:02}", date.year, date.month, date.day)
+    }
+}
+
+/// Formats a date for RSS feeds, in the RFC 2822 format (e.g.
+/// `Wed, 12 Jul 2023 09:30:00 +0200`).
+pub fn to_rfc2822(date: &PageDate) -> String {
+    format!(
+        "{}, {:02} {} {:04} {:02}:{:02}:{:02} {}",
+        WEEKDAYS[weekday(date.year, date.month, date.day)],
+        date.day,
+        MONTHS[date.month as usize - 1],
+        date.year,
+        date.hour,
+        date.minute,
+        date.second,
+        format_offset(date.offset, false)
+    )
+}
+
+/// Formats a date for Atom feeds and JSON-LD, in the RFC 3339 format
+/// (e.g. `2023-07-12T09:30:00+02:00`).
+pub fn to_rfc3339(date: &PageDate) -> String {
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}",
+        date.year,
+        date.month,
+        date.day,
+        date.hour,
+        date.minute,
+        date.second,
+        format_offset(date.offset, true)
+    )
+}
+
+/// Formats a date for display.
+///
+/// The format uses `%Y` (year), `%m` and `%d` (zero-padded month and
+/// day), `%e` (day), `%B` and `%b` (month name and abbreviation), `%A`
+/// and `%a` (weekday name and abbreviation), `%H`, `%M` and `%S` (time)
+/// and `%%`. Names are in the given language, or in English when the
+/// language is not known. An empty format selects the customary format
+/// of the language.
+///
+/// # Arguments
+///
+/// * `date` - The date to format.
+/// * `format` - The format.
+/// * `language` - The language code (e.g. `fr` or `en-GB`).
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::dates::{format_date, parse_date};
+///
+/// let date = parse_date("2023-07-12", 0).unwrap();
+/// assert_eq!(format_date(&date, "", "en"), "July 12, 2023");
+/// assert_eq!(format_date(&date, "", "fr"), "12 juillet 2023");
+/// assert_eq!(format_date(&date, "%A %d/%m/%Y", "de"), "Mittwoch 12/07/2023");
+/// ```
+pub fn format_date(
+    date: &PageDate,
+    format: &str,
+    language: &str,
+) -> String {
+    let names = locale(language);
+    let format = if format.is_empty() {
+        names.format
+    } else {
+        format
+    };
+    let weekday = weekday(date.year, date.month, date.day);
+    let month = date.month as usize - 1;
+
+    let mut output = String::new();
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            output.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('Y') => output.push_str(&date.year.to_string()),
+            Some('m') => output.push_str(&format!("{:02}", date.month)),
+            Some('d') => output.push_str(&format!("{:02}", date.day)),
+            Some('e') => output.push_str(&date.day.to_string()),
+            Some('B') => output.push_str(names.months[month]),
+            Some('b') => {
+                output.push_str(&abbreviate(names.months[month]))
+            }
+            Some('A') => output.push_str(names.weekdays[weekday]),
+            Some('a') => {
+                output.push_str(&abbreviate(names.weekdays[weekday]))
+            }
+            Some('H') => output.push_str(&format!("{:02}", date.hour)),
+            Some('M') => {
+                output.push_str(&format!("{:02}", date.minute))
+            }
+            Some('S') => {
+                output.push_str(&format!("{:02}", date.second))
+            }
+            Some('%') => output.push('%'),
+            Some(other) => {
+                output.push('%');
+                output.push(other);
+            }
+            None => output.push('%'),
+        }
+    }
+    output
+}
+
+/// Returns the dates of the front matter in every output format.
+///
+/// # Arguments
+///
+/// * `metadata` - The front matter of the page.
+/// * `default_offset` - The offset from UTC of the site, in minutes.
+/// * `format` - The display format, as accepted by `format_date`.
+/// * `language` - The language of the page.
+///
+/// # Returns
+///
+/// A JSON object mapping each parsed date field to its `iso`
+/// (RFC 3339), `w3c`, `rfc2822` and `display` forms.
+pub fn date_data(
+    metadata: &HashMap<String, String>,
+    default_offset: i32,
+    format: &str,
+    language: &str,
+) -> Value {
+    let mut dates = Map::new();
+    for field in DATE_FIELDS {
+        let Some(date) = metadata
+            .get(field)
+            .and_then(|value| parse_date(value, default_offset))
+        else {
+            continue;
+        };
+        dates.insert(
+            field.to_string(),
+            serde_json::json!({
+                "iso": to_rfc3339(&date),
+                "w3c": to_w3c(&date),
+                "rfc2822": to_rfc2822(&date),
+                "display": format_date(&date, format, language),
+            }),
+        );
+    }
+    Value::Object(dates)
+}
+
+/// Returns a warning for each date of the front matter that cannot be
+/// parsed.
+///
+/// # Arguments
+///
+/// * `file_name` - The name of the content file.
+/// * `metadata` - The front matter of the page.
+///
+/// # Returns
+///
+/// The warning messages, in field order.
+pub fn check_dates(
+    file_name: &str,
+    metadata: &HashMap<String, String>,
+) -> Vec<String> {
+    DATE_FIELDS
+        .iter()
+        .filter_map(|field| {
+            let value = metadata.get(*field)?;
+            if value.trim().is_empty() || parse_date(value, 0).is_some()
+            {
+                None
+            } else {
+                Some(format!(
+                    "Unparseable date `{}` in the `{}` field of `{}`",
+                    value, field, file_name
+                ))
+            }
+        })
+        .collect()
+}
+
+/// Parses the ISO 8601 and RFC 3339 formats.
+fn parse_iso(text: &str, default_offset: i32) -> Option<PageDate> {
+    let caps = ISO_DATE.captures(text)?;
+    let number = |index: usize| {
+        caps.get(index)
+            .and_then(|value| value.as_str().parse::<u32>().ok())
+    };
+
+    let offset = match caps.get(7) {
+        Some(zone) => parse_offset(zone.as_str())?,
+        None => default_offset,
+    };
+    Some(PageDate {
+        year: caps[1].parse().ok()?,
+        month: number(2)?,
+        day: number(3)?,
+        hour: number(4).unwrap_or_default(),
+        minute: number(5).unwrap_or_default(),
+        second: number(6).unwrap_or_default(),
+        offset,
+        has_time: caps.get(4).is_some(),
+    })
+}
+
+/// Parses RFC 2822 and human formats, made of a day, a month name and a
+/// year in either order, optionally followed by a time and a timezone.
+fn parse_text(text: &str, default_offset: i32) -> Option<PageDate> {
+    let words: Vec<&str> = text
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    let (index, (year, month, day)) =
+        words.windows(3).enumerate().find_map(|(index, window)| {
+            let date = match (
+                parse_day(window[0]),
+                month_number(window[1]),
+                parse_day(window[1]),
+                month_number(window[0]),
+                window[2].parse::<i32>(),
+            ) {
+                (Some(day), Some(month), _, _, Ok(year))
+                | (_, _, Some(day), Some(month), Ok(year)) => {
+                    (year, month, day)
+                }
+                _ => return None,
+            };
+            Some((index, date))
+        })?;
+
+    let mut date = PageDate {
+        year,
+        month,
+        day,
+        offset: default_offset,
+        ..Default::default()
+    };
+
+    let mut rest = words[index + 3..].iter();
+    if let Some(time) = rest.next() {
+        let parts: Vec<&str> = time.split(':').collect();
+        let numbers: Vec<u32> =
+            parts.iter().filter_map(|part| part.parse().ok()).collect();
+        if parts.len() < 2 || numbers.len() != parts.len() {
+            return None;
+        }
+        date.hour = numbers[0];
+        date.minute = numbers[1];
+        date.second = numbers.get(2).copied().unwrap_or_default();
+        date.has_time = true;
+
+        if let Some(zone) = rest.next() {
+            date.offset = parse_offset(zone)?;
+        }
+    }
+    Some(date)
+}
+
+/// Parses a day of the month, allowing an English ordinal suffix.
+fn parse_day(word: &str) -> Option<u32> {
+    let digits = word.trim_end_matches(['s', 't', 'n', 'd', 'r', 'h']);
+    if digits.len() > 2 {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+/// Returns the number of a month from its English name or abbreviation.
+fn month_number(word: &str) -> Option<u32> {
+    let word = word.trim_end_matches('.').to_lowercase();
+    if word.len() < 3 {
+        return None;
+    }
+    const NAMES: [&str; 12] = [
+        "january",
+        "february",
+        "march",
+        "april",
+        "may",
+        "june",
+        "july",
+        "august",
+        "september",
+        "october",
+        "november",
+        "december",
+    ];
+    NAMES
+        .iter()
+        .position(|name| {
+            name.starts_with(&word)
+                || (word == "sept" && *name == "september")
+        })
+        .map(|index| index as u32 + 1)
+}
+
+/// Returns whether a year is a leap year.
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// Returns the number of days of a month.
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the day of the week of a date, from 0 for Sunday.
+fn weekday(year: i32, month: u32, day: u32) -> usize {
+    const OFFSETS: [i32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+    let year = if month < 3 { year - 1 } else { year };
+    let day = year + year.div_euclid(4) - year.div_euclid(100)
+        + year.div_euclid(400)
+        + OFFSETS[month as usize - 1]
+        + day as i32;
+    day.rem_euclid(7) as usize
+}
+
+/// Formats an offset from UTC, as `+02:00` or `+0200`.
+fn format_offset(offset: i32, colon: bool) -> String {
+    let sign = if offset < 0 { '-' } else { '+' };
+    let offset = offset.abs();
+    if colon {
+        format!("{}{:02}:{:02}", sign, offset / 60, offset % 60)
+    } else {
+        format!("{}{:02}{:02}", sign, offset / 60, offset % 60)
+    }
+}
+
+/// Returns the first three characters of a name.
+fn abbreviate(name: &str) -> String {
+    name.chars().take(3).collect()
+}
+
+/// The month and weekday names and the customary date format of a
+/// language.
+struct Locale {
+    months: [&'static str; 12],
+    weekdays: [&'static str; 7],
+    format: &'static str,
+}
+
+/// Returns the names of a language, falling back to English.
+fn locale(language: &str) -> Locale {
+    let language = language
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    match language.as_str() {
+        "fr" => Locale {
+            months: [
+                "janvier",
+                "février",
+                "mars",
+                "avril",
+                "mai",
+                "juin",
+                "juillet",
+                "août",
+                "septembre",
+                "octobre",
+                "novembre",
+                "décembre",
+            ],
+            weekdays: [
+                "dimanche", "lundi", "mardi", "mercredi", "jeudi",
+                "vendredi", "samedi",
+            ],
+            format: "%e %B %Y",
+        },
+        "de" => Locale {
+            months: [
+                "Januar",
+                "Februar",
+                "März",
+                "April",
+                "Mai",
+                "Juni",
+                "Juli",
+                "August",
+                "September",
+                "Oktober",
+                "November",
+                "Dezember",
+            ],
+            weekdays: [
+                "Sonntag",
+                "Montag",
+                "Dienstag",
+                "Mittwoch",
+                "Donnerstag",
+                "Freitag",
+                "Samstag",
+            ],
+            format: "%e. %B %Y",
+        },
+        "es" => Locale {
+            months: [
+                "enero",
+                "febrero",
+                "marzo",
+                "abril",
+                "mayo",
+                "junio",
+                "julio",
+                "agosto",
+                "septiembre",
+                "octubre",
+                "noviembre",
+                "diciembre",
+            ],
+            weekdays: [
+                "domingo",
+                "lunes",
+                "martes",
+                "miércoles",
+                "jueves",
+                "viernes",
+                "sábado",
+            ],
+            format: "%e de %B de %Y",
+        },
+        "it" => Locale {
+            months: [
+                "gennaio",
+                "febbraio",
+                "marzo",
+                "aprile",
+                "maggio",
+                "giugno",
+                "luglio",
+                "agosto",
+                "settembre",
+                "ottobre",
+                "novembre",
+                "dicembre",
+            ],
+            weekdays: [
+                "domenica",
+                "lunedì",
+                "martedì",
+                "mercoledì",
+                "giovedì",
+                "venerdì",
+                "sabato",
+            ],
+            format: "%e %B %Y",
+        },
+        "pt" => Locale {
+            months: [
+                "janeiro",
+                "fevereiro",
+                "março",
+                "abril",
+                "maio",
+                "junho",
+                "julho",
+                "agosto",
+                "setembro",
+                "outubro",
+                "novembro",
+                "dezembro",
+            ],
+            weekdays: [
+                "domingo",
+                "segunda-feira",
+                "terça-feira",
+                "quarta-feira",
+                "quinta-feira",
+                "sexta-feira",
+                "sábado",
+            ],
+            format: "%e de %B de %Y",
+        },
+        "nl" => Locale {
+            months: [
+                "januari",
+                "februari",
+                "maart",
+                "april",
+                "mei",
+                "juni",
+                "juli",
+                "augustus",
+                "september",
+                "oktober",
+                "november",
+                "december",
+            ],
+            weekdays: [
+                "zondag",
+                "maandag",
+                "dinsdag",
+                "woensdag",
+                "donderdag",
+                "vrijdag",
+                "zaterdag",
+            ],
+            format: "%e %B %Y",
+        },
+        _ => Locale {
+            months: [
+                "January",
+                "February",
+                "March",
+                "April",
+                "May",
+                "June",
+                "July",
+                "August",
+                "September",
+                "October",
+                "November",
+                "December",
+            ],
+            weekdays: [
+                "Sunday",
+                "Monday",
+                "Tuesday",
+                "Wednesday",
+                "Thursday",
+                "Friday",
+                "Saturday",
+            ],
+            format: "%B %e, %Y",
+        },
+    }
+}
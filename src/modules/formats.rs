@@ -0,0 +1,264 @@
+// Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::models::data::FileData;
+use crate::modules::cascade::is_defaults_file;
+use crate::modules::datafiles::parse_data;
+use crate::utilities::directory::extract_front_matter;
+use serde_json::{Map, Value};
+use std::{collections::HashMap, path::Path};
+
+/// The extension of the content files written in Markdown.
+pub const MARKDOWN_PAGE_EXTENSION: &str = "md";
+
+/// The extensions of the content files written in a data format.
+pub const DATA_PAGE_EXTENSIONS: [&str; 4] =
+    ["json", "toml", "yaml", "yml"];
+
+/// The field holding the body of a data content file.
+pub const BODY_FIELD: &str = "content";
+
+/// The field holding the front matter of a JSON content file exported
+/// with separate `frontmatter` and `content` keys.
+pub const FRONT_MATTER_FIELD: &str = "frontmatter";
+
+/// The extensions of the content files written in HTML.
+pub const HTML_PAGE_EXTENSIONS: [&str; 2] = ["html", "htm"];
+
+/// The front matter field that turns off the layout of an HTML page.
+pub const RENDER_FIELD: &str = "render";
+
+/// Returns whether a content file is a page, in Markdown, HTML or a data
+/// format, rather than an asset.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::formats::is_content_page;
+///
+/// assert!(is_content_page("launch/index.md"));
+/// assert!(is_content_page("landing.html"));
+/// assert!(!is_content_page("launch/hero.png"));
+/// ```
+pub fn is_content_page(name: &str) -> bool {
+    let markdown = Path::new(name)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            extension.eq_ignore_ascii_case(MARKDOWN_PAGE_EXTENSION)
+        });
+    markdown || is_data_page(name) || is_html_page(name)
+}
+
+/// Returns whether a content file is written in a data format.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::formats::is_data_page;
+///
+/// assert!(is_data_page("products/widget.json"));
+/// assert!(is_data_page("about.yml"));
+/// assert!(!is_data_page("about.md"));
+/// ```
+pub fn is_data_page(name: &str) -> bool {
+    Path::new(name)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            DATA_PAGE_EXTENSIONS
+                .contains(&extension.to_lowercase().as_str())
+        })
+}
+
+/// Returns whether a content file is written in HTML.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::formats::is_html_page;
+///
+/// assert!(is_html_page("landing.html"));
+/// assert!(is_html_page("legacy/offer.HTM"));
+/// assert!(!is_html_page("landing.md"));
+/// ```
+pub fn is_html_page(name: &str) -> bool {
+    Path::new(name)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            HTML_PAGE_EXTENSIONS
+                .contains(&extension.to_lowercase().as_str())
+        })
+}
+
+/// Returns the body of an HTML page, after its front matter.
+///
+/// The body is used as is: it is neither converted from Markdown nor
+/// changed by the pre and post-processors of Markdown pages.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::formats::html_body;
+///
+/// let content = "---\ntitle: Landing\n---\n<section>Hi</section>";
+/// assert_eq!(html_body(content), "<section>Hi</section>");
+/// ```
+pub fn html_body(content: &str) -> &str {
+    extract_front_matter(content)
+}
+
+/// Returns whether an HTML page is wrapped in its layout.
+///
+/// An HTML page with `render: false` in its front matter is written as
+/// is, without layout, so it must be a complete HTML document.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::formats::renders_layout;
+/// use std::collections::HashMap;
+///
+/// let mut metadata = HashMap::new();
+/// assert!(renders_layout(&metadata));
+///
+/// metadata.insert("render".to_string(), "false".to_string());
+/// assert!(!renders_layout(&metadata));
+/// ```
+pub fn renders_layout(metadata: &HashMap<String, String>) -> bool {
+    !metadata.get(RENDER_FIELD).is_some_and(|render| {
+        render.trim().eq_ignore_ascii_case("false")
+    })
+}
+
+/// Parses a content file written in a data format.
+///
+/// The file is a single JSON, TOML or YAML object. The `content` field
+/// holds the Markdown body of the page, which may contain HTML, and the
+/// other fields are its front matter. A JSON object may also keep the
+/// front matter apart in a `frontmatter` object.
+///
+/// # Arguments
+///
+/// * `name` - The name of the content file, whose extension gives its
+///   format.
+/// * `content` - The content of the file.
+///
+/// # Returns
+///
+/// The front matter and body of the page, or an error if the file is
+/// not a valid object.
+pub fn parse_data_page(
+    name: &str,
+    content: &str,
+) -> Result<(Map<String, Value>, String), String> {
+    let extension = Path::new(name)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default();
+    let Value::Object(mut fields) = parse_data(content, extension)
+        .map_err(|e| format!("Error parsing {}: {}", name, e))?
+    else {
+        return Err(format!("Error parsing {}: not an object", name));
+    };
+
+    let body = match fields.remove(BODY_FIELD) {
+        Some(Value::String(body)) => body,
+        None | Some(Value::Null) => String::new(),
+        Some(_) => {
+            return Err(format!(
+                "Error parsing {}: `{}` is not a string",
+                name, BODY_FIELD
+            ))
+        }
+    };
+    let front_matter = match fields.remove(FRONT_MATTER_FIELD) {
+        Some(Value::Object(front_matter)) => front_matter,
+        Some(value) => {
+            let _ =
+                fields.insert(FRONT_MATTER_FIELD.to_string(), value);
+            fields
+        }
+        None => fields,
+    };
+    Ok((front_matter, body))
+}
+
+/// Returns the Markdown content of a page with a YAML front matter.
+///
+/// Every value is written in the JSON flow syntax, which is valid YAML,
+/// so nested values are kept. A page without front matter is only its
+/// body.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::formats::markdown_page;
+/// use serde_json::json;
+///
+/// let front_matter = json!({ "title": "Hello", "weight": 2 });
+/// assert_eq!(
+///     markdown_page(front_matter.as_object().unwrap(), "Body"),
+///     "---\ntitle: \"Hello\"\nweight: 2\n---\n\nBody"
+/// );
+/// ```
+pub fn markdown_page(
+    front_matter: &Map<String, Value>,
+    body: &str,
+) -> String {
+    let lines: Vec<String> = front_matter
+        .iter()
+        .map(|(key, value)| {
+            let simple = !key.is_empty()
+                && key.chars().all(|c| {
+                    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+                });
+            let key = if simple {
+                key.clone()
+            } else {
+                Value::String(key.clone()).to_string()
+            };
+            format!("{}: {}", key, value)
+        })
+        .collect();
+
+    if lines.is_empty() {
+        body.to_string()
+    } else {
+        format!("---\n{}\n---\n\n{}", lines.join("\n"), body)
+    }
+}
+
+/// Converts the content files written in a data format to Markdown
+/// pages.
+///
+/// JSON, TOML and YAML content files become pages with the front matter
+/// and body described in `parse_data_page`, so they go through the same
+/// steps as Markdown pages. Other content files, and the `_defaults.yml`
+/// files of the front matter defaults, are left unchanged.
+///
+/// # Arguments
+///
+/// * `files` - The content files.
+///
+/// # Returns
+///
+/// The content files, or an error naming the first data content file
+/// that cannot be parsed.
+pub fn normalize_pages(
+    files: Vec<FileData>,
+) -> Result<Vec<FileData>, String> {
+    files
+        .into_iter()
+        .map(|mut file| {
+            if is_data_page(&file.name) && !is_defaults_file(&file.name)
+            {
+                let (front_matter, body) =
+                    parse_data_page(&file.name, &file.content)?;
+                file.content = markdown_page(&front_matter, &body);
+            }
+            Ok(file)
+        })
+        .collect()
+}
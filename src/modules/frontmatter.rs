@@ -4,7 +4,7 @@
 use serde_json::{Map, Value as JsonValue};
 use std::collections::HashMap;
 use toml::Value as TomlValue;
-use yaml_rust::YamlLoader;
+use yaml_rust::{Yaml, YamlLoader};
 
 /// ## Function: `extract` - Extracts front matter from a string of content
 ///
@@ -31,7 +31,7 @@ pub fn extract(content: &str) -> HashMap<String, String> {
     {
         if let Ok(doc) = parse_yaml_document(front_matter_str) {
             front_matter
-                .extend(parse_yaml_hash(doc.as_hash().unwrap()));
+                .extend(parse_yaml_values(doc.as_hash().unwrap()));
         }
     } else if let Some(front_matter_str) =
         extract_front_matter_str(content, "+++\n", "\n+++\n")
@@ -149,6 +149,53 @@ pub fn parse_yaml_hash(
     entries.into_iter().collect()
 }
 
+/// ## Function: `parse_yaml_values` - Parses a YAML hash into a `HashMap` of key-value pairs
+///
+/// Unlike `parse_yaml_hash`, which only keeps string values, this
+/// function also keeps integers, reals and booleans (e.g. `weight: 10`
+/// or `draft: true`) as their string representation, and joins lists
+/// of scalars (e.g. `menu: [main, footer]`) with `", "` so they read
+/// like the comma-separated `tags` and `keywords` values.
+///
+/// ### Arguments
+///
+/// * `yaml_hash` - The YAML hash to parse into a `HashMap` of key-value
+///   pairs
+///
+/// ### Returns
+///
+/// A `HashMap` of key-value pairs representing the YAML hash. Nested
+/// hashes and null values are skipped.
+///
+pub fn parse_yaml_values(
+    yaml_hash: &yaml_rust::yaml::Hash,
+) -> HashMap<String, String> {
+    yaml_hash
+        .iter()
+        .filter_map(|(k, v)| {
+            let key = k.as_str()?;
+            yaml_value_to_string(v).map(|value| (key.to_string(), value))
+        })
+        .collect()
+}
+
+/// Converts a scalar or a list of scalars to a front matter string.
+fn yaml_value_to_string(value: &Yaml) -> Option<String> {
+    match value {
+        Yaml::String(s) | Yaml::Real(s) => Some(s.to_string()),
+        Yaml::Integer(i) => Some(i.to_string()),
+        Yaml::Boolean(b) => Some(b.to_string()),
+        Yaml::Array(items) => Some(
+            items
+                .iter()
+                .filter_map(yaml_value_to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+        _ => None,
+    }
+}
+
 /// ## Function: `parse_toml_table` - Parses a TOML table into a `HashMap` of key-value pairs
 ///
 /// This function parses a TOML table into a `HashMap` of key-value pairs.
@@ -161,16 +208,82 @@ pub fn parse_yaml_hash(
 /// ### Returns
 ///
 /// A `HashMap` of key-value pairs representing the TOML table.
-/// If the TOML table is not valid, an error is returned.
+/// Integers, floats, booleans and dates are kept as their string
+/// representation and arrays of scalars are joined with `", "`.
+/// Nested tables are skipped.
 ///
 pub fn parse_toml_table(
     toml_table: &toml::value::Table,
 ) -> HashMap<String, String> {
     toml_table
         .iter()
-        .filter_map(
-            |(k, v)| v.as_str().map(|s| (k.to_string(), s.to_string()))
-        )
+        .filter_map(|(k, v)| {
+            toml_value_to_string(v).map(|s| (k.to_string(), s))
+        })
+        .collect()
+}
+
+/// Converts a scalar or an array of scalars to a front matter string.
+fn toml_value_to_string(value: &TomlValue) -> Option<String> {
+    match value {
+        TomlValue::String(s) => Some(s.to_string()),
+        TomlValue::Integer(i) => Some(i.to_string()),
+        TomlValue::Float(f) => Some(f.to_string()),
+        TomlValue::Boolean(b) => Some(b.to_string()),
+        TomlValue::Datetime(d) => Some(d.to_string()),
+        TomlValue::Array(items) => Some(
+            items
+                .iter()
+                .filter_map(toml_value_to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+        TomlValue::Table(_) => None,
+    }
+}
+
+/// ## Function: `split_list` - Splits a front matter list value
+///
+/// Front matter lists arrive either comma-separated (`"main, footer"`,
+/// the form produced for YAML and TOML lists) or as a JSON array
+/// (`["main","footer"]`, the form produced by `parse_json_object`).
+/// This function accepts both and returns the trimmed, non-empty items.
+///
+/// ### Arguments
+///
+/// * `value` - The front matter value to split.
+///
+/// ### Returns
+///
+/// A `Vec` of the list items.
+///
+/// ### Examples
+///
+/// ```rust
+/// use ssg::modules::frontmatter::split_list;
+///
+/// assert_eq!(split_list("main, footer"), vec!["main", "footer"]);
+/// assert_eq!(split_list(r#"["main","footer"]"#), vec!["main", "footer"]);
+/// ```
+///
+pub fn split_list(value: &str) -> Vec<String> {
+    let value = value.trim();
+    if value.starts_with('[') {
+        if let Ok(items) = serde_json::from_str::<Vec<JsonValue>>(value) {
+            return items
+                .iter()
+                .map(|item| match item {
+                    JsonValue::String(s) => s.trim().to_string(),
+                    other => other.to_string(),
+                })
+                .filter(|item| !item.is_empty())
+                .collect();
+        }
+    }
+    value
+        .split(',')
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
         .collect()
 }
 /// ## Function: `extract_json_object_str` - Extracts a JSON object from a string of content
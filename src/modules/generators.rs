@@ -0,0 +1,227 @@
+// Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::models::data::{FileData, PageGenerator, SiteConfig};
+use crate::modules::frontmatter::extract;
+use crate::modules::permalinks::slugify;
+use crate::modules::series::generated_page;
+use serde_json::Value;
+use std::{collections::HashMap, path::Path};
+
+/// Returns the value at a dotted path (e.g. `pricing.amount` or
+/// `items.0`), if any.
+pub fn lookup<'d>(value: &'d Value, path: &str) -> Option<&'d Value> {
+    path.split('.')
+        .filter(|segment| !segment.is_empty())
+        .try_fold(value, |current, segment| match current {
+            Value::Array(items) => {
+                items.get(segment.parse::<usize>().ok()?)
+            }
+            Value::Object(map) => map.get(segment),
+            _ => None,
+        })
+}
+
+/// Returns a record field as front matter text.
+///
+/// Lists of values are joined with `", "`, like the `tags` front matter,
+/// and a missing field is empty.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::generators::record_field;
+/// use serde_json::json;
+///
+/// let record = json!({ "price": 9, "tags": ["a", "b"] });
+/// assert_eq!(record_field(&record, "price"), "9");
+/// assert_eq!(record_field(&record, "tags"), "a, b");
+/// assert_eq!(record_field(&record, "name"), "");
+/// ```
+pub fn record_field(record: &Value, key: &str) -> String {
+    match lookup(record, key) {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|item| match item {
+                Value::String(text) => text.clone(),
+                _ => item.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        Some(value) => value.to_string(),
+    }
+}
+
+/// Returns the records of a page generator with the content file names
+/// of their pages.
+///
+/// # Arguments
+///
+/// * `name` - The name of the generator.
+/// * `generator` - The generator.
+/// * `data` - The global data of the site.
+///
+/// # Returns
+///
+/// The content file name and record of every page, in the order of the
+/// data, or an error if the data does not exist or if a record lacks a
+/// field of the path of its page.
+pub fn generator_records<'d>(
+    name: &str,
+    generator: &PageGenerator,
+    data: &'d Value,
+) -> Result<Vec<(String, &'d Value)>, String> {
+    let records: Vec<&Value> = match lookup(data, &generator.data) {
+        Some(Value::Array(items)) => items.iter().collect(),
+        Some(Value::Object(map)) => map.values().collect(),
+        _ => {
+            return Err(format!(
+                "Unknown data `{}` in the `{}` page generator",
+                generator.data, name
+            ))
+        }
+    };
+    let pattern = if generator.path.is_empty() {
+        format!("{}/:{}", name, generator.title)
+    } else {
+        generator.path.clone()
+    };
+
+    records
+        .into_iter()
+        .enumerate()
+        .map(|(index, record)| {
+            let segments = pattern
+                .split('/')
+                .filter(|segment| !segment.is_empty())
+                .map(|segment| match segment.strip_prefix(':') {
+                    Some(key) => {
+                        let slug = slugify(&record_field(record, key));
+                        if slug.is_empty() {
+                            Err(format!(
+                                "Record {} of the `{}` page generator has no `{}` field",
+                                index + 1,
+                                name,
+                                key
+                            ))
+                        } else {
+                            Ok(slug)
+                        }
+                    }
+                    None => Ok(segment.to_string()),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok((format!("{}.md", segments.join("/")), record))
+        })
+        .collect()
+}
+
+/// Generates the pages of the records of every page generator.
+///
+/// A generated page takes its title, description, body and other front
+/// matter fields from its record, and inherits the rest of its front
+/// matter from the closest `index.md` page, so it renders with the same
+/// template fields. A content file at the name of a generated page
+/// replaces it.
+///
+/// # Arguments
+///
+/// * `files` - The content files.
+/// * `config` - The site configuration.
+/// * `data` - The global data of the site.
+///
+/// # Returns
+///
+/// The Markdown content files of the generated pages, or an error if
+/// the records of a generator cannot be read.
+pub fn generate_data_files(
+    files: &[FileData],
+    config: &SiteConfig,
+    data: &Value,
+) -> Result<Vec<FileData>, String> {
+    let mut generated = Vec::new();
+
+    for (name, generator) in &config.generators {
+        for (file_name, record) in
+            generator_records(name, generator, data)?
+        {
+            if files.iter().any(|file| file.name == file_name) {
+                continue;
+            }
+
+            let title = record_field(record, &generator.title);
+            let description =
+                record_field(record, &generator.description);
+            let mut fields: Vec<(&str, String)> = vec![
+                ("title", title),
+                ("description", description.clone()),
+                ("item_description", description),
+                ("layout", generator.layout.clone()),
+            ];
+            for (key, record_key) in &generator.fields {
+                fields.push((key, record_field(record, record_key)));
+            }
+            let fields: Vec<(&str, &str)> = fields
+                .iter()
+                .map(|(key, value)| (*key, value.as_str()))
+                .collect();
+
+            generated.push(FileData {
+                content: generated_page(
+                    closest_index(files, &file_name),
+                    &fields,
+                    &record_field(record, &generator.content),
+                ),
+                name: file_name,
+                ..Default::default()
+            });
+        }
+    }
+    Ok(generated)
+}
+
+/// Returns the records of the generated pages, for templates.
+///
+/// # Arguments
+///
+/// * `config` - The site configuration.
+/// * `data` - The global data of the site.
+///
+/// # Returns
+///
+/// A map of the content file names of the generated pages to their
+/// record, or an error if the records of a generator cannot be read.
+pub fn record_data(
+    config: &SiteConfig,
+    data: &Value,
+) -> Result<HashMap<String, Value>, String> {
+    let mut records = HashMap::new();
+    for (name, generator) in &config.generators {
+        for (file_name, record) in
+            generator_records(name, generator, data)?
+        {
+            let _ = records.insert(file_name, record.clone());
+        }
+    }
+    Ok(records)
+}
+
+/// Returns the front matter of the closest `index.md` page of a content
+/// file, in its directory or one of its parents.
+fn closest_index(
+    files: &[FileData],
+    file_name: &str,
+) -> HashMap<String, String> {
+    Path::new(file_name)
+        .ancestors()
+        .skip(1)
+        .find_map(|directory| {
+            let index = directory.join("index.md");
+            let index = index.to_string_lossy().replace('\\', "/");
+            files.iter().find(|file| file.name == index)
+        })
+        .map(|file| extract(&file.content))
+        .unwrap_or_default()
+}
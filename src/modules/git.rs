@@ -0,0 +1,282 @@
+// Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::models::data::{FileData, GitConfig, GitInfo};
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+    process::Command,
+};
+
+/// Parses the output of `git log --format=%aI%x09%an`.
+///
+/// # Arguments
+///
+/// * `output` - One `date<TAB>author` line per commit, newest first.
+///
+/// # Returns
+///
+/// The `GitInfo` of the file, or `None` when there are no commits.
+pub fn parse_git_log(output: &str) -> Option<GitInfo> {
+    let commits: Vec<(&str, &str)> = output
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(date, author)| (date.trim(), author.trim()))
+        .filter(|(date, _)| !date.is_empty())
+        .collect();
+
+    let (modified, _) = commits.first()?;
+    let (created, _) = commits.last()?;
+    let mut contributors: Vec<String> = Vec::new();
+    for (_, author) in commits.iter().rev() {
+        if !author.is_empty()
+            && !contributors.iter().any(|c| c == author)
+        {
+            contributors.push(author.to_string());
+        }
+    }
+
+    Some(GitInfo {
+        created: created.to_string(),
+        modified: modified.to_string(),
+        contributors,
+    })
+}
+
+/// Reads the git history of a file, following renames.
+///
+/// # Arguments
+///
+/// * `path` - The path to the file.
+///
+/// # Returns
+///
+/// The `GitInfo` of the file, or `None` when the file is not tracked by
+/// git or git is not available.
+pub fn file_history(path: &Path) -> Option<GitInfo> {
+    let output =
+        git(path, &["log", "--follow", "--format=%aI%x09%an"])?;
+    parse_git_log(&output)
+}
+
+/// Parses the output of `git log --name-status --format=%x01%aI%x09%an`.
+///
+/// Renames are followed: the commits of a file before it was renamed
+/// are attributed to its current name.
+///
+/// # Arguments
+///
+/// * `output` - The commits, newest first, each a `\u{1}date<TAB>author`
+///   line followed by the `status<TAB>path` lines of its files.
+///
+/// # Returns
+///
+/// A map of the current paths of the files to their `GitInfo`.
+pub fn parse_git_history(output: &str) -> HashMap<String, GitInfo> {
+    let mut logs: HashMap<String, String> = HashMap::new();
+    let mut renamed: HashMap<String, String> = HashMap::new();
+    let mut commit = "";
+
+    for line in output.lines() {
+        if let Some(header) = line.strip_prefix('\u{1}') {
+            commit = header;
+            continue;
+        }
+        let fields: Vec<&str> = line.split('\t').collect();
+        let (status, old_path, path) = match fields.as_slice() {
+            [status, old_path, path] => {
+                (*status, Some(*old_path), *path)
+            }
+            [status, path] => (*status, None, *path),
+            _ => continue,
+        };
+        let current = renamed
+            .get(path)
+            .cloned()
+            .unwrap_or_else(|| path.to_string());
+        if let Some(old_path) =
+            old_path.filter(|_| status.starts_with('R'))
+        {
+            let _ =
+                renamed.insert(old_path.to_string(), current.clone());
+        }
+        let log = logs.entry(current).or_default();
+        log.push_str(commit);
+        log.push('\n');
+    }
+
+    logs.into_iter()
+        .filter_map(|(path, log)| Some((path, parse_git_log(&log)?)))
+        .collect()
+}
+
+/// Reads the git history of all the files of a directory at once.
+///
+/// # Arguments
+///
+/// * `dir` - The path to the directory.
+///
+/// # Returns
+///
+/// A map of the paths of the files, relative to `dir`, to their
+/// `GitInfo`, or `None` when the directory is not in a repository or
+/// git is not available.
+pub fn directory_history(
+    dir: &Path,
+) -> Option<HashMap<String, GitInfo>> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args([
+            "-c",
+            "core.quotePath=false",
+            "log",
+            "-M",
+            "--name-status",
+            "--relative",
+            "--format=%x01%aI%x09%an",
+            "--",
+            ".",
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(parse_git_history(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Returns the path of a directory relative to the root of its
+/// repository, which prefixes the repository path of its files.
+///
+/// # Arguments
+///
+/// * `dir` - The path to the directory.
+///
+/// # Returns
+///
+/// The path of the directory in the repository, ending with `/` unless
+/// it is the root, or `None` when the directory is not in a repository
+/// or git is not available.
+pub fn repository_prefix(dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(["rev-parse", "--show-prefix"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Returns the URL of the page editing a content file.
+///
+/// # Arguments
+///
+/// * `pattern` - The `edit_url` of the configuration.
+/// * `path` - The path of the file in the repository.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::git::edit_url;
+///
+/// assert_eq!(
+///     edit_url("https://github.com/org/site/edit/main/{path}", "content/about.md"),
+///     "https://github.com/org/site/edit/main/content/about.md"
+/// );
+/// ```
+pub fn edit_url(pattern: &str, path: &str) -> String {
+    pattern.replace("{path}", path)
+}
+
+/// Reads the git history of the content files, when enabled.
+///
+/// The history of the whole content directory is read with a single
+/// git command. Pages generated from data files have no history.
+///
+/// # Arguments
+///
+/// * `content_path` - The path to the content directory.
+/// * `files` - The content files.
+/// * `config` - The git settings.
+///
+/// # Returns
+///
+/// A map of content file names to their `GitInfo`. Files without
+/// history are left out.
+pub fn collect_git_info(
+    content_path: &Path,
+    files: &[FileData],
+    config: &GitConfig,
+) -> HashMap<String, GitInfo> {
+    if !config.dates && !config.contributors {
+        return HashMap::new();
+    }
+
+    let mut history =
+        directory_history(content_path).unwrap_or_default();
+    files
+        .iter()
+        .filter_map(|file| {
+            let info = history.remove(&file.name)?;
+            Some((file.name.clone(), info))
+        })
+        .collect()
+}
+
+/// Adds the information of the git history to the front matter of a
+/// page.
+///
+/// The `pub_date` and `item_pub_date` are set to the created date,
+/// `last_build_date` and `lastmod` to the last modified date, and
+/// `contributors` to the comma-separated authors. The fields set by the
+/// page's own front matter are kept, while the values of the front
+/// matter defaults are replaced.
+///
+/// # Arguments
+///
+/// * `metadata` - The front matter of the page.
+/// * `page_fields` - The fields set by the page's own front matter.
+/// * `info` - The git history of the page.
+/// * `config` - The git settings.
+pub fn apply_git_info(
+    metadata: &mut HashMap<String, String>,
+    page_fields: &HashSet<String>,
+    info: &GitInfo,
+    config: &GitConfig,
+) {
+    let mut set_default = |key: &str, value: String| {
+        let entry = metadata.entry(key.to_string()).or_default();
+        if !page_fields.contains(key) || entry.trim().is_empty() {
+            *entry = value;
+        }
+    };
+
+    if config.dates {
+        set_default("pub_date", info.created.clone());
+        set_default("item_pub_date", info.created.clone());
+        set_default("last_build_date", info.modified.clone());
+        set_default("lastmod", info.modified.clone());
+    }
+    if config.contributors && !info.contributors.is_empty() {
+        set_default("contributors", info.contributors.join(", "));
+    }
+}
+
+/// Runs a git command on a file from the directory of the file.
+fn git(path: &Path, args: &[&str]) -> Option<String> {
+    let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty());
+    let file_name = path.file_name()?;
+
+    let mut command = Command::new("git");
+    if let Some(dir) = dir {
+        let _ = command.current_dir(dir);
+    }
+    let output =
+        command.args(args).arg("--").arg(file_name).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
@@ -4,6 +4,7 @@
 use crate::{
     modules::{
         markdown::convert_markdown_to_html,
+        math::{extract_math, restore_math},
         postprocessor::post_process_html,
     },
     utilities::directory::{
@@ -93,9 +94,12 @@ pub fn generate_html(
     // Extract front matter from content
     let markdown_content = extract_front_matter(content);
 
+    // Set the LaTeX formulas aside, to convert them to MathML
+    let (markdown_content, formulas) = extract_math(markdown_content);
+
     // Preprocess content to update class attributes and image tags
     let processed_content =
-        preprocess_content(markdown_content, &class_regex, &img_regex)?;
+        preprocess_content(&markdown_content, &class_regex, &img_regex)?;
 
     // Convert Markdown to HTML
     let markdown_html =
@@ -119,6 +123,9 @@ pub fn generate_html(
     let header_tags = vec!["h1", "h2", "h3", "h4", "h5", "h6"];
     let html_string = process_headers(&processed_html, &header_tags, &id_regex);
 
+    // Put the MathML of the formulas back in place
+    let html_string = restore_math(&html_string, &formulas);
+
     // Construct the final HTML with JSON content if available
     let json_html = json_content.map_or_else(
         || "".to_string(),
@@ -0,0 +1,415 @@
+// Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::models::data::{
+    FileData, MenuItem, RssData, SiteConfig, Translation,
+};
+use crate::modules::authors::feed_item;
+use crate::modules::dates::date_key;
+use crate::modules::frontmatter::extract;
+use crate::modules::navigation::NavigationGenerator;
+use crate::modules::permalinks::{
+    collect_routes, resolve_path, resolve_routes, Routes,
+};
+use crate::modules::series::field;
+use serde_json::Value;
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+
+/// Returns the default language of the site.
+///
+/// # Arguments
+///
+/// * `config` - The site configuration.
+///
+/// # Returns
+///
+/// The `default_language` of the configuration, or else the first of
+/// its `languages` by weight, or an empty string for a site with a
+/// single language.
+pub fn default_language(config: &SiteConfig) -> String {
+    if !config.default_language.is_empty() {
+        return config.default_language.clone();
+    }
+    config
+        .languages
+        .iter()
+        .min_by_key(|(code, language)| (language.weight, *code))
+        .map(|(code, _)| code.clone())
+        .unwrap_or_default()
+}
+
+/// Splits the language of a page from its file name.
+///
+/// A page is in a language when its file name has the language code
+/// as a suffix (`about.fr.md`) or lives in a directory named after the
+/// language (`fr/about.md`). Only the configured languages are
+/// recognized; other pages are in the default language.
+///
+/// # Arguments
+///
+/// * `file_name` - The name of the content file.
+/// * `config` - The site configuration.
+///
+/// # Returns
+///
+/// A tuple of the language code and the translation key of the page,
+/// that is its file name without the language (e.g. `about.md`).
+pub fn split_language(
+    file_name: &str,
+    config: &SiteConfig,
+) -> (String, String) {
+    let name = file_name.replace('\\', "/");
+
+    if let Some((dir, rest)) = name.split_once('/') {
+        if config.languages.contains_key(dir) {
+            return (dir.to_string(), rest.to_string());
+        }
+    }
+
+    let path = Path::new(&name);
+    if let (Some(stem), Some(ext)) = (
+        path.file_stem().and_then(|stem| stem.to_str()),
+        path.extension().and_then(|ext| ext.to_str()),
+    ) {
+        if let Some((base, code)) = stem.rsplit_once('.') {
+            if config.languages.contains_key(code) {
+                let key = path
+                    .with_file_name(format!("{}.{}", base, ext))
+                    .to_string_lossy()
+                    .to_string();
+                return (code.to_string(), key);
+            }
+        }
+    }
+
+    (default_language(config), name)
+}
+
+/// Resolves the output paths of the pages of a multilingual site.
+///
+/// Each page is resolved from its translation key, so translations
+/// share their path. Pages in a language other than the default one are
+/// placed under a directory named after the language (e.g. `fr/about`).
+/// Without configured languages, this is `resolve_routes`.
+///
+/// # Arguments
+///
+/// * `files` - The content files.
+/// * `config` - The site configuration.
+///
+/// # Returns
+///
+/// The `Routes` of the pages, or an error as returned by
+/// `resolve_routes`.
+pub fn resolve_localized_routes(
+    files: &[FileData],
+    config: &SiteConfig,
+) -> Result<Routes, String> {
+    if config.languages.is_empty() {
+        return resolve_routes(files, &config.permalinks);
+    }
+
+    let default = default_language(config);
+    collect_routes(files, |file| {
+        let (language, key) = split_language(&file.name, config);
+        let path = resolve_path(
+            &key,
+            &extract(&file.content),
+            &config.permalinks,
+        )?;
+        Ok(match (language == default, path.is_empty()) {
+            (true, _) => path,
+            (false, true) => language,
+            (false, false) => format!("{}/{}", language, path),
+        })
+    })
+}
+
+/// Generates the translations of every page.
+///
+/// # Arguments
+///
+/// * `files` - The content files.
+/// * `routes` - The output paths of the pages.
+/// * `config` - The site configuration.
+///
+/// # Returns
+///
+/// A map of content file names to the translations of the page,
+/// including the page itself, ordered by language weight. Pages of a
+/// site without configured languages have no translations.
+pub fn generate_translations(
+    files: &[FileData],
+    routes: &Routes,
+    config: &SiteConfig,
+) -> HashMap<String, Vec<Translation>> {
+    let mut groups: BTreeMap<String, Vec<(String, &str)>> =
+        BTreeMap::new();
+    for file in files {
+        let (language, key) = split_language(&file.name, config);
+        groups.entry(key).or_default().push((language, &file.name));
+    }
+
+    let mut translations = HashMap::new();
+    if config.languages.is_empty() {
+        return translations;
+    }
+
+    for mut group in groups.into_values() {
+        group.sort_by_key(|(language, _)| {
+            (
+                config
+                    .languages
+                    .get(language)
+                    .map(|language| language.weight)
+                    .unwrap_or_default(),
+                language.clone(),
+            )
+        });
+
+        for (_, current) in &group {
+            let links = group
+                .iter()
+                .map(|(language, name)| Translation {
+                    language: language.clone(),
+                    name: config
+                        .languages
+                        .get(language)
+                        .map(|language| language.name.clone())
+                        .filter(|name| !name.is_empty())
+                        .unwrap_or_else(|| language.clone()),
+                    url: routes.url(name),
+                    active: name == current,
+                })
+                .collect();
+            let _ = translations.insert(current.to_string(), links);
+        }
+    }
+
+    translations
+}
+
+/// Generates the `<link rel="alternate" hreflang>` tags of a page.
+///
+/// An `x-default` alternate points to the translation in the default
+/// language, when there is one.
+///
+/// # Arguments
+///
+/// * `translations` - The translations of the page.
+/// * `base_url` - The base URL prepended to the page URLs.
+/// * `default_language` - The default language of the site.
+///
+/// # Returns
+///
+/// A `String` containing the link tags, empty when the page has no
+/// other translation.
+pub fn generate_hreflang_links(
+    translations: &[Translation],
+    base_url: &str,
+    default_language: &str,
+) -> String {
+    if translations.len() < 2 {
+        return String::new();
+    }
+
+    let base_url = base_url.trim_end_matches('/');
+    let mut links: Vec<String> = translations
+        .iter()
+        .map(|translation| {
+            format!(
+                "<link rel=\"alternate\" hreflang=\"{}\" href=\"{}{}\">",
+                translation.language, base_url, translation.url
+            )
+        })
+        .collect();
+    if let Some(default) = translations
+        .iter()
+        .find(|translation| translation.language == default_language)
+    {
+        links.push(format!(
+            "<link rel=\"alternate\" hreflang=\"x-default\" href=\"{}{}\">",
+            base_url, default.url
+        ));
+    }
+    links.join("\n")
+}
+
+/// Returns the interface strings of a language.
+///
+/// Strings missing from the language fall back to the default language.
+///
+/// # Arguments
+///
+/// * `config` - The site configuration.
+/// * `language` - The language code.
+///
+/// # Returns
+///
+/// A map of string keys to their translation.
+pub fn interface_strings(
+    config: &SiteConfig,
+    language: &str,
+) -> BTreeMap<String, String> {
+    let mut strings = config
+        .languages
+        .get(&default_language(config))
+        .map(|language| language.strings.clone())
+        .unwrap_or_default();
+    if let Some(language) = config.languages.get(language) {
+        strings.extend(language.strings.clone());
+    }
+    strings
+}
+
+/// Returns the URLs of the pages of each language.
+///
+/// # Arguments
+///
+/// * `files` - The content files.
+/// * `routes` - The output paths of the pages.
+/// * `config` - The site configuration.
+///
+/// # Returns
+///
+/// A map of language codes to the URLs of their pages.
+pub fn language_urls(
+    files: &[FileData],
+    routes: &Routes,
+    config: &SiteConfig,
+) -> BTreeMap<String, Vec<String>> {
+    let mut urls: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for file in files {
+        let (language, _) = split_language(&file.name, config);
+        urls.entry(language)
+            .or_default()
+            .push(routes.url(&file.name));
+    }
+    urls
+}
+
+/// Lists the dated pages of each language, newest first.
+///
+/// Index pages are left out, as they list other pages.
+///
+/// # Arguments
+///
+/// * `entries` - The entries of all pages, as created by `page_entry`.
+/// * `config` - The site configuration.
+///
+/// # Returns
+///
+/// A map of language codes to the entries of their dated pages. The
+/// pages of a site without languages are under its default language.
+pub fn language_pages<'a>(
+    entries: &'a [Value],
+    config: &SiteConfig,
+) -> HashMap<String, Vec<&'a Value>> {
+    let mut dated: Vec<(&Value, (i32, u32, u32))> = entries
+        .iter()
+        .filter_map(|entry| {
+            Some((entry, date_key(field(entry, "date"))?))
+        })
+        .collect();
+    dated.sort_by_key(|(_, date)| Reverse(*date));
+
+    let mut pages: HashMap<String, Vec<&Value>> = HashMap::new();
+    for (entry, _) in dated {
+        let (language, key) =
+            split_language(field(entry, "name"), config);
+        if !NavigationGenerator::is_index_page(&key) {
+            pages.entry(language).or_default().push(entry);
+        }
+    }
+    pages
+}
+
+/// Generates the feed items of the home page of each language.
+///
+/// The feed of a language lists its pages, as returned by
+/// `language_pages`.
+///
+/// # Arguments
+///
+/// * `entries` - The entries of all pages, as created by `page_entry`.
+/// * `config` - The site configuration.
+/// * `offset` - The offset of dates written without a timezone, in
+///   minutes.
+///
+/// # Returns
+///
+/// A map of language codes to the items of their feed, or an empty map
+/// for a site without languages.
+pub fn language_feeds(
+    entries: &[Value],
+    config: &SiteConfig,
+    offset: i32,
+) -> HashMap<String, Vec<RssData>> {
+    if config.languages.is_empty() {
+        return HashMap::new();
+    }
+
+    language_pages(entries, config)
+        .into_iter()
+        .map(|(language, pages)| {
+            let items = pages
+                .into_iter()
+                .map(|entry| {
+                    feed_item(
+                        entry,
+                        field(entry, "author").to_string(),
+                        config,
+                        offset,
+                    )
+                })
+                .collect();
+            (language, items)
+        })
+        .collect()
+}
+
+/// Generates the menus of each language.
+///
+/// The menus of a language hold the entries of its own pages, at their
+/// resolved paths, and the entries of the site configuration. A site
+/// without languages has a single set of menus, under its default
+/// language.
+///
+/// # Arguments
+///
+/// * `files` - The content files.
+/// * `routes` - The output paths of the pages.
+/// * `config` - The site configuration.
+///
+/// # Returns
+///
+/// A map of language codes to their menus (see
+/// `NavigationGenerator::generate_menus`), or an error if two entries
+/// of a menu share an identifier.
+pub fn language_menus(
+    files: &[FileData],
+    routes: &Routes,
+    config: &SiteConfig,
+) -> Result<HashMap<String, BTreeMap<String, Vec<MenuItem>>>, String> {
+    let mut pages: BTreeMap<String, Vec<FileData>> = BTreeMap::new();
+    for file in files {
+        let (language, _) = split_language(&file.name, config);
+        pages.entry(language).or_default().push(file.clone());
+    }
+
+    pages
+        .into_iter()
+        .map(|(language, files)| {
+            NavigationGenerator::generate_menus(
+                &files,
+                &config.menus,
+                routes,
+            )
+            .map(|menus| (language, menus))
+        })
+        .collect()
+}
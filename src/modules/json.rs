@@ -173,6 +173,58 @@ pub fn sitemap(options: SiteMapData, dir: &Path) -> String {
     )
     .unwrap();
 
+    urlset(&urls)
+}
+
+/// ## Function: `sitemap_from_urls` - Generate a sitemap listing the given pages
+///
+/// The `loc` field of the `SiteMapData` object is the base URL the page
+/// URLs are joined to.
+///
+/// The `urls` parameter holds the URL of each page, relative to the site
+/// root, with its last modified date in the W3C format. Pages without a
+/// date use the `lastmod` field of the `SiteMapData` object.
+///
+/// Returns a string containing the sitemap.xml file.
+pub fn sitemap_from_urls(
+    options: &SiteMapData,
+    urls: &[(String, String)],
+) -> String {
+    let urls: Vec<String> = urls
+        .iter()
+        .map(|(url, lastmod)| {
+            let lastmod = if lastmod.is_empty() {
+                &options.lastmod
+            } else {
+                lastmod
+            };
+            format!(
+                r#"<url><changefreq>{}</changefreq><lastmod>{}</lastmod><loc>{}</loc></url>"#,
+                options.changefreq,
+                lastmod,
+                escape_xml(&format!(
+                    "{}/{}",
+                    options.loc.trim_end_matches('/'),
+                    url.trim_start_matches('/')
+                ))
+            )
+        })
+        .collect();
+
+    urlset(&urls)
+}
+
+/// Escapes the characters of a text that are special in XML.
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Wraps the `<url>` entries of a sitemap in its `<urlset>` element.
+fn urlset(urls: &[String]) -> String {
     let urls_str = urls.join("\n");
 
     format!(
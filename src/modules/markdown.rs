@@ -38,3 +38,189 @@ pub fn convert_markdown_to_html(
     let html_content = markdown_to_html(markdown_content, options);
     Ok(html_content.to_string())
 }
+
+/// Splits a Markdown body into its prose and its code blocks.
+///
+/// Fenced code blocks and indented code blocks, which start after a
+/// blank line with four columns of indentation outside of a list, are
+/// returned as code.
+///
+/// # Arguments
+///
+/// * `markdown` - The Markdown body.
+///
+/// # Returns
+///
+/// The consecutive parts of the body, each with `true` when it is code.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::markdown::split_code_blocks;
+///
+/// assert_eq!(
+///     split_code_blocks("Text\n\n    code\n```\nmore\n```\nEnd"),
+///     vec![
+///         ("Text\n\n", false),
+///         ("    code\n```\nmore\n```\n", true),
+///         ("End", false),
+///     ]
+/// );
+/// ```
+pub fn split_code_blocks(markdown: &str) -> Vec<(&str, bool)> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut position = 0;
+    let mut current = false;
+    let mut fence: Option<&str> = None;
+    let mut indented = false;
+    let mut in_list = false;
+    let mut after_break = true;
+
+    for line in markdown.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        let blank = trimmed.is_empty();
+        let indent = indentation(line);
+        let code = if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            true
+        } else if let Some(marker) = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| indent < 4 && trimmed.starts_with(marker))
+        {
+            fence = Some(marker);
+            true
+        } else if indented && (blank || indent >= 4) {
+            true
+        } else {
+            indented = !blank && indent >= 4 && after_break && !in_list;
+            indented
+        };
+
+        if !code && !blank && indent < 4 {
+            if is_list_item(trimmed) {
+                in_list = true;
+            } else if after_break {
+                in_list = false;
+            }
+        }
+        after_break = blank || code;
+
+        if code != current && position > start {
+            parts.push((&markdown[start..position], current));
+            start = position;
+        }
+        current = code;
+        position += line.len();
+    }
+    if position > start {
+        parts.push((&markdown[start..position], current));
+    }
+
+    parts
+}
+
+/// Splits a line of Markdown prose into its text and its code spans.
+///
+/// A code span starts with a run of backticks and ends with the next
+/// run of the same length. Backticks escaped with a backslash or left
+/// unmatched are text.
+///
+/// # Arguments
+///
+/// * `text` - The Markdown prose.
+///
+/// # Returns
+///
+/// The consecutive parts of the text, each with `true` when it is a
+/// code span, delimiters included.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::markdown::split_code_spans;
+///
+/// assert_eq!(
+///     split_code_spans("a ``b ` c`` d"),
+///     vec![("a ", false), ("``b ` c``", true), (" d", false)]
+/// );
+/// ```
+pub fn split_code_spans(text: &str) -> Vec<(&str, bool)> {
+    let bytes = text.as_bytes();
+    let run_at = |index: usize| {
+        bytes[index..].iter().take_while(|b| **b == b'`').count()
+    };
+    let mut parts = Vec::new();
+    let mut last = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'`' => {
+                let run = run_at(i);
+                let mut j = i + run;
+                let mut end = None;
+                while j < bytes.len() {
+                    if bytes[j] != b'`' {
+                        j += 1;
+                        continue;
+                    }
+                    let length = run_at(j);
+                    if length == run {
+                        end = Some(j + run);
+                        break;
+                    }
+                    j += length;
+                }
+                match end {
+                    Some(end) => {
+                        if last < i {
+                            parts.push((&text[last..i], false));
+                        }
+                        parts.push((&text[i..end], true));
+                        last = end;
+                        i = end;
+                    }
+                    None => i += run,
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    if last < text.len() {
+        parts.push((&text[last..], false));
+    }
+
+    parts
+}
+
+/// Returns the indentation of a line in columns, with tab stops of
+/// four columns.
+fn indentation(line: &str) -> usize {
+    let mut columns = 0;
+    for c in line.chars() {
+        match c {
+            ' ' => columns += 1,
+            '\t' => columns += 4 - columns % 4,
+            _ => break,
+        }
+    }
+    columns
+}
+
+/// Returns whether a line without its indentation starts a list item.
+fn is_list_item(trimmed: &str) -> bool {
+    let marker =
+        trimmed.trim_start_matches(|c: char| c.is_ascii_digit());
+    let rest = if marker.len() < trimmed.len() {
+        marker.strip_prefix(['.', ')'])
+    } else {
+        marker.strip_prefix(['-', '*', '+'])
+    };
+    rest.is_some_and(|rest| {
+        rest.is_empty() || rest.starts_with(char::is_whitespace)
+    })
+}
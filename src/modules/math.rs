@@ -0,0 +1,1355 @@
+// Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::modules::markdown::{split_code_blocks, split_code_spans};
+use regex::{Captures, Regex};
+use std::{iter::Peekable, mem, str::Chars};
+
+/// The marker around the placeholder of an inline formula.
+const INLINE_MARKER: char = '\u{E000}';
+
+/// The marker around the placeholder of a display formula.
+const DISPLAY_MARKER: char = '\u{E001}';
+
+lazy_static::lazy_static! {
+    /// Matches a display formula placeholder alone in its paragraph.
+    static ref PARAGRAPH_PLACEHOLDER: Regex =
+        Regex::new(&format!("<p>{0}(\\d+){0}</p>", DISPLAY_MARKER))
+            .unwrap();
+    /// Matches a formula placeholder.
+    static ref PLACEHOLDER: Regex = Regex::new(&format!(
+        "[{0}{1}](\\d+)[{0}{1}]",
+        INLINE_MARKER, DISPLAY_MARKER
+    ))
+    .unwrap();
+}
+
+/// The class of a math symbol, which gives its spacing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Class {
+    /// A letter or another ordinary symbol.
+    Ordinary,
+    /// A binary operator, such as `+`.
+    Binary,
+    /// A relation, such as `=`.
+    Relation,
+    /// A large operator taking limits, such as `\sum`.
+    Large,
+    /// An integral, taking its limits as scripts.
+    Integral,
+    /// An opening delimiter.
+    Open,
+    /// A closing delimiter.
+    Close,
+    /// A punctuation mark.
+    Punctuation,
+}
+
+/// A LaTeX command with its character, class and ASCII fallback.
+type Symbol = (&'static str, &'static str, Class, &'static str);
+
+/// The LaTeX commands of the math symbols.
+const SYMBOLS: &[Symbol] = &[
+    ("alpha", "α", Class::Ordinary, "alpha"),
+    ("beta", "β", Class::Ordinary, "beta"),
+    ("gamma", "γ", Class::Ordinary, "gamma"),
+    ("delta", "δ", Class::Ordinary, "delta"),
+    ("epsilon", "ϵ", Class::Ordinary, "epsilon"),
+    ("varepsilon", "ε", Class::Ordinary, "epsilon"),
+    ("zeta", "ζ", Class::Ordinary, "zeta"),
+    ("eta", "η", Class::Ordinary, "eta"),
+    ("theta", "θ", Class::Ordinary, "theta"),
+    ("vartheta", "ϑ", Class::Ordinary, "theta"),
+    ("iota", "ι", Class::Ordinary, "iota"),
+    ("kappa", "κ", Class::Ordinary, "kappa"),
+    ("lambda", "λ", Class::Ordinary, "lambda"),
+    ("mu", "μ", Class::Ordinary, "mu"),
+    ("nu", "ν", Class::Ordinary, "nu"),
+    ("xi", "ξ", Class::Ordinary, "xi"),
+    ("pi", "π", Class::Ordinary, "pi"),
+    ("varpi", "ϖ", Class::Ordinary, "pi"),
+    ("rho", "ρ", Class::Ordinary, "rho"),
+    ("varrho", "ϱ", Class::Ordinary, "rho"),
+    ("sigma", "σ", Class::Ordinary, "sigma"),
+    ("varsigma", "ς", Class::Ordinary, "sigma"),
+    ("tau", "τ", Class::Ordinary, "tau"),
+    ("upsilon", "υ", Class::Ordinary, "upsilon"),
+    ("phi", "ϕ", Class::Ordinary, "phi"),
+    ("varphi", "φ", Class::Ordinary, "phi"),
+    ("chi", "χ", Class::Ordinary, "chi"),
+    ("psi", "ψ", Class::Ordinary, "psi"),
+    ("omega", "ω", Class::Ordinary, "omega"),
+    ("Gamma", "Γ", Class::Ordinary, "Gamma"),
+    ("Delta", "Δ", Class::Ordinary, "Delta"),
+    ("Theta", "Θ", Class::Ordinary, "Theta"),
+    ("Lambda", "Λ", Class::Ordinary, "Lambda"),
+    ("Xi", "Ξ", Class::Ordinary, "Xi"),
+    ("Pi", "Π", Class::Ordinary, "Pi"),
+    ("Sigma", "Σ", Class::Ordinary, "Sigma"),
+    ("Upsilon", "Υ", Class::Ordinary, "Upsilon"),
+    ("Phi", "Φ", Class::Ordinary, "Phi"),
+    ("Psi", "Ψ", Class::Ordinary, "Psi"),
+    ("Omega", "Ω", Class::Ordinary, "Omega"),
+    ("infty", "∞", Class::Ordinary, "inf"),
+    ("partial", "∂", Class::Ordinary, "d"),
+    ("nabla", "∇", Class::Ordinary, "nabla"),
+    ("hbar", "ℏ", Class::Ordinary, "hbar"),
+    ("ell", "ℓ", Class::Ordinary, "l"),
+    ("emptyset", "∅", Class::Ordinary, "{}"),
+    ("forall", "∀", Class::Ordinary, "for all "),
+    ("exists", "∃", Class::Ordinary, "exists "),
+    ("neg", "¬", Class::Ordinary, "not "),
+    ("prime", "′", Class::Ordinary, "'"),
+    ("surd", "√", Class::Ordinary, "sqrt"),
+    ("angle", "∠", Class::Ordinary, "angle "),
+    ("ldots", "…", Class::Ordinary, "..."),
+    ("dots", "…", Class::Ordinary, "..."),
+    ("cdots", "⋯", Class::Ordinary, "..."),
+    ("vdots", "⋮", Class::Ordinary, "..."),
+    ("ddots", "⋱", Class::Ordinary, "..."),
+    ("pm", "±", Class::Binary, "+/-"),
+    ("mp", "∓", Class::Binary, "-/+"),
+    ("times", "×", Class::Binary, "x"),
+    ("div", "÷", Class::Binary, "/"),
+    ("cdot", "⋅", Class::Binary, "*"),
+    ("ast", "∗", Class::Binary, "*"),
+    ("circ", "∘", Class::Binary, "o"),
+    ("bullet", "∙", Class::Binary, "*"),
+    ("cup", "∪", Class::Binary, "u"),
+    ("cap", "∩", Class::Binary, "n"),
+    ("setminus", "∖", Class::Binary, "\\"),
+    ("wedge", "∧", Class::Binary, "and"),
+    ("land", "∧", Class::Binary, "and"),
+    ("vee", "∨", Class::Binary, "or"),
+    ("lor", "∨", Class::Binary, "or"),
+    ("oplus", "⊕", Class::Binary, "(+)"),
+    ("otimes", "⊗", Class::Binary, "(x)"),
+    ("leq", "≤", Class::Relation, "<="),
+    ("le", "≤", Class::Relation, "<="),
+    ("geq", "≥", Class::Relation, ">="),
+    ("ge", "≥", Class::Relation, ">="),
+    ("neq", "≠", Class::Relation, "!="),
+    ("ne", "≠", Class::Relation, "!="),
+    ("approx", "≈", Class::Relation, "~="),
+    ("equiv", "≡", Class::Relation, "=="),
+    ("sim", "∼", Class::Relation, "~"),
+    ("simeq", "≃", Class::Relation, "~="),
+    ("cong", "≅", Class::Relation, "~="),
+    ("propto", "∝", Class::Relation, "~"),
+    ("ll", "≪", Class::Relation, "<<"),
+    ("gg", "≫", Class::Relation, ">>"),
+    ("in", "∈", Class::Relation, "in"),
+    ("notin", "∉", Class::Relation, "not in"),
+    ("ni", "∋", Class::Relation, "contains"),
+    ("subset", "⊂", Class::Relation, "subset of"),
+    ("subseteq", "⊆", Class::Relation, "subset of"),
+    ("supset", "⊃", Class::Relation, "superset of"),
+    ("supseteq", "⊇", Class::Relation, "superset of"),
+    ("to", "→", Class::Relation, "->"),
+    ("rightarrow", "→", Class::Relation, "->"),
+    ("leftarrow", "←", Class::Relation, "<-"),
+    ("gets", "←", Class::Relation, "<-"),
+    ("leftrightarrow", "↔", Class::Relation, "<->"),
+    ("Rightarrow", "⇒", Class::Relation, "=>"),
+    ("Leftarrow", "⇐", Class::Relation, "<="),
+    ("Leftrightarrow", "⇔", Class::Relation, "<=>"),
+    ("implies", "⟹", Class::Relation, "=>"),
+    ("iff", "⟺", Class::Relation, "<=>"),
+    ("mapsto", "↦", Class::Relation, "|->"),
+    ("mid", "∣", Class::Relation, "|"),
+    ("parallel", "∥", Class::Relation, "||"),
+    ("perp", "⊥", Class::Relation, "_|_"),
+    ("sum", "∑", Class::Large, "sum"),
+    ("prod", "∏", Class::Large, "prod"),
+    ("coprod", "∐", Class::Large, "coprod"),
+    ("bigcup", "⋃", Class::Large, "U"),
+    ("bigcap", "⋂", Class::Large, "n"),
+    ("int", "∫", Class::Integral, "int"),
+    ("iint", "∬", Class::Integral, "int int"),
+    ("iiint", "∭", Class::Integral, "int int int"),
+    ("oint", "∮", Class::Integral, "oint"),
+    ("langle", "⟨", Class::Open, "<"),
+    ("rangle", "⟩", Class::Close, ">"),
+    ("lceil", "⌈", Class::Open, "ceil("),
+    ("rceil", "⌉", Class::Close, ")"),
+    ("lfloor", "⌊", Class::Open, "floor("),
+    ("rfloor", "⌋", Class::Close, ")"),
+    ("lbrace", "{", Class::Open, "{"),
+    ("rbrace", "}", Class::Close, "}"),
+    ("vert", "|", Class::Ordinary, "|"),
+    ("Vert", "‖", Class::Ordinary, "||"),
+    ("minus", "−", Class::Binary, "-"),
+];
+
+/// The functions written in upright letters, and whether their scripts
+/// are limits.
+const FUNCTIONS: &[(&str, bool)] = &[
+    ("sin", false),
+    ("cos", false),
+    ("tan", false),
+    ("cot", false),
+    ("sec", false),
+    ("csc", false),
+    ("arcsin", false),
+    ("arccos", false),
+    ("arctan", false),
+    ("sinh", false),
+    ("cosh", false),
+    ("tanh", false),
+    ("log", false),
+    ("ln", false),
+    ("lg", false),
+    ("exp", false),
+    ("arg", false),
+    ("deg", false),
+    ("dim", false),
+    ("ker", false),
+    ("hom", false),
+    ("det", true),
+    ("gcd", true),
+    ("Pr", true),
+    ("lim", true),
+    ("liminf", true),
+    ("limsup", true),
+    ("max", true),
+    ("min", true),
+    ("sup", true),
+    ("inf", true),
+];
+
+/// The font commands and the MathML variant of their letters.
+const VARIANTS: &[(&str, &str)] = &[
+    ("mathrm", "normal"),
+    ("mathit", "italic"),
+    ("mathbf", "bold"),
+    ("boldsymbol", "bold-italic"),
+    ("mathbb", "double-struck"),
+    ("mathcal", "script"),
+    ("mathscr", "script"),
+    ("mathfrak", "fraktur"),
+    ("mathsf", "sans-serif"),
+    ("mathtt", "monospace"),
+];
+
+/// The spacing commands and their width.
+const SPACES: &[(&str, &str)] = &[
+    (",", "0.167em"),
+    (":", "0.222em"),
+    (">", "0.222em"),
+    (";", "0.278em"),
+    (" ", "0.333em"),
+    ("!", "-0.167em"),
+    ("quad", "1em"),
+    ("qquad", "2em"),
+];
+
+/// The commands that only change the size or style of what follows.
+const IGNORED: &[&str] = &[
+    "displaystyle",
+    "textstyle",
+    "scriptstyle",
+    "limits",
+    "nolimits",
+    "big",
+    "Big",
+    "bigg",
+    "Bigg",
+    "bigl",
+    "bigr",
+    "Bigl",
+    "Bigr",
+    "biggl",
+    "biggr",
+];
+
+/// The double-struck letters with their own character.
+const DOUBLE_STRUCK: &[(char, char)] = &[
+    ('C', 'ℂ'),
+    ('H', 'ℍ'),
+    ('N', 'ℕ'),
+    ('P', 'ℙ'),
+    ('Q', 'ℚ'),
+    ('R', 'ℝ'),
+    ('Z', 'ℤ'),
+];
+
+/// The characters with a superscript form.
+const SUPERSCRIPTS: &[(char, char)] = &[
+    ('0', '⁰'),
+    ('1', '¹'),
+    ('2', '²'),
+    ('3', '³'),
+    ('4', '⁴'),
+    ('5', '⁵'),
+    ('6', '⁶'),
+    ('7', '⁷'),
+    ('8', '⁸'),
+    ('9', '⁹'),
+    ('+', '⁺'),
+    ('−', '⁻'),
+    ('=', '⁼'),
+    ('(', '⁽'),
+    (')', '⁾'),
+    ('i', 'ⁱ'),
+    ('n', 'ⁿ'),
+];
+
+/// The characters with a subscript form.
+const SUBSCRIPTS: &[(char, char)] = &[
+    ('0', '₀'),
+    ('1', '₁'),
+    ('2', '₂'),
+    ('3', '₃'),
+    ('4', '₄'),
+    ('5', '₅'),
+    ('6', '₆'),
+    ('7', '₇'),
+    ('8', '₈'),
+    ('9', '₉'),
+    ('+', '₊'),
+    ('−', '₋'),
+    ('=', '₌'),
+    ('(', '₍'),
+    (')', '₎'),
+    ('a', 'ₐ'),
+    ('e', 'ₑ'),
+    ('h', 'ₕ'),
+    ('i', 'ᵢ'),
+    ('j', 'ⱼ'),
+    ('k', 'ₖ'),
+    ('l', 'ₗ'),
+    ('m', 'ₘ'),
+    ('n', 'ₙ'),
+    ('o', 'ₒ'),
+    ('p', 'ₚ'),
+    ('r', 'ᵣ'),
+    ('s', 'ₛ'),
+    ('t', 'ₜ'),
+    ('u', 'ᵤ'),
+    ('v', 'ᵥ'),
+    ('x', 'ₓ'),
+];
+
+/// A node of a parsed formula.
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    /// A letter, with its MathML variant.
+    Ident(String, Option<&'static str>),
+    /// A number.
+    Number(String),
+    /// An operator, delimiter or other symbol.
+    Op(String, Class),
+    /// A function name, and whether its scripts are limits.
+    Function(String, bool),
+    /// Text, from `\text`.
+    Text(String),
+    /// A space of the given width.
+    Space(&'static str),
+    /// A group of nodes.
+    Row(Vec<Node>),
+    /// A fraction, with a bar unless it is a binomial coefficient.
+    Frac(Box<Node>, Box<Node>, bool),
+    /// A square root, or a root with an index.
+    Root(Box<Node>, Option<Box<Node>>),
+    /// A base with a subscript, a superscript or both.
+    Scripts(Box<Node>, Option<Box<Node>>, Option<Box<Node>>),
+    /// Nodes between stretchy delimiters.
+    Fenced(String, Vec<Node>, String),
+    /// The rows of cells of a matrix, or of an alignment when `true`.
+    Table(Vec<Vec<Node>>, bool),
+    /// An unsupported command.
+    Error(String),
+}
+
+/// What ended a row of nodes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Stop {
+    /// The end of the formula.
+    End,
+    /// The `}` of a group.
+    Group,
+    /// The `&` between cells.
+    Cell,
+    /// The `\\` between rows.
+    Row,
+    /// The `\right` of a `\left`, with its delimiter.
+    Right(String),
+    /// The `\end` of an environment.
+    Environment,
+}
+
+/// A recursive descent parser of LaTeX formulas.
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    groups: usize,
+    fences: usize,
+    environments: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(tex: &'a str) -> Self {
+        Parser {
+            chars: tex.chars().peekable(),
+            groups: 0,
+            fences: 0,
+            environments: 0,
+        }
+    }
+
+    /// Parses the whole formula, whose rows become a table.
+    fn parse(mut self) -> Node {
+        let (rows, _) = self.table();
+        table_or_row(rows)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+            let _ = self.chars.next();
+        }
+    }
+
+    /// Parses nodes up to the end of a row, cell, group or fence.
+    fn row(&mut self) -> (Vec<Node>, Stop) {
+        let mut nodes = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let Some(c) = self.chars.next() else {
+                return (nodes, Stop::End);
+            };
+            match c {
+                '}' if self.groups > 0 => return (nodes, Stop::Group),
+                '}' => {}
+                '&' if self.environments > 0 => {
+                    return (nodes, Stop::Cell)
+                }
+                '^' | '_' => {
+                    let script = Box::new(self.argument());
+                    let (base, mut sub, mut sup) = match nodes.pop() {
+                        Some(Node::Scripts(base, sub, sup))
+                            if (c == '_' && sub.is_none())
+                                || (c == '^' && sup.is_none()) =>
+                        {
+                            (base, sub, sup)
+                        }
+                        Some(node) => (Box::new(node), None, None),
+                        None => {
+                            (Box::new(Node::Row(vec![])), None, None)
+                        }
+                    };
+                    if c == '_' {
+                        sub = Some(script);
+                    } else {
+                        sup = Some(script);
+                    }
+                    nodes.push(Node::Scripts(base, sub, sup));
+                }
+                '\\' => {
+                    let name = self.command_name();
+                    match name.as_str() {
+                        "\\" if self.environments > 0 => {
+                            return (nodes, Stop::Row)
+                        }
+                        "right" if self.fences > 0 => {
+                            let delimiter = self.delimiter();
+                            return (nodes, Stop::Right(delimiter));
+                        }
+                        "end" if self.environments > 0 => {
+                            let _ = self.raw_argument();
+                            return (nodes, Stop::Environment);
+                        }
+                        _ => nodes.extend(self.command(&name)),
+                    }
+                }
+                _ => nodes.push(self.atom(c)),
+            }
+        }
+    }
+
+    /// Parses the rows and cells up to the end of an environment.
+    fn table(&mut self) -> (Vec<Vec<Node>>, Stop) {
+        self.environments += 1;
+        let mut rows = Vec::new();
+        let mut cells = Vec::new();
+        let stop = loop {
+            let (nodes, stop) = self.row();
+            cells.push(Node::Row(nodes));
+            match stop {
+                Stop::Cell => {}
+                Stop::Row => rows.push(mem::take(&mut cells)),
+                stop => {
+                    rows.push(cells);
+                    break stop;
+                }
+            }
+        };
+        self.environments -= 1;
+
+        // Drop the empty row after a final `\\`
+        if rows.len() > 1
+            && rows.last().is_some_and(|cells| {
+                cells.iter().all(|cell| *cell == Node::Row(vec![]))
+            })
+        {
+            let _ = rows.pop();
+        }
+        (rows, stop)
+    }
+
+    /// Parses a group, a command or a single character.
+    fn argument(&mut self) -> Node {
+        self.skip_whitespace();
+        match self.chars.next() {
+            Some('{') => self.group(),
+            Some('\\') => {
+                let name = self.command_name();
+                let mut nodes = self.command(&name);
+                if nodes.len() == 1 {
+                    nodes.remove(0)
+                } else {
+                    Node::Row(nodes)
+                }
+            }
+            Some(c) if c.is_ascii_digit() => {
+                Node::Number(c.to_string())
+            }
+            Some(c) => self.atom(c),
+            None => Node::Row(vec![]),
+        }
+    }
+
+    /// Parses the rest of a group, after its `{`.
+    fn group(&mut self) -> Node {
+        self.groups += 1;
+        let mut nodes = Vec::new();
+        loop {
+            let (row, stop) = self.row();
+            nodes.extend(row);
+            if matches!(stop, Stop::Group | Stop::End) {
+                break;
+            }
+        }
+        self.groups -= 1;
+        unwrap_row(nodes)
+    }
+
+    /// Returns the text of a braced argument, or of a single character.
+    fn raw_argument(&mut self) -> String {
+        self.skip_whitespace();
+        match self.chars.next() {
+            Some('{') => {
+                let mut depth = 0;
+                let mut text = String::new();
+                for c in self.chars.by_ref() {
+                    match c {
+                        '{' => depth += 1,
+                        '}' if depth == 0 => break,
+                        '}' => depth -= 1,
+                        _ => {}
+                    }
+                    text.push(c);
+                }
+                text
+            }
+            Some(c) => c.to_string(),
+            None => String::new(),
+        }
+    }
+
+    /// Returns the name of a command, after its `\`.
+    fn command_name(&mut self) -> String {
+        let mut name = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if !c.is_ascii_alphabetic() {
+                break;
+            }
+            name.push(c);
+            let _ = self.chars.next();
+        }
+        if name.is_empty() {
+            if let Some(c) = self.chars.next() {
+                name.push(c);
+            }
+        }
+        name
+    }
+
+    /// Returns the character of the delimiter after `\left` or `\right`.
+    fn delimiter(&mut self) -> String {
+        self.skip_whitespace();
+        match self.chars.next() {
+            Some('.') | None => String::new(),
+            Some('\\') => match self.command_name().as_str() {
+                "|" => "‖".to_string(),
+                name => symbol(name).map_or_else(
+                    || name.to_string(),
+                    |s| s.1.to_string(),
+                ),
+            },
+            Some(c) => c.to_string(),
+        }
+    }
+
+    /// Parses the nodes of a command.
+    fn command(&mut self, name: &str) -> Vec<Node> {
+        let node = match name {
+            "frac" | "dfrac" | "tfrac" | "cfrac" => {
+                let numerator = self.argument();
+                let denominator = self.argument();
+                Node::Frac(
+                    Box::new(numerator),
+                    Box::new(denominator),
+                    true,
+                )
+            }
+            "binom" => {
+                let n = self.argument();
+                let k = self.argument();
+                Node::Fenced(
+                    "(".to_string(),
+                    vec![Node::Frac(Box::new(n), Box::new(k), false)],
+                    ")".to_string(),
+                )
+            }
+            "sqrt" => {
+                self.skip_whitespace();
+                let index = if self.chars.peek() == Some(&'[') {
+                    let _ = self.chars.next();
+                    let index: String = self
+                        .chars
+                        .by_ref()
+                        .take_while(|c| *c != ']')
+                        .collect();
+                    Some(Box::new(Parser::new(&index).parse()))
+                } else {
+                    None
+                };
+                Node::Root(Box::new(self.argument()), index)
+            }
+            "text" | "textrm" | "textit" | "textbf" | "mbox" => {
+                Node::Text(self.raw_argument())
+            }
+            "operatorname" => {
+                Node::Function(self.raw_argument(), false)
+            }
+            "left" => {
+                let open = self.delimiter();
+                self.fences += 1;
+                let mut nodes = Vec::new();
+                let close = loop {
+                    let (row, stop) = self.row();
+                    nodes.extend(row);
+                    match stop {
+                        Stop::Right(close) => break close,
+                        Stop::End => break String::new(),
+                        _ => {}
+                    }
+                };
+                self.fences -= 1;
+                Node::Fenced(open, nodes, close)
+            }
+            "right" | "middle" => {
+                Node::Op(self.delimiter(), Class::Ordinary)
+            }
+            "begin" => self.environment(),
+            "{" => Node::Op("{".to_string(), Class::Open),
+            "}" => Node::Op("}".to_string(), Class::Close),
+            "|" => Node::Op("‖".to_string(), Class::Ordinary),
+            "$" | "%" | "&" | "#" | "_" => {
+                Node::Op(name.to_string(), Class::Ordinary)
+            }
+            _ => {
+                if let Some(&(_, variant)) = VARIANTS
+                    .iter()
+                    .find(|(command, _)| *command == name)
+                {
+                    with_variant(self.argument(), variant)
+                } else if let Some(&(_, width)) =
+                    SPACES.iter().find(|(command, _)| *command == name)
+                {
+                    Node::Space(width)
+                } else if let Some(&(_, limits)) = FUNCTIONS
+                    .iter()
+                    .find(|(command, _)| *command == name)
+                {
+                    Node::Function(name.to_string(), limits)
+                } else if let Some(&(_, text, class, _)) = symbol(name)
+                {
+                    if class == Class::Ordinary
+                        && text.chars().all(char::is_alphabetic)
+                    {
+                        Node::Ident(text.to_string(), None)
+                    } else {
+                        Node::Op(text.to_string(), class)
+                    }
+                } else if IGNORED.contains(&name) {
+                    return vec![];
+                } else {
+                    Node::Error(format!("\\{}", name))
+                }
+            }
+        };
+        vec![node]
+    }
+
+    /// Parses an environment, after its `\begin`.
+    fn environment(&mut self) -> Node {
+        let name = self.raw_argument();
+        if name == "array" {
+            // The column specification is not rendered
+            let _ = self.raw_argument();
+        }
+        let name = name.trim_end_matches('*');
+        let aligned = matches!(
+            name,
+            "align" | "aligned" | "gather" | "gathered" | "split"
+        );
+        let (rows, _) = self.table();
+        let table = Node::Table(rows, aligned);
+        let (open, close) = match name {
+            "pmatrix" => ("(", ")"),
+            "bmatrix" => ("[", "]"),
+            "Bmatrix" => ("{", "}"),
+            "vmatrix" => ("|", "|"),
+            "Vmatrix" => ("‖", "‖"),
+            "cases" => ("{", ""),
+            _ => return table,
+        };
+        Node::Fenced(open.to_string(), vec![table], close.to_string())
+    }
+
+    /// Parses a single character.
+    fn atom(&mut self, c: char) -> Node {
+        match c {
+            '{' => self.group(),
+            '0'..='9' | '.' => {
+                let mut number = c.to_string();
+                while let Some(&next) = self.chars.peek() {
+                    if !(next.is_ascii_digit() || next == '.') {
+                        break;
+                    }
+                    number.push(next);
+                    let _ = self.chars.next();
+                }
+                Node::Number(number)
+            }
+            '+' => Node::Op("+".to_string(), Class::Binary),
+            '-' => Node::Op("−".to_string(), Class::Binary),
+            '*' => Node::Op("∗".to_string(), Class::Binary),
+            '=' | '<' | '>' | ':' => {
+                Node::Op(c.to_string(), Class::Relation)
+            }
+            ',' | ';' => Node::Op(c.to_string(), Class::Punctuation),
+            '(' | '[' => Node::Op(c.to_string(), Class::Open),
+            ')' | ']' => Node::Op(c.to_string(), Class::Close),
+            '\'' => Node::Op("′".to_string(), Class::Ordinary),
+            '~' => Node::Space("0.333em"),
+            c if c.is_alphabetic() => Node::Ident(c.to_string(), None),
+            c => Node::Op(c.to_string(), Class::Ordinary),
+        }
+    }
+}
+
+/// Returns the symbol of a command.
+fn symbol(name: &str) -> Option<&'static Symbol> {
+    SYMBOLS.iter().find(|(command, ..)| *command == name)
+}
+
+/// Returns a single node as is, and several nodes as a row.
+fn unwrap_row(mut nodes: Vec<Node>) -> Node {
+    if nodes.len() == 1 {
+        nodes.remove(0)
+    } else {
+        Node::Row(nodes)
+    }
+}
+
+/// Returns a table of several cells, or the content of a single cell.
+fn table_or_row(mut rows: Vec<Vec<Node>>) -> Node {
+    if rows.len() == 1 && rows[0].len() == 1 {
+        match rows.remove(0).remove(0) {
+            Node::Row(nodes) => unwrap_row(nodes),
+            node => node,
+        }
+    } else {
+        Node::Table(rows, true)
+    }
+}
+
+/// Applies a MathML variant to the letters of a node.
+fn with_variant(node: Node, variant: &'static str) -> Node {
+    match node {
+        Node::Ident(text, _) => Node::Ident(text, Some(variant)),
+        Node::Row(nodes) => Node::Row(
+            nodes
+                .into_iter()
+                .map(|node| with_variant(node, variant))
+                .collect(),
+        ),
+        node => node,
+    }
+}
+
+/// Escapes the text of a MathML element.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Writes the MathML of a node.
+fn write_mathml(node: &Node, display: bool, out: &mut String) {
+    match node {
+        Node::Ident(text, Some(variant)) => out.push_str(&format!(
+            "<mi mathvariant=\"{}\">{}</mi>",
+            variant,
+            escape(text)
+        )),
+        Node::Ident(text, None) | Node::Function(text, _) => {
+            out.push_str(&format!("<mi>{}</mi>", escape(text)))
+        }
+        Node::Number(number) => {
+            out.push_str(&format!("<mn>{}</mn>", number))
+        }
+        Node::Op(text, Class::Open | Class::Close) => out.push_str(
+            &format!("<mo stretchy=\"false\">{}</mo>", escape(text)),
+        ),
+        Node::Op(text, _) => {
+            out.push_str(&format!("<mo>{}</mo>", escape(text)))
+        }
+        Node::Text(text) => {
+            out.push_str(&format!("<mtext>{}</mtext>", escape(text)))
+        }
+        Node::Space(width) => {
+            out.push_str(&format!("<mspace width=\"{}\"/>", width))
+        }
+        Node::Row(nodes) => {
+            if let [node] = nodes.as_slice() {
+                write_mathml(node, display, out);
+            } else {
+                out.push_str("<mrow>");
+                for node in nodes {
+                    write_mathml(node, display, out);
+                }
+                out.push_str("</mrow>");
+            }
+        }
+        Node::Frac(numerator, denominator, bar) => {
+            out.push_str(if *bar {
+                "<mfrac>"
+            } else {
+                "<mfrac linethickness=\"0\">"
+            });
+            write_mathml(numerator, display, out);
+            write_mathml(denominator, display, out);
+            out.push_str("</mfrac>");
+        }
+        Node::Root(radicand, None) => {
+            out.push_str("<msqrt>");
+            write_mathml(radicand, display, out);
+            out.push_str("</msqrt>");
+        }
+        Node::Root(radicand, Some(index)) => {
+            out.push_str("<mroot>");
+            write_mathml(radicand, display, out);
+            write_mathml(index, display, out);
+            out.push_str("</mroot>");
+        }
+        Node::Scripts(base, sub, sup) => {
+            let limits = match base.as_ref() {
+                Node::Op(_, Class::Large) => display,
+                Node::Function(_, limits) => *limits && display,
+                _ => false,
+            };
+            let tag = match (sub.is_some(), sup.is_some(), limits) {
+                (true, true, false) => "msubsup",
+                (true, false, false) => "msub",
+                (_, _, false) => "msup",
+                (true, true, true) => "munderover",
+                (true, false, true) => "munder",
+                (_, _, true) => "mover",
+            };
+            out.push_str(&format!("<{}>", tag));
+            write_mathml(base, display, out);
+            for script in [sub, sup].into_iter().flatten() {
+                write_mathml(script, display, out);
+            }
+            out.push_str(&format!("</{}>", tag));
+        }
+        Node::Fenced(open, nodes, close) => {
+            out.push_str("<mrow>");
+            if !open.is_empty() {
+                out.push_str(&format!(
+                    "<mo fence=\"true\">{}</mo>",
+                    escape(open)
+                ));
+            }
+            for node in nodes {
+                write_mathml(node, display, out);
+            }
+            if !close.is_empty() {
+                out.push_str(&format!(
+                    "<mo fence=\"true\">{}</mo>",
+                    escape(close)
+                ));
+            }
+            out.push_str("</mrow>");
+        }
+        Node::Table(rows, _) => {
+            out.push_str("<mtable>");
+            for cells in rows {
+                out.push_str("<mtr>");
+                for cell in cells {
+                    out.push_str("<mtd>");
+                    write_mathml(cell, display, out);
+                    out.push_str("</mtd>");
+                }
+                out.push_str("</mtr>");
+            }
+            out.push_str("</mtable>");
+        }
+        Node::Error(command) => out.push_str(&format!(
+            "<merror><mtext>{}</mtext></merror>",
+            escape(command)
+        )),
+    }
+}
+
+/// Writes the plain text of a node.
+fn write_text(node: &Node, out: &mut String) {
+    match node {
+        Node::Ident(text, Some("double-struck")) => {
+            out.extend(text.chars().map(|c| {
+                DOUBLE_STRUCK
+                    .iter()
+                    .find(|(letter, _)| *letter == c)
+                    .map_or(c, |(_, double)| *double)
+            }))
+        }
+        Node::Ident(text, _)
+        | Node::Number(text)
+        | Node::Function(text, _)
+        | Node::Text(text)
+        | Node::Error(text) => out.push_str(text),
+        Node::Op(text, Class::Relation) => {
+            out.push_str(&format!(" {} ", text))
+        }
+        Node::Op(text, Class::Binary) => {
+            // A sign at the start of a group is not a binary operator
+            if out.trim_end().is_empty()
+                || out.ends_with(['(', '[', '{', ' '])
+            {
+                out.push_str(text);
+            } else {
+                out.push_str(&format!(" {} ", text));
+            }
+        }
+        Node::Op(text, Class::Punctuation) => {
+            out.push_str(&format!("{} ", text))
+        }
+        Node::Op(text, _) => out.push_str(text),
+        Node::Space(_) => out.push(' '),
+        Node::Row(nodes) => {
+            for (index, node) in nodes.iter().enumerate() {
+                write_text(node, out);
+                let function = match node {
+                    Node::Function(..) => true,
+                    Node::Scripts(base, ..) => {
+                        matches!(base.as_ref(), Node::Function(..))
+                    }
+                    _ => false,
+                };
+                if function
+                    && matches!(
+                        nodes.get(index + 1),
+                        Some(Node::Ident(..) | Node::Number(_))
+                    )
+                {
+                    out.push(' ');
+                }
+            }
+        }
+        Node::Frac(numerator, denominator, true) => {
+            out.push_str(&format!(
+                "{}/{}",
+                operand(numerator),
+                operand(denominator)
+            ));
+        }
+        Node::Frac(top, bottom, false) => {
+            out.push_str(&format!("{}, {}", text(top), text(bottom)));
+        }
+        Node::Root(radicand, index) => {
+            if let Some(index) = index {
+                out.push_str(&script(index, SUPERSCRIPTS, '^'));
+            }
+            out.push('√');
+            out.push_str(&operand(radicand));
+        }
+        Node::Scripts(base, sub, sup) => {
+            write_text(base, out);
+            if let Some(sub) = sub {
+                out.push_str(&script(sub, SUBSCRIPTS, '_'));
+            }
+            if let Some(sup) = sup {
+                out.push_str(&script(sup, SUPERSCRIPTS, '^'));
+            }
+        }
+        Node::Fenced(open, nodes, close) => {
+            out.push_str(open);
+            if open == "{" && close.is_empty() {
+                out.push(' ');
+            }
+            out.push_str(&text(&Node::Row(nodes.clone())));
+            out.push_str(close);
+        }
+        Node::Table(rows, aligned) => {
+            let rows: Vec<String> = rows
+                .iter()
+                .map(|cells| {
+                    cells
+                        .iter()
+                        .map(text)
+                        .filter(|cell| !cell.is_empty())
+                        .collect::<Vec<_>>()
+                        .join(if *aligned { " " } else { ", " })
+                })
+                .collect();
+            out.push_str(&rows.join("; "));
+        }
+    }
+}
+
+/// Returns the plain text of a node, on a single line.
+fn text(node: &Node) -> String {
+    let mut out = String::new();
+    write_text(node, &mut out);
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Returns the plain text of an operand, in parentheses unless it is a
+/// single letter, number or group.
+fn operand(node: &Node) -> String {
+    let text = text(node);
+    let simple = text.chars().all(|c| c.is_alphanumeric() || c == '.')
+        || matches!(node, Node::Fenced(..));
+    if simple {
+        text
+    } else {
+        format!("({})", text)
+    }
+}
+
+/// Returns the plain text of a script, in superscript or subscript
+/// characters when they all have one.
+fn script(
+    node: &Node,
+    characters: &[(char, char)],
+    marker: char,
+) -> String {
+    let text = text(node);
+    let scripted: Option<String> = text
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| {
+            characters
+                .iter()
+                .find(|(plain, _)| *plain == c)
+                .map(|(_, scripted)| *scripted)
+        })
+        .collect();
+    match scripted {
+        Some(scripted) if !scripted.is_empty() => scripted,
+        _ if text.chars().count() == 1 => format!("{}{}", marker, text),
+        _ => format!("{}({})", marker, text),
+    }
+}
+
+/// Converts a LaTeX formula to MathML.
+///
+/// The formula is parsed at build time, so pages need no math library.
+/// The LaTeX source is kept as an annotation of the formula, and
+/// unsupported commands are rendered as errors.
+///
+/// # Arguments
+///
+/// * `tex` - The LaTeX formula, without its `$` delimiters.
+/// * `display` - Whether the formula is displayed as a block, from
+///   `$$...$$`, rather than inline, from `$...$`.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::math::latex_to_mathml;
+///
+/// let mathml = latex_to_mathml(r"\frac{1}{2}", false);
+/// assert!(mathml.starts_with("<math xmlns=\"http://www.w3.org/1998/Math/MathML\" display=\"inline\">"));
+/// assert!(mathml.contains("<mfrac><mn>1</mn><mn>2</mn></mfrac>"));
+/// ```
+pub fn latex_to_mathml(tex: &str, display: bool) -> String {
+    let mut mathml = String::new();
+    match Parser::new(tex).parse() {
+        Node::Row(nodes) => {
+            for node in &nodes {
+                write_mathml(node, display, &mut mathml);
+            }
+        }
+        node => write_mathml(&node, display, &mut mathml),
+    }
+    format!(
+        "<math xmlns=\"http://www.w3.org/1998/Math/MathML\" \
+         display=\"{}\"><semantics><mrow>{}</mrow>\
+         <annotation encoding=\"application/x-tex\">{}</annotation>\
+         </semantics></math>",
+        if display { "block" } else { "inline" },
+        mathml,
+        escape(tex.trim())
+    )
+}
+
+/// Converts a LaTeX formula to plain text.
+///
+/// Symbols become their Unicode characters, simple scripts become
+/// superscript and subscript characters, and fractions are written
+/// with a `/`.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::math::latex_to_text;
+///
+/// assert_eq!(latex_to_text(r"\alpha^2 + \beta_1"), "α² + β₁");
+/// assert_eq!(latex_to_text(r"\frac{a+b}{2}"), "(a + b)/2");
+/// ```
+pub fn latex_to_text(tex: &str) -> String {
+    text(&Parser::new(tex).parse())
+}
+
+/// Replaces the formulas of a Markdown body.
+///
+/// Display formulas are written between `$$`, and may span several
+/// lines. Inline formulas are written between `$` on a single line: the
+/// opening `$` must not be followed by a space, and the closing `$` must
+/// not follow a space nor be followed by a digit, so prices such as
+/// `$5 and $10` are not formulas. Fenced and indented code blocks,
+/// inline code and `\$` are left as is.
+///
+/// # Arguments
+///
+/// * `markdown` - The Markdown body.
+/// * `render` - Returns the replacement of a formula, from its LaTeX
+///   source and whether it is a display formula.
+///
+/// # Returns
+///
+/// The Markdown body with its formulas replaced.
+pub fn replace_math(
+    markdown: &str,
+    mut render: impl FnMut(&str, bool) -> String,
+) -> String {
+    split_code_blocks(markdown)
+        .into_iter()
+        .map(|(part, code)| {
+            if code {
+                part.to_string()
+            } else {
+                replace_in_prose(part, &mut render)
+            }
+        })
+        .collect()
+}
+
+/// Replaces the formulas of Markdown prose outside inline code.
+fn replace_in_prose(
+    text: &str,
+    render: &mut impl FnMut(&str, bool) -> String,
+) -> String {
+    split_code_spans(text)
+        .into_iter()
+        .map(|(part, code)| {
+            if code {
+                part.to_string()
+            } else {
+                replace_formulas(part, render)
+            }
+        })
+        .collect()
+}
+
+/// Replaces the formulas of Markdown text without code.
+fn replace_formulas(
+    text: &str,
+    render: &mut impl FnMut(&str, bool) -> String,
+) -> String {
+    let bytes = text.as_bytes();
+    let mut output = String::new();
+    let mut last = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'$' if bytes.get(i + 1) == Some(&b'$') => {
+                let formula = text[i + 2..].find("$$").map(|end| {
+                    (&text[i + 2..i + 2 + end], i + 2 + end + 2)
+                });
+                match formula {
+                    Some((tex, end)) if !tex.trim().is_empty() => {
+                        output.push_str(&text[last..i]);
+                        output.push_str(&render(tex.trim(), true));
+                        last = end;
+                        i = end;
+                    }
+                    _ => i += 2,
+                }
+            }
+            b'$' => match inline_end(bytes, i) {
+                Some(end) => {
+                    output.push_str(&text[last..i]);
+                    output.push_str(&render(&text[i + 1..end], false));
+                    last = end + 1;
+                    i = end + 1;
+                }
+                None => i += 1,
+            },
+            _ => i += 1,
+        }
+    }
+    output.push_str(&text[last..]);
+    output
+}
+
+/// Returns the position of the `$` closing the inline formula opened
+/// at a position.
+fn inline_end(bytes: &[u8], start: usize) -> Option<usize> {
+    match bytes.get(start + 1) {
+        Some(b) if !b.is_ascii_whitespace() => {}
+        _ => return None,
+    }
+    let mut j = start + 1;
+    while j < bytes.len() && bytes[j] != b'\n' {
+        match bytes[j] {
+            b'\\' => j += 2,
+            b'$' => {
+                if !bytes[j - 1].is_ascii_whitespace()
+                    && !bytes.get(j + 1).is_some_and(u8::is_ascii_digit)
+                {
+                    return Some(j);
+                }
+                j += 1;
+            }
+            _ => j += 1,
+        }
+    }
+    None
+}
+
+/// Replaces the formulas of a Markdown body with placeholders.
+///
+/// The placeholders go through the Markdown conversion unchanged, and
+/// `restore_math` replaces them with the MathML of their formulas.
+///
+/// # Arguments
+///
+/// * `markdown` - The Markdown body.
+///
+/// # Returns
+///
+/// The Markdown body with placeholders, and the MathML of its formulas.
+pub fn extract_math(markdown: &str) -> (String, Vec<String>) {
+    let mut formulas = Vec::new();
+    let markdown = replace_math(markdown, |tex, display| {
+        formulas.push(latex_to_mathml(tex, display));
+        let marker = if display {
+            DISPLAY_MARKER
+        } else {
+            INLINE_MARKER
+        };
+        format!("{}{}{}", marker, formulas.len() - 1, marker)
+    });
+    (markdown, formulas)
+}
+
+/// Replaces the placeholders of `extract_math` with their formulas.
+///
+/// A display formula alone in its paragraph replaces the paragraph.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::math::{extract_math, restore_math};
+///
+/// let (markdown, formulas) = extract_math("$$x^2$$");
+/// let html = restore_math(&format!("<p>{}</p>", markdown), &formulas);
+/// assert!(html.starts_with("<math"));
+/// assert!(html.contains("display=\"block\""));
+/// ```
+pub fn restore_math(html: &str, formulas: &[String]) -> String {
+    if formulas.is_empty() {
+        return html.to_string();
+    }
+    let formula = |caps: &Captures<'_>| {
+        caps[1]
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| formulas.get(index))
+            .map_or_else(|| caps[0].to_string(), String::clone)
+    };
+    let html = PARAGRAPH_PLACEHOLDER.replace_all(html, formula);
+    PLACEHOLDER.replace_all(&html, formula).into_owned()
+}
+
+/// Replaces the formulas of a Markdown body with their plain text.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::math::math_to_text;
+///
+/// assert_eq!(
+///     math_to_text(r"Area: $\pi r^2$, `$x$`"),
+///     "Area: πr², `$x$`"
+/// );
+/// ```
+pub fn math_to_text(markdown: &str) -> String {
+    replace_math(markdown, |tex, _| latex_to_text(tex))
+}
+
+/// Replaces the math symbols missing from the standard PDF fonts with
+/// ASCII text.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::math::ascii_math;
+///
+/// assert_eq!(ascii_math("α ≤ xⁿ₁ × 2"), "alpha <= x^n_1 × 2");
+/// ```
+pub fn ascii_math(text: &str) -> String {
+    let mut output = String::new();
+    for c in text.chars() {
+        if u32::from(c) < 0x100 {
+            output.push(c);
+        } else if let Some((plain, _)) =
+            SUPERSCRIPTS.iter().find(|(_, scripted)| *scripted == c)
+        {
+            output.push('^');
+            output.push_str(&ascii_math(&plain.to_string()));
+        } else if let Some((plain, _)) =
+            SUBSCRIPTS.iter().find(|(_, scripted)| *scripted == c)
+        {
+            output.push('_');
+            output.push_str(&ascii_math(&plain.to_string()));
+        } else if let Some((_, _, _, ascii)) = SYMBOLS
+            .iter()
+            .find(|(_, symbol, ..)| symbol.chars().eq([c]))
+        {
+            output.push_str(ascii);
+        } else if let Some((letter, _)) =
+            DOUBLE_STRUCK.iter().find(|(_, double)| *double == c)
+        {
+            output.push(*letter);
+        } else {
+            output.push(c);
+        }
+    }
+    output
+}
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use crate::macro_generate_tags_from_fields;
-use crate::models::data::{MetaTag, MetaTagGroups};
+use crate::models::data::{MetaTag, MetaTagGroups, PageLink};
 use std::collections::HashMap;
 
 // Type alias for better readability
@@ -245,3 +245,56 @@ pub fn generate_all_meta_tags(metadata: &MetaDataMap) -> MetaTagGroups {
         twitter: generate_twitter_meta_tags(metadata),
     }
 }
+
+/// Generates a schema.org `BreadcrumbList` for the given breadcrumb trail.
+///
+/// The URLs of the breadcrumbs are made absolute with `base_url`.
+/// Breadcrumbs without a URL are listed without an `item`.
+///
+/// # Arguments
+/// * `breadcrumbs` - The breadcrumb trail of the page, from the home page.
+/// * `base_url` - The absolute URL of the site.
+///
+/// # Returns
+/// A `String` containing the JSON-LD `<script>` element, or an empty
+/// string when there are no breadcrumbs.
+///
+pub fn generate_breadcrumb_json_ld(
+    breadcrumbs: &[PageLink],
+    base_url: &str,
+) -> String {
+    if breadcrumbs.is_empty() {
+        return String::new();
+    }
+
+    let items: Vec<serde_json::Value> = breadcrumbs
+        .iter()
+        .enumerate()
+        .map(|(index, crumb)| {
+            let mut item = serde_json::json!({
+                "@type": "ListItem",
+                "position": index + 1,
+                "name": crumb.title,
+            });
+            if !crumb.url.is_empty() {
+                item["item"] = serde_json::Value::String(format!(
+                    "{}{}",
+                    base_url.trim_end_matches('/'),
+                    crumb.url
+                ));
+            }
+            item
+        })
+        .collect();
+
+    let list = serde_json::json!({
+        "@context": "https://schema.org",
+        "@type": "BreadcrumbList",
+        "itemListElement": items,
+    });
+    // A `</script>` in a title must not close the element
+    format!(
+        "<script type=\"application/ld+json\">{}</script>",
+        list.to_string().replace("</", "<\\/")
+    )
+}
@@ -1,15 +1,52 @@
 // Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+/// The `archives` module generates the date-based archive pages.
+pub mod archives;
+
+/// The `authors` module handles author profiles and author pages.
+pub mod authors;
+
+/// The `bundles` module copies the assets of page bundles.
+pub mod bundles;
+
+/// The `cascade` module applies the front matter defaults of pages.
+pub mod cascade;
+
 /// The `cname` module generates the CNAME content.
 pub mod cname;
 
+/// The `collections` module groups and queries the pages of the site.
+pub mod collections;
+
+/// The `config` module loads the site configuration.
+pub mod config;
+
+/// The `datafiles` module loads the global data files of the site.
+pub mod datafiles;
+
+/// The `dates` module parses and formats the dates of the front matter.
+pub mod dates;
+
+/// The `formats` module reads the content files written in JSON, TOML,
+/// YAML and HTML.
+pub mod formats;
+
 /// The `frontmatter` module extracts the front matter from files.
 pub mod frontmatter;
 
+/// The `generators` module generates pages from global data files.
+pub mod generators;
+
+/// The `git` module reads the git history of the content files.
+pub mod git;
+
 /// The `human` module contains functions for generating human-readable
 pub mod human;
 
+/// The `i18n` module groups translations of multilingual sites.
+pub mod i18n;
+
 /// The `html` module contains functions for generating HTML.
 pub mod html;
 
@@ -25,6 +62,9 @@ pub mod manifest;
 /// The `markdown` module contains functions for converting Markdown to HTML.
 pub mod markdown;
 
+/// The `math` module converts LaTeX formulas to MathML and plain text.
+pub mod math;
+
 /// The `metatags` module contains functions for generating meta tags.
 pub mod metatags;
 
@@ -34,6 +74,12 @@ pub mod metadata;
 /// The `navigation` module generates the navigation menu.
 pub mod navigation;
 
+/// The `pagination` module splits lists into pages.
+pub mod pagination;
+
+/// The `permalinks` module resolves the output paths of the pages.
+pub mod permalinks;
+
 /// The `plaintext` module contains functions for generating plaintext.
 pub mod plaintext;
 
@@ -46,14 +92,38 @@ pub mod postprocessor;
 /// The `pdf` module contains functions for generating PDFs.
 pub mod pdf;
 
+/// The `publication` module handles drafts, scheduled and expired pages.
+pub mod publication;
+
+/// The `redirects` module generates redirects from page aliases.
+pub mod redirects;
+
+/// The `references` module resolves the cross-references between pages.
+pub mod references;
+
+/// The `related` module suggests related pages.
+pub mod related;
+
 /// The `rss` module contains functions for generating RSS feeds.
 pub mod rss;
 
+/// The `sections` module generates the data of the section list pages.
+pub mod sections;
+
+/// The `series` module groups multi-part articles into series.
+pub mod series;
+
 /// The `sitemap` module generates the sitemap content.
 pub mod sitemap;
 
+/// The `summary` module computes page summaries and reading times.
+pub mod summary;
+
 /// The `tags` module contains functions for generating a tags page.
 pub mod tags;
 
 /// The `txt` module generates the robots.txt content.
 pub mod txt;
+
+/// The `urls` module applies the base URL and relative links to the site.
+pub mod urls;
@@ -1,9 +1,15 @@
 // Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use crate::models::data::FileData;
+use crate::models::data::{
+    AdjacentPages, FileData, MenuItem, PageLink,
+};
+use crate::modules::dates::date_key;
+use crate::modules::frontmatter::{extract, split_list};
+use crate::modules::permalinks::Routes;
+use crate::modules::sections::SECTION_INDEX;
 use crate::utilities::directory::to_title_case;
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Write;
 use std::path::Path;
 
@@ -14,10 +20,17 @@ lazy_static::lazy_static! {
         set.insert("md");
         set.insert("toml");
         set.insert("json");
+        set.insert("yaml");
+        set.insert("yml");
+        set.insert("html");
+        set.insert("htm");
         set
     };
 }
 
+/// The name of the menu rendered as the navigation bar.
+pub const MAIN_MENU: &str = "main";
+
 /// Struct representing the components of a file name
 struct FileNameComponents {
     file_stem: String, // The stem of the file name (without extension)
@@ -62,6 +75,11 @@ pub struct NavigationGenerator;
 impl NavigationGenerator {
     /// Generates a navigation menu as an unordered list of links.
     ///
+    /// The links are the top-level entries of the `main` menu of the
+    /// pages (see `generate_menus`), in the markup of the default
+    /// templates. Templates render `menus.main` instead, which lets
+    /// them choose their own markup.
+    ///
     /// # Arguments
     ///
     /// * `files` - A slice of `FileData` structs containing the compiled HTML files.
@@ -80,7 +98,7 @@ impl NavigationGenerator {
         }
 
         // Filter supported files
-        let files_supported: Vec<&FileData> = files
+        let files_supported: Vec<FileData> = files
             .iter()
             .filter(|file| {
                 let extension = Path::new(&file.name)
@@ -89,6 +107,7 @@ impl NavigationGenerator {
                     .unwrap_or("");
                 SUPPORTED_EXTENSIONS.contains(&extension)
             })
+            .cloned()
             .collect();
 
         // Check if there are supported files
@@ -96,34 +115,41 @@ impl NavigationGenerator {
             return String::new(); // Return an empty string if there are no supported files
         }
 
-        // Map file names to URLs
-        let mut file_links = BTreeMap::new();
-        for file in files_supported {
-            let path = Path::new(&file.name);
-            let file_components = FileNameComponents::from_path(path);
-            let file_name = file_components.file_name();
-
-            if !["index", "404", "privacy", "terms", "offline"]
-                .contains(&file_name)
-            {
-                let url = format!(
-                    "/{}/index.html",
-                    path.with_extension("").display()
-                );
-                file_links.insert(file_name.to_string(), url);
-            }
-        }
+        let menus = Self::generate_menus(
+            &files_supported,
+            &BTreeMap::new(),
+            &Routes::new(),
+        )
+        .unwrap_or_else(|e| {
+            eprintln!("Error generating navigation: {}", e);
+            BTreeMap::new()
+        });
+        Self::render_navigation(
+            menus.get(MAIN_MENU).map(Vec::as_slice).unwrap_or_default(),
+        )
+    }
 
+    /// Renders the top-level entries of a menu as the unordered list of
+    /// links of `generate_navigation`.
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - The entries of the menu.
+    ///
+    /// # Returns
+    ///
+    /// A string containing the HTML code for the navigation menu.
+    pub fn render_navigation(items: &[MenuItem]) -> String {
         // Generate navigation links
         let mut nav_links = String::new();
-        for (file_name, url) in file_links {
+        for item in items {
             write!(
                 &mut nav_links,
                 "<li class=\"nav-item\"><a aria-label=\"{}\" href=\"{}\" title=\"Navigation link for the {} page\" class=\"text-uppercase p-2 \">{}</a></li>",
-                file_name,
-                url,
-                to_title_case(&file_name),
-                &file_name,
+                item.title,
+                item.url,
+                item.title,
+                item.title,
             )
             .unwrap_or_else(|e| {
                 eprintln!("Error writing navigation link: {}", e);
@@ -136,4 +162,461 @@ impl NavigationGenerator {
             nav_links
         )
     }
+
+    /// Returns the URL of the page generated from a content file.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_name` - The name of the content file (e.g. `about.md`).
+    ///
+    /// # Returns
+    ///
+    /// The URL of the generated page, in the `/{name}/index.html` form
+    /// of the default routes. The `index` page maps to `/index.html`
+    /// and a nested `index` page to the index of its directory (e.g.
+    /// `guides/index.md` to `/guides/index.html`).
+    pub fn page_url(file_name: &str) -> String {
+        let path = Self::page_path(file_name);
+        if path.is_empty() {
+            "/index.html".to_string()
+        } else {
+            format!("/{}/index.html", path)
+        }
+    }
+
+    /// Returns whether a content file is the index page of its
+    /// directory: an `index` page, or an `_index` section list page.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_name` - The name of the content file (e.g. `guides/_index.md`).
+    pub fn is_index_page(file_name: &str) -> bool {
+        Path::new(file_name).file_stem().is_some_and(|stem| {
+            stem == "index" || stem == SECTION_INDEX
+        })
+    }
+
+    /// Returns the output directory of a content file, relative to the
+    /// build directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_name` - The name of the content file (e.g. `guides/install.md`).
+    ///
+    /// # Returns
+    ///
+    /// The path without the extension (e.g. `guides/install`), with a
+    /// trailing `index` or `_index` removed. The root `index` page maps
+    /// to an empty string.
+    pub fn page_path(file_name: &str) -> String {
+        let stem = Path::new(file_name)
+            .with_extension("")
+            .to_string_lossy()
+            .replace('\\', "/");
+        if Self::is_index_page(file_name) {
+            stem.rsplit_once('/')
+                .map(|(directory, _)| directory.to_string())
+                .unwrap_or_default()
+        } else {
+            stem
+        }
+    }
+
+    /// Generates the named menus of the site.
+    ///
+    /// Pages opt into a menu through their front matter:
+    ///
+    /// * `menu` - The name of the menu (e.g. `main`), or a list of names.
+    /// * `weight` - The sort weight of the entry (default `0`).
+    /// * `nav_title` - The label of the entry (defaults to `title`).
+    /// * `parent` - The identifier of the parent entry, for submenus.
+    /// * `identifier` - The identifier of the entry (defaults to the
+    ///   file name without its extension).
+    ///
+    /// Entries declared in the site configuration are merged with those
+    /// of the pages. Each menu is sorted by weight, then title, and
+    /// entries are nested under their parent.
+    ///
+    /// When neither the configuration nor a page declares the `main`
+    /// menu, it lists every page without a `menu` field, except the
+    /// index pages.
+    ///
+    /// # Arguments
+    ///
+    /// * `files` - A slice of `FileData` structs containing the source files.
+    /// * `configured` - The menu entries declared in the site configuration.
+    /// * `routes` - The output paths of the pages.
+    ///
+    /// # Returns
+    ///
+    /// A map of menu names to their top-level entries, or an error if
+    /// two entries of a menu share an identifier.
+    pub fn generate_menus(
+        files: &[FileData],
+        configured: &BTreeMap<String, Vec<MenuItem>>,
+        routes: &Routes,
+    ) -> Result<BTreeMap<String, Vec<MenuItem>>, String> {
+        let mut entries: BTreeMap<String, Vec<MenuItem>> =
+            BTreeMap::new();
+
+        for (menu, items) in configured {
+            for item in items {
+                let mut item = item.clone();
+                if item.identifier.is_empty() {
+                    item.identifier = item.title.to_lowercase();
+                }
+                entries.entry(menu.clone()).or_default().push(item);
+            }
+        }
+
+        let pages = summarise(files, routes);
+        for page in &pages {
+            let Some(menus) = page.metadata.get("menu") else {
+                continue;
+            };
+            for menu in split_list(menus) {
+                entries.entry(menu).or_default().push(page.menu_item());
+            }
+        }
+
+        if !entries.contains_key(MAIN_MENU) {
+            let items: Vec<MenuItem> = pages
+                .iter()
+                .filter(|page| {
+                    !page.metadata.contains_key("menu")
+                        && !Self::is_index_page(&page.name)
+                })
+                .map(PageSummary::menu_item)
+                .collect();
+            if !items.is_empty() {
+                entries.insert(MAIN_MENU.to_string(), items);
+            }
+        }
+
+        entries
+            .into_iter()
+            .map(|(menu, items)| {
+                let items = build_menu_tree(&menu, items)?;
+                Ok((menu, items))
+            })
+            .collect()
+    }
+
+    /// Generates the breadcrumb trail of every page.
+    ///
+    /// The trail follows the content directory hierarchy: it starts at
+    /// the home page, goes through every parent directory and ends with
+    /// the page itself. A directory links to its `index` page when it
+    /// has one, otherwise its title-cased name is used and its URL is
+    /// left empty. Labels come from the `nav_title` or `title` front
+    /// matter of each page.
+    ///
+    /// # Arguments
+    ///
+    /// * `files` - A slice of `FileData` structs containing the source files.
+    /// * `routes` - The output paths of the pages.
+    ///
+    /// # Returns
+    ///
+    /// A map of content file names to their breadcrumb trail.
+    pub fn generate_breadcrumbs(
+        files: &[FileData],
+        routes: &Routes,
+    ) -> HashMap<String, Vec<PageLink>> {
+        let pages = summarise(files, routes);
+        let by_path: HashMap<&str, &PageSummary> = pages
+            .iter()
+            .map(|page| (page.path.as_str(), page))
+            .collect();
+
+        let home = by_path.get("").map_or_else(
+            || PageLink::new("Home".to_string(), String::new()),
+            |page| page.link(),
+        );
+
+        pages
+            .iter()
+            .map(|page| {
+                let mut trail = vec![home.clone()];
+                if !page.path.is_empty() {
+                    let segments: Vec<&str> =
+                        page.path.split('/').collect();
+                    for depth in 1..segments.len() {
+                        let directory = segments[..depth].join("/");
+                        trail.push(
+                            by_path
+                                .get(directory.as_str())
+                                .map_or_else(
+                                    || {
+                                        PageLink::new(
+                                            to_title_case(
+                                                segments[depth - 1],
+                                            ),
+                                            String::new(),
+                                        )
+                                    },
+                                    |parent| parent.link(),
+                                ),
+                        );
+                    }
+                    trail.push(page.link());
+                }
+                (page.name.clone(), trail)
+            })
+            .collect()
+    }
+
+    /// Generates the previous and next page links of every page.
+    ///
+    /// Pages are grouped by section, the content directory they live
+    /// in, and ordered by `weight`, then `date` (undated pages last),
+    /// then title. Pages at the root of the content directory and
+    /// directory `index` pages do not belong to a section and get no
+    /// links.
+    ///
+    /// # Arguments
+    ///
+    /// * `files` - A slice of `FileData` structs containing the source files.
+    /// * `routes` - The output paths of the pages.
+    ///
+    /// # Returns
+    ///
+    /// A map of content file names to their adjacent pages.
+    pub fn generate_adjacent_pages(
+        files: &[FileData],
+        routes: &Routes,
+    ) -> HashMap<String, AdjacentPages> {
+        let mut sections: BTreeMap<String, Vec<PageSummary>> =
+            BTreeMap::new();
+        for page in summarise(files, routes) {
+            let is_index = Self::is_index_page(&page.name);
+            if let Some((section, _)) = page.path.rsplit_once('/') {
+                if !is_index {
+                    sections
+                        .entry(section.to_string())
+                        .or_default()
+                        .push(page);
+                }
+            }
+        }
+
+        let mut adjacent = HashMap::new();
+        for pages in sections.values_mut() {
+            // Undated pages come after dated pages of the same weight
+            pages.sort_by_cached_key(|page| {
+                let date = page
+                    .metadata
+                    .get("date")
+                    .and_then(|date| date_key(date));
+                (page.weight(), date.is_none(), date, page.label())
+            });
+            for (index, page) in pages.iter().enumerate() {
+                adjacent.insert(
+                    page.name.clone(),
+                    AdjacentPages {
+                        previous: index
+                            .checked_sub(1)
+                            .map(|previous| pages[previous].link()),
+                        next: pages
+                            .get(index + 1)
+                            .map(PageSummary::link),
+                    },
+                );
+            }
+        }
+        adjacent
+    }
+
+    /// Marks the entries of the menus that link to the current page.
+    ///
+    /// The entry whose URL matches `url` gets `active` set, and that
+    /// entry and all its ancestors get `active_trail` set, so templates
+    /// can highlight the current page and expand its submenu.
+    ///
+    /// # Arguments
+    ///
+    /// * `menus` - The menus returned by `generate_menus`.
+    /// * `url` - The URL of the page being rendered.
+    ///
+    /// # Returns
+    ///
+    /// A copy of the menus with the active markers set.
+    pub fn mark_active(
+        menus: &BTreeMap<String, Vec<MenuItem>>,
+        url: &str,
+    ) -> BTreeMap<String, Vec<MenuItem>> {
+        let mut menus = menus.clone();
+        for items in menus.values_mut() {
+            for item in items.iter_mut() {
+                mark_active_item(item, url);
+            }
+        }
+        menus
+    }
+}
+
+/// The front matter of a content page, as needed to build navigation.
+struct PageSummary {
+    /// The name of the content file.
+    name: String,
+    /// The path of the content file without its extension (see
+    /// `page_path`), which places the page in the content hierarchy.
+    path: String,
+    /// The URL of the page.
+    url: String,
+    /// The front matter of the page.
+    metadata: HashMap<String, String>,
+}
+
+impl PageSummary {
+    /// Returns the navigation label of the page: its `nav_title`, its
+    /// `title`, or its title-cased file name.
+    fn label(&self) -> String {
+        self.metadata
+            .get("nav_title")
+            .or_else(|| self.metadata.get("title"))
+            .cloned()
+            .unwrap_or_else(|| {
+                let path = Path::new(&self.name);
+                to_title_case(
+                    FileNameComponents::from_path(path).file_name(),
+                )
+            })
+    }
+
+    /// Returns the `weight` of the page, or `0`.
+    fn weight(&self) -> i64 {
+        self.metadata
+            .get("weight")
+            .and_then(|weight| weight.trim().parse::<i64>().ok())
+            .unwrap_or_default()
+    }
+
+    /// Returns a link to the page.
+    fn link(&self) -> PageLink {
+        PageLink::new(self.label(), self.url.clone())
+    }
+
+    /// Returns the menu entry of the page. Its identifier defaults to
+    /// the file name without its extension.
+    fn menu_item(&self) -> MenuItem {
+        MenuItem {
+            identifier: self
+                .metadata
+                .get("identifier")
+                .cloned()
+                .unwrap_or_else(|| {
+                    Path::new(&self.name)
+                        .with_extension("")
+                        .display()
+                        .to_string()
+                }),
+            title: self.label(),
+            url: self.url.clone(),
+            weight: self.weight(),
+            parent: self
+                .metadata
+                .get("parent")
+                .cloned()
+                .unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Extracts the front matter of the supported content files.
+fn summarise(files: &[FileData], routes: &Routes) -> Vec<PageSummary> {
+    files
+        .iter()
+        .filter(|file| {
+            let extension = Path::new(&file.name)
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .unwrap_or("");
+            SUPPORTED_EXTENSIONS.contains(extension)
+        })
+        .map(|file| PageSummary {
+            name: file.name.clone(),
+            path: NavigationGenerator::page_path(&file.name),
+            url: routes.url(&file.name),
+            metadata: extract(&file.content),
+        })
+        .collect()
+}
+
+/// Sorts the flat entries of a menu and nests them under their parent.
+///
+/// Entries whose parent does not exist in the menu are kept at the top
+/// level, so a typo in `parent` never hides a page. Two entries sharing
+/// an identifier are an error, as only one of them could be a parent.
+fn build_menu_tree(
+    menu: &str,
+    mut items: Vec<MenuItem>,
+) -> Result<Vec<MenuItem>, String> {
+    items.sort_by(|a, b| {
+        a.weight.cmp(&b.weight).then_with(|| a.title.cmp(&b.title))
+    });
+
+    let mut identifiers = HashSet::new();
+    for item in &items {
+        if !identifiers.insert(item.identifier.clone()) {
+            return Err(format!(
+                "Duplicate identifier `{}` in the `{}` menu",
+                item.identifier, menu
+            ));
+        }
+    }
+    let mut attached = HashSet::new();
+
+    let mut roots: Vec<MenuItem> = items
+        .iter()
+        .filter(|item| {
+            item.parent.is_empty()
+                || !identifiers.contains(&item.parent)
+        })
+        .cloned()
+        .collect();
+    for root in roots.iter_mut() {
+        attached.insert(root.identifier.clone());
+        attach_children(root, &items, &mut attached);
+    }
+
+    // Entries caught in a parent cycle are never reached from a root.
+    roots.extend(
+        items
+            .into_iter()
+            .filter(|item| !attached.contains(&item.identifier)),
+    );
+    Ok(roots)
+}
+
+/// Recursively moves the entries whose parent is `item` into its children.
+fn attach_children(
+    item: &mut MenuItem,
+    items: &[MenuItem],
+    attached: &mut HashSet<String>,
+) {
+    for candidate in items {
+        if candidate.parent == item.identifier
+            && !attached.contains(&candidate.identifier)
+        {
+            attached.insert(candidate.identifier.clone());
+            let mut child = candidate.clone();
+            attach_children(&mut child, items, attached);
+            item.children.push(child);
+        }
+    }
+}
+
+/// Sets the active markers of an entry and its children.
+///
+/// Returns `true` when the entry is on the active trail.
+fn mark_active_item(item: &mut MenuItem, url: &str) -> bool {
+    item.active = item.url == url;
+    let mut trail = item.active;
+    for child in item.children.iter_mut() {
+        trail |= mark_active_item(child, url);
+    }
+    item.active_trail = trail;
+    trail
 }
@@ -0,0 +1,325 @@
+// Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::models::data::{Paginator, SiteConfig};
+use crate::modules::authors::author_page_name;
+use crate::modules::i18n::{language_pages, split_language};
+use crate::modules::redirects::generate_redirect_html;
+use crate::modules::series::field;
+use crate::utilities::minification::minify_html;
+use crate::utilities::template::render_template_with_data;
+use serde_json::{json, Map, Value};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs, io,
+    path::Path,
+};
+
+/// The template of the pagination links rendered below a list.
+const PAGINATION_NAV_TEMPLATE: &str = r#"{{#if paginator.prev_url}}<a href="{{paginator.prev_url}}" rel="prev">Previous</a> {{/if}}<span>Page {{paginator.current}} of {{paginator.total_pages}}</span>{{#if paginator.next_url}} <a href="{{paginator.next_url}}" rel="next">Next</a>{{/if}}"#;
+
+/// Returns the URL of a page of a paginated list.
+///
+/// The first page lives at the root of the list, the following pages
+/// at `page/<number>/` below it.
+///
+/// # Arguments
+///
+/// * `base_url` - The URL of the list root, ending with a `/` (e.g. `/tags/`).
+/// * `number` - The number of the page, starting at 1.
+///
+/// # Returns
+///
+/// The URL of the page.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::pagination::page_url;
+///
+/// assert_eq!(page_url("/tags/", 1), "/tags/index.html");
+/// assert_eq!(page_url("/tags/", 2), "/tags/page/2/index.html");
+/// ```
+pub fn page_url(base_url: &str, number: usize) -> String {
+    if number <= 1 {
+        format!("{}index.html", base_url)
+    } else {
+        format!("{}page/{}/index.html", base_url, number)
+    }
+}
+
+/// Splits a list of items into pages.
+///
+/// An empty list still yields one, empty, page so that the list page
+/// is rendered.
+///
+/// # Arguments
+///
+/// * `items` - The items to split.
+/// * `per_page` - The maximum number of items on a page, or `0` to keep all items on one page.
+/// * `base_url` - The URL of the list root, ending with a `/`.
+///
+/// # Returns
+///
+/// A vector of pages, each with its `Paginator` and its items.
+pub fn paginate<T: Clone>(
+    items: &[T],
+    per_page: usize,
+    base_url: &str,
+) -> Vec<(Paginator, Vec<T>)> {
+    let chunks: Vec<Vec<T>> = if per_page == 0 || items.is_empty() {
+        vec![items.to_vec()]
+    } else {
+        items.chunks(per_page).map(|chunk| chunk.to_vec()).collect()
+    };
+    let total_pages = chunks.len();
+
+    chunks
+        .into_iter()
+        .enumerate()
+        .map(|(index, chunk)| {
+            let current = index + 1;
+            let paginator = Paginator {
+                current,
+                total_pages,
+                total_items: items.len(),
+                per_page: if per_page == 0 {
+                    items.len()
+                } else {
+                    per_page
+                },
+                url: page_url(base_url, current),
+                first_url: page_url(base_url, 1),
+                last_url: page_url(base_url, total_pages),
+                prev_url: if current > 1 {
+                    page_url(base_url, current - 1)
+                } else {
+                    String::new()
+                },
+                next_url: if current < total_pages {
+                    page_url(base_url, current + 1)
+                } else {
+                    String::new()
+                },
+            };
+            (paginator, chunk)
+        })
+        .collect()
+}
+
+/// Returns the template data of a page of a list.
+///
+/// # Arguments
+///
+/// * `paginator` - The paginator of the page.
+/// * `items` - The items on the page.
+///
+/// # Returns
+///
+/// The fields of the paginator as a JSON object, with the `items` of the
+/// page and the `pages` of the list, each with its `number`, `url` and
+/// whether it is the `current` page. A list on a single page has no
+/// `pages`, so that templates only show the navigation of paginated
+/// lists.
+pub fn paginator_data(paginator: &Paginator, items: &[Value]) -> Value {
+    let base_url = paginator
+        .first_url
+        .strip_suffix("index.html")
+        .unwrap_or(&paginator.first_url);
+    let pages: Vec<Value> = if paginator.total_pages > 1 {
+        (1..=paginator.total_pages)
+            .map(|number| {
+                json!({
+                    "number": number,
+                    "url": page_url(base_url, number),
+                    "current": number == paginator.current,
+                })
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let mut data =
+        serde_json::to_value(paginator).unwrap_or(Value::Null);
+    data["items"] = Value::Array(items.to_vec());
+    data["pages"] = Value::Array(pages);
+    data
+}
+
+/// Collects the items of every list page.
+///
+/// The home page of each language lists the dated pages of the
+/// language, newest first, section list pages their pages, archive pages
+/// the pages of their period, author pages the pages of their author and
+/// series overview pages the parts of their series.
+///
+/// # Arguments
+///
+/// * `entries` - The entries of all pages, as created by `page_entry`.
+/// * `sections` - The data of the section list pages.
+/// * `archives` - The data of the archive pages.
+/// * `series` - The series navigation of the pages.
+/// * `author_pages` - The pages of every author.
+/// * `config` - The site configuration.
+///
+/// # Returns
+///
+/// A map of the content file names of the list pages to the entries
+/// they list.
+pub fn list_items(
+    entries: &[Value],
+    sections: &HashMap<String, Value>,
+    archives: &HashMap<String, Value>,
+    series: &HashMap<String, Value>,
+    author_pages: &BTreeMap<String, Vec<String>>,
+    config: &SiteConfig,
+) -> HashMap<String, Vec<Value>> {
+    let array = |value: &Value, key: &str| {
+        value.get(key).and_then(Value::as_array).cloned()
+    };
+    let mut items: HashMap<String, Vec<Value>> = HashMap::new();
+
+    let pages = language_pages(entries, config);
+    for entry in entries {
+        let name = field(entry, "name");
+        let (language, key) = split_language(name, config);
+        if Path::new(&key).with_extension("") == Path::new("index") {
+            let home_items = pages
+                .get(&language)
+                .map(|pages| {
+                    pages.iter().map(|&page| page.clone()).collect()
+                })
+                .unwrap_or_default();
+            let _ = items.insert(name.to_string(), home_items);
+        }
+    }
+
+    for (name, data) in sections.iter().chain(archives) {
+        if let Some(pages) = array(data, "pages") {
+            let _ = items.insert(name.clone(), pages);
+        }
+    }
+    for (name, data) in series {
+        if data["index"] == 0 {
+            if let Some(parts) = array(data, "parts") {
+                let _ = items.insert(name.clone(), parts);
+            }
+        }
+    }
+    for (id, names) in author_pages {
+        let author_items = names
+            .iter()
+            .filter_map(|name| {
+                entries
+                    .iter()
+                    .find(|entry| field(entry, "name") == name)
+                    .cloned()
+            })
+            .collect();
+        let _ = items.insert(author_page_name(id), author_items);
+    }
+
+    items
+}
+
+/// Generates the `rel="prev"` and `rel="next"` link tags of a page.
+///
+/// # Arguments
+///
+/// * `paginator` - The paginator of the page.
+///
+/// # Returns
+///
+/// A `String` containing the link tags, empty for a single page.
+pub fn generate_pagination_links(paginator: &Paginator) -> String {
+    let mut links = Vec::new();
+    if !paginator.prev_url.is_empty() {
+        links.push(format!(
+            "<link rel=\"prev\" href=\"{}\">",
+            paginator.prev_url
+        ));
+    }
+    if !paginator.next_url.is_empty() {
+        links.push(format!(
+            "<link rel=\"next\" href=\"{}\">",
+            paginator.next_url
+        ));
+    }
+    links.join("\n")
+}
+
+/// Generates the navigation between the pages of a list.
+///
+/// # Arguments
+///
+/// * `paginator` - The paginator of the page.
+///
+/// # Returns
+///
+/// A `String` containing a `<nav class="pagination">` element, or an
+/// empty string for a single page.
+pub fn generate_pagination_nav(paginator: &Paginator) -> String {
+    if paginator.total_pages <= 1 {
+        return String::new();
+    }
+
+    let mut data = Map::new();
+    data.insert(
+        "paginator".to_string(),
+        serde_json::to_value(paginator).unwrap_or(Value::Null),
+    );
+    let nav = render_template_with_data(
+        PAGINATION_NAV_TEMPLATE,
+        &HashMap::new(),
+        &data,
+    )
+    .unwrap_or_default();
+
+    format!("<nav class=\"pagination\">{}</nav>", nav)
+}
+
+/// Writes the pages of a paginated list.
+///
+/// The first page is written to `index.html` in `output_dir` and the
+/// following ones to `page/<number>/index.html`. The URL of the first
+/// page of a paginated list, `page/1/index.html`, redirects to the list
+/// root. The pages are minified.
+///
+/// # Arguments
+///
+/// * `output_dir` - The directory of the list.
+/// * `base_url` - The URL of the list root, ending with a `/`.
+/// * `pages` - The HTML of the pages, in order.
+///
+/// # Returns
+///
+/// Returns an `io::Result<()>` which is `Ok` if all pages were written.
+pub fn write_paginated_pages(
+    output_dir: &Path,
+    base_url: &str,
+    pages: &[String],
+) -> io::Result<()> {
+    for (index, html) in pages.iter().enumerate() {
+        let dir = if index == 0 {
+            output_dir.to_path_buf()
+        } else {
+            output_dir.join("page").join((index + 1).to_string())
+        };
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("index.html");
+        fs::write(&path, html)?;
+        fs::write(&path, minify_html(&path)?)?;
+    }
+
+    if pages.len() > 1 {
+        let alias_dir = output_dir.join("page").join("1");
+        fs::create_dir_all(&alias_dir)?;
+        fs::write(
+            alias_dir.join("index.html"),
+            generate_redirect_html(&page_url(base_url, 1)),
+        )?;
+    }
+
+    Ok(())
+}
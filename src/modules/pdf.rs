@@ -1,3 +1,4 @@
+use crate::modules::math::ascii_math;
 use printpdf::{BuiltinFont, Mm, PdfDocument};
 use regex::Regex;
 use std::error::Error;
@@ -95,7 +96,8 @@ pub fn generate_pdf(
     let font_size = 13.0;
     let line_height = Mm(6.0);
 
-    let cleaned_content = remove_css_classes(plain_text)?;
+    // The built-in font has no glyphs for most math symbols
+    let cleaned_content = ascii_math(&remove_css_classes(plain_text)?);
     let mut remaining_text = cleaned_content.trim_start();
     let mut rect_y = page_height - margin;
 
@@ -0,0 +1,462 @@
+// Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::models::data::FileData;
+use crate::modules::dates::date_key;
+use crate::modules::frontmatter::extract;
+use crate::modules::navigation::NavigationGenerator;
+use crate::modules::redirects::{
+    HTACCESS_FILE, NETLIFY_REDIRECTS_FILE, NGINX_MAP_FILE,
+    SERVE_REDIRECTS_FILE,
+};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::Path,
+};
+
+/// The output paths of the pages, keyed by content file name.
+///
+/// Pages without a resolved route fall back to the path derived from
+/// their file name (see `NavigationGenerator::page_path`).
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Routes {
+    paths: HashMap<String, String>,
+}
+
+impl Routes {
+    /// Creates an empty set of routes.
+    pub fn new() -> Self {
+        Routes::default()
+    }
+
+    /// Sets the output path of a content file.
+    pub fn insert(&mut self, file_name: &str, path: &str) {
+        let _ =
+            self.paths.insert(file_name.to_string(), path.to_string());
+    }
+
+    /// Returns whether a content file has a resolved route.
+    pub fn contains(&self, file_name: &str) -> bool {
+        self.paths.contains_key(file_name)
+    }
+
+    /// Returns the output path of a content file, relative to the build
+    /// directory (e.g. `2024/05/hello`, or an empty string for the home
+    /// page).
+    pub fn path(&self, file_name: &str) -> String {
+        self.paths.get(file_name).cloned().unwrap_or_else(|| {
+            NavigationGenerator::page_path(file_name)
+        })
+    }
+
+    /// Returns the URL of the page generated from a content file (e.g.
+    /// `/2024/05/hello/index.html`).
+    pub fn url(&self, file_name: &str) -> String {
+        let path = self.path(file_name);
+        if path.is_empty() {
+            "/index.html".to_string()
+        } else {
+            format!("/{}/index.html", path)
+        }
+    }
+}
+
+/// Returns the name under which a page is written to the build directory.
+///
+/// `write_files_to_build_directory` writes a file named `<path>/index.md`
+/// to the `<path>` directory, and `index.md` to the build directory
+/// itself.
+///
+/// # Arguments
+///
+/// * `path` - The output path of the page, as returned by `Routes::path`.
+///
+/// # Returns
+///
+/// The name of the compiled file.
+pub fn output_name(path: &str) -> String {
+    if path.is_empty() {
+        "index.md".to_string()
+    } else {
+        format!("{}/index.md", path)
+    }
+}
+
+/// The files written beside every page.
+const PAGE_FILES: [&str; 4] =
+    ["manifest.json", "robots.txt", "rss.xml", "sitemap.xml"];
+
+/// The site-wide files written to the build directory only.
+const SITE_FILES: [&str; 8] = [
+    "CNAME",
+    "humans.txt",
+    "main.js",
+    "sw.js",
+    NETLIFY_REDIRECTS_FILE,
+    HTACCESS_FILE,
+    NGINX_MAP_FILE,
+    SERVE_REDIRECTS_FILE,
+];
+
+/// The path of the tags page, filled in from the page of the same path.
+const TAGS_PATH: &str = "tags";
+
+/// Returns whether the build writes to a path.
+///
+/// The path is written when it is the path of a page, a file written
+/// beside a page or at the root of the site, the tags page, or a page of
+/// a list split into several pages (e.g. `blog/page/2`).
+///
+/// # Arguments
+///
+/// * `path` - The path, relative to the build directory.
+/// * `page_paths` - The output paths of the pages.
+///
+/// # Returns
+///
+/// `true` if a page or a generated file would be overwritten.
+pub fn is_generated_path(
+    path: &str,
+    page_paths: &HashSet<String>,
+) -> bool {
+    let is_list =
+        |list: &str| list == TAGS_PATH || page_paths.contains(list);
+    let (dir, name) = path.rsplit_once('/').unwrap_or(("", path));
+    let segments: Vec<&str> = path.split('/').collect();
+
+    is_list(path)
+        || (PAGE_FILES.contains(&name)
+            && (dir.is_empty() || page_paths.contains(dir)))
+        || (dir.is_empty() && SITE_FILES.contains(&name))
+        || segments.windows(2).enumerate().any(|(index, pair)| {
+            pair[0] == "page"
+                && pair[1].parse::<usize>().is_ok()
+                && is_list(&segments[..index].join("/"))
+        })
+}
+
+/// Converts a text into a URL slug.
+///
+/// Latin letters with diacritics, ligatures, Greek and Cyrillic are
+/// transliterated to ASCII. Other letters and digits, such as CJK
+/// characters, are kept. Everything else becomes a single `-`.
+///
+/// # Arguments
+///
+/// * `text` - The text to convert, such as a page title.
+///
+/// # Returns
+///
+/// The lowercase slug, without leading or trailing dashes.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::permalinks::slugify;
+///
+/// assert_eq!(slugify("Crème Brûlée & Co."), "creme-brulee-co");
+/// assert_eq!(slugify("Привет, мир"), "privet-mir");
+/// ```
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.chars().flat_map(char::to_lowercase) {
+        match transliterate(c) {
+            Some(ascii) => slug.push_str(ascii),
+            None if c.is_alphanumeric() => slug.push(c),
+            None => slug.push('-'),
+        }
+    }
+
+    slug.split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Returns the ASCII transliteration of a lowercase character, if any.
+fn transliterate(c: char) -> Option<&'static str> {
+    let ascii = match c {
+        'a'..='z' | '0'..='9' => return None,
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => {
+            "a"
+        }
+        'æ' => "ae",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => {
+            "e"
+        }
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => {
+            "i"
+        }
+        'ĳ' => "ij",
+        'ĵ' => "j",
+        'ķ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => {
+            "o"
+        }
+        'œ' => "oe",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' | 'ș' => "s",
+        'ß' => "ss",
+        'ţ' | 'ť' | 'ŧ' | 'ț' => "t",
+        'þ' => "th",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => {
+            "u"
+        }
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        // Greek
+        'α' | 'ά' => "a",
+        'β' => "v",
+        'γ' => "g",
+        'δ' => "d",
+        'ε' | 'έ' => "e",
+        'ζ' => "z",
+        'η' | 'ή' => "i",
+        'θ' => "th",
+        'ι' | 'ί' | 'ϊ' | 'ΐ' => "i",
+        'κ' => "k",
+        'λ' => "l",
+        'μ' => "m",
+        'ν' => "n",
+        'ξ' => "x",
+        'ο' | 'ό' => "o",
+        'π' => "p",
+        'ρ' => "r",
+        'σ' | 'ς' => "s",
+        'τ' => "t",
+        'υ' | 'ύ' | 'ϋ' | 'ΰ' => "y",
+        'φ' => "f",
+        'χ' => "ch",
+        'ψ' => "ps",
+        'ω' | 'ώ' => "o",
+        // Cyrillic
+        'а' => "a",
+        'б' => "b",
+        'в' => "v",
+        'г' => "g",
+        'д' => "d",
+        'е' | 'э' => "e",
+        'ё' => "yo",
+        'ж' => "zh",
+        'з' => "z",
+        'и' | 'і' => "i",
+        'ї' => "yi",
+        'й' => "y",
+        'к' => "k",
+        'л' => "l",
+        'м' => "m",
+        'н' => "n",
+        'о' => "o",
+        'п' => "p",
+        'р' => "r",
+        'с' => "s",
+        'т' => "t",
+        'у' => "u",
+        'ф' => "f",
+        'х' => "kh",
+        'ц' => "ts",
+        'ч' => "ch",
+        'ш' => "sh",
+        'щ' => "shch",
+        'ъ' | 'ь' => "",
+        'ы' => "y",
+        'ю' => "yu",
+        'я' => "ya",
+        'є' => "ye",
+        'ґ' => "g",
+        _ => return None,
+    };
+    Some(ascii)
+}
+
+/// Resolves the output path of a page.
+///
+/// The permalink pattern of the page's section is used when one is
+/// configured; the most specific section wins. A pattern is a path made
+/// of literal segments and the following placeholders:
+///
+/// * `:year`, `:month`, `:day` - The parts of the page `date`.
+/// * `:slug` - The `slug` front matter, or the slug of the title.
+/// * `:title` - The slug of the title.
+/// * `:section` - The name of the directory the page lives in.
+/// * `:filename` - The file name without its extension.
+///
+/// Without a pattern, the path derived from the file name is used, with
+/// its last segment replaced by the `slug` front matter when set. Index
+/// and `_index` pages always keep the path of their directory.
+///
+/// # Arguments
+///
+/// * `file_name` - The name of the content file.
+/// * `metadata` - The front matter of the page.
+/// * `patterns` - The permalink patterns, keyed by section.
+///
+/// # Returns
+///
+/// The output path of the page, relative to the build directory, or an
+/// error when the pattern uses a date the page does not have, or a slug
+/// or title that has no letters or digits.
+pub fn resolve_path(
+    file_name: &str,
+    metadata: &HashMap<String, String>,
+    patterns: &BTreeMap<String, String>,
+) -> Result<String, String> {
+    let default_path = NavigationGenerator::page_path(file_name);
+    if NavigationGenerator::is_index_page(file_name) {
+        return Ok(default_path);
+    }
+    let stem = Path::new(file_name)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let section = Path::new(file_name)
+        .parent()
+        .map(|parent| parent.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_default();
+    let field = |key: &str| {
+        metadata
+            .get(key)
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    };
+    let slug_of = |key: &str, text: &str| {
+        let slug = slugify(text);
+        if slug.is_empty() {
+            Err(format!(
+                "The {} `{}` of `{}` gives an empty permalink slug",
+                key, text, file_name
+            ))
+        } else {
+            Ok(slug)
+        }
+    };
+    let title = match metadata.get("title") {
+        Some(title) => slug_of("title", title),
+        None => slug_of("file name", &stem),
+    };
+    let slug = field("slug").map(|slug| slug_of("slug", slug));
+
+    let pattern = patterns
+        .iter()
+        .filter(|(key, _)| {
+            let key = key.trim_matches('/');
+            section == key || section.starts_with(&format!("{}/", key))
+        })
+        .max_by_key(|(key, _)| key.trim_matches('/').len())
+        .map(|(_, pattern)| pattern);
+
+    let Some(pattern) = pattern else {
+        return Ok(
+            match (slug.transpose()?, default_path.rsplit_once('/')) {
+                (Some(slug), Some((parent, _))) => {
+                    format!("{}/{}", parent, slug)
+                }
+                (Some(slug), None) => slug,
+                (None, _) => default_path,
+            },
+        );
+    };
+
+    let date = field("date").and_then(date_key);
+    let date_part = |segment: &str| {
+        date.ok_or_else(|| {
+            format!(
+                "Page `{}` has no valid `date` for the `{}` segment of \
+                 its permalink `{}`",
+                file_name, segment, pattern
+            )
+        })
+    };
+    let section_name =
+        section.rsplit('/').next().unwrap_or_default().to_string();
+    let segments = pattern
+        .split('/')
+        .map(|segment| {
+            Ok(match segment {
+                ":year" => date_part(segment)?.0.to_string(),
+                ":month" => format!("{:02}", date_part(segment)?.1),
+                ":day" => format!("{:02}", date_part(segment)?.2),
+                ":slug" => match &slug {
+                    Some(slug) => slug.clone()?,
+                    None => title.clone()?,
+                },
+                ":title" => title.clone()?,
+                ":section" => section_name.clone(),
+                ":filename" => stem.clone(),
+                literal => literal.to_string(),
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    Ok(segments
+        .into_iter()
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("/"))
+}
+
+/// Resolves the output paths of all pages and detects collisions.
+///
+/// # Arguments
+///
+/// * `files` - The content files.
+/// * `patterns` - The permalink patterns, keyed by section.
+///
+/// # Returns
+///
+/// The `Routes` of the pages, or an error naming the page whose path
+/// cannot be resolved, the pages that map to the same output path, or
+/// the page that maps to the path of a generated file.
+pub fn resolve_routes(
+    files: &[FileData],
+    patterns: &BTreeMap<String, String>,
+) -> Result<Routes, String> {
+    collect_routes(files, |file| {
+        resolve_path(&file.name, &extract(&file.content), patterns)
+    })
+}
+
+/// Builds the routes of the pages from a function returning the output
+/// path of a page, and detects collisions with other pages and with the
+/// files the build generates (see `is_generated_path`).
+pub(crate) fn collect_routes(
+    files: &[FileData],
+    path_of: impl Fn(&FileData) -> Result<String, String>,
+) -> Result<Routes, String> {
+    let mut routes = Routes::new();
+    let mut owners: BTreeMap<String, &str> = BTreeMap::new();
+
+    for file in files {
+        let path = path_of(file)?;
+        if let Some(owner) = owners.insert(path.clone(), &file.name) {
+            return Err(format!(
+                "Permalink collision: `{}` and `{}` both map to `/{}`",
+                owner, file.name, path
+            ));
+        }
+        routes.insert(&file.name, &path);
+    }
+
+    let mut other_paths: HashSet<String> =
+        owners.keys().cloned().collect();
+    for (path, owner) in &owners {
+        let _ = other_paths.remove(path);
+        if path != TAGS_PATH && is_generated_path(path, &other_paths) {
+            return Err(format!(
+                "Permalink collision: `{}` maps to `/{}`, which is \
+                 generated by the build",
+                owner, path
+            ));
+        }
+        let _ = other_paths.insert(path.clone());
+    }
+
+    Ok(routes)
+}
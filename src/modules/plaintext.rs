@@ -1,6 +1,7 @@
 // Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use crate::modules::math::math_to_text;
 use crate::modules::preprocessor::preprocess_content;
 use crate::utilities::directory::extract_front_matter;
 use pulldown_cmark::{Parser, Event, Tag};
@@ -63,8 +64,10 @@ pub fn generate_plain_text(
 
     // Extract front matter from content
     let markdown_content = extract_front_matter(content);
+    // Replace the LaTeX formulas with their plain text
+    let markdown_content = math_to_text(markdown_content);
     // Preprocess content to update class attributes and image tags
-    let processed_content = preprocess_content(markdown_content, &class_regex, &img_regex)?;
+    let processed_content = preprocess_content(&markdown_content, &class_regex, &img_regex)?;
 
     // Further preprocess to remove Markdown link references.
     let no_markdown_links = link_ref_regex.replace_all(&processed_content, "$1");
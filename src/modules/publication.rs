@@ -0,0 +1,160 @@
+// Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::models::data::{BuildOptions, FileData, PublicationStatus};
+use crate::modules::dates::date_key;
+use crate::modules::frontmatter::extract;
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Returns today's date in the site timezone as a `(year, month, day)`
+/// tuple.
+///
+/// # Arguments
+///
+/// * `offset` - The offset from UTC of the site, in minutes.
+///
+/// # Panics
+///
+/// Panics if the system clock is set before 1970, as no page could
+/// then be reliably classified as published or scheduled.
+pub fn today(offset: i32) -> (i32, u32, u32) {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("the system clock is set before 1970")
+        .as_secs() as i64
+        + i64::from(offset) * 60;
+
+    // Convert the days since 1970-01-01 to a civil date
+    let days = seconds.div_euclid(86_400) + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460
+        + day_of_era / 36_524
+        - day_of_era / 146_096)
+        / 365;
+    let day_of_year = day_of_era
+        - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year as i32, month, day)
+}
+
+/// Determines the publication status of a page from its front matter.
+///
+/// A page is a draft when its `draft` field is `true`. It is scheduled
+/// when its `publish_date`, or else its `date`, is after `today`, and
+/// expired when its `expiry_date` is on or before `today`. Dates that
+/// cannot be parsed are ignored.
+///
+/// # Arguments
+///
+/// * `metadata` - The front matter of the page.
+/// * `today` - The current date, as a `(year, month, day)` tuple.
+///
+/// # Returns
+///
+/// The `PublicationStatus` of the page.
+pub fn publication_status(
+    metadata: &HashMap<String, String>,
+    today: (i32, u32, u32),
+) -> PublicationStatus {
+    let date =
+        |key: &str| metadata.get(key).and_then(|value| date_key(value));
+
+    if metadata
+        .get("draft")
+        .is_some_and(|draft| draft.trim().eq_ignore_ascii_case("true"))
+    {
+        PublicationStatus::Draft
+    } else if date("expiry_date").is_some_and(|expiry| expiry <= today)
+    {
+        PublicationStatus::Expired
+    } else if date("publish_date")
+        .or_else(|| date("date"))
+        .is_some_and(|publish| publish > today)
+    {
+        PublicationStatus::Scheduled
+    } else {
+        PublicationStatus::Published
+    }
+}
+
+/// Returns whether a page with the given status is part of the build.
+///
+/// # Arguments
+///
+/// * `status` - The publication status of the page.
+/// * `options` - The options of the build.
+///
+/// # Returns
+///
+/// `true` if the page is built.
+pub fn is_included(
+    status: PublicationStatus,
+    options: &BuildOptions,
+) -> bool {
+    match status {
+        PublicationStatus::Published => true,
+        PublicationStatus::Draft => options.drafts,
+        PublicationStatus::Scheduled => options.future,
+        PublicationStatus::Expired => false,
+    }
+}
+
+/// Removes the unpublished pages from a list of content files.
+///
+/// # Arguments
+///
+/// * `files` - The content files.
+/// * `options` - The options of the build.
+/// * `today` - The current date, as a `(year, month, day)` tuple.
+///
+/// # Returns
+///
+/// The content files that are part of the build.
+pub fn filter_published(
+    files: Vec<FileData>,
+    options: &BuildOptions,
+    today: (i32, u32, u32),
+) -> Vec<FileData> {
+    files
+        .into_iter()
+        .filter(|file| {
+            is_included(
+                publication_status(&extract(&file.content), today),
+                options,
+            )
+        })
+        .collect()
+}
+
+/// Generates the marker shown on pages built for preview.
+///
+/// # Arguments
+///
+/// * `status` - The publication status of the page.
+///
+/// # Returns
+///
+/// A `String` containing the HTML of the marker, or an empty string for
+/// a published page.
+pub fn status_marker(status: PublicationStatus) -> String {
+    let label = match status {
+        PublicationStatus::Published => return String::new(),
+        PublicationStatus::Draft => "Draft",
+        PublicationStatus::Scheduled => "Scheduled",
+        PublicationStatus::Expired => "Expired",
+    };
+    format!(
+        "<p class=\"publication-status\" role=\"status\"><strong>{}</strong></p>",
+        label
+    )
+}
@@ -0,0 +1,375 @@
+// Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::models::data::FileData;
+use crate::modules::frontmatter::{extract, split_list};
+use crate::modules::permalinks::{is_generated_path, Routes};
+use crate::modules::urls::prefix_url;
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs, io,
+    path::{Component, Path, PathBuf},
+};
+
+/// The name of the Netlify and Cloudflare Pages redirects manifest.
+pub const NETLIFY_REDIRECTS_FILE: &str = "_redirects";
+
+/// The name of the Apache redirects manifest.
+pub const HTACCESS_FILE: &str = ".htaccess";
+
+/// The name of the nginx redirects map.
+pub const NGINX_MAP_FILE: &str = "redirects.map";
+
+/// The name of the redirects manifest read by the development server.
+///
+/// Unlike `_redirects`, its aliases and targets are not prefixed with
+/// the path of the site, as the server serves the build directory at
+/// the root.
+pub const SERVE_REDIRECTS_FILE: &str = ".redirects";
+
+/// Normalizes an alias into a path relative to the site root.
+///
+/// Leading and trailing slashes and a trailing `index.html` are
+/// removed, so `/old/post/`, `old/post` and `/old/post/index.html` are
+/// the same alias.
+///
+/// # Arguments
+///
+/// * `alias` - The alias as written in the front matter.
+///
+/// # Returns
+///
+/// The normalized alias (e.g. `old/post` or `old.html`).
+pub fn normalize_alias(alias: &str) -> String {
+    let alias = alias.trim().trim_matches('/');
+    alias
+        .strip_suffix("index.html")
+        .unwrap_or(alias)
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// Normalizes an alias and checks that it is a path inside the site.
+///
+/// # Arguments
+///
+/// * `alias` - The alias as written in the front matter.
+///
+/// # Returns
+///
+/// The normalized alias, or an error if the alias is the root of the
+/// site, has an empty, `.`, `..` or absolute segment, or contains
+/// whitespace or a `"`, which the redirect manifests cannot quote.
+pub fn check_alias(alias: &str) -> Result<String, String> {
+    let normalized = normalize_alias(alias);
+    let invalid = |reason: &str| {
+        Err(format!("Invalid alias `{}`: {}", alias.trim(), reason))
+    };
+
+    if normalized.is_empty() {
+        return invalid("it is the root of the site");
+    }
+    if normalized
+        .chars()
+        .any(|c| c.is_whitespace() || c == '"' || c == '\\')
+    {
+        return invalid(
+            "it contains whitespace, a quote or a backslash",
+        );
+    }
+    if normalized.split('/').any(|segment| {
+        segment.is_empty() || segment == "." || segment == ".."
+    }) || !Path::new(&normalized)
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return invalid(
+            "it has an empty, `.`, `..` or absolute segment",
+        );
+    }
+
+    Ok(normalized)
+}
+
+/// Returns the URL of a normalized alias.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::redirects::alias_url;
+///
+/// assert_eq!(alias_url("old/post"), "/old/post/");
+/// assert_eq!(alias_url("old.html"), "/old.html");
+/// ```
+pub fn alias_url(alias: &str) -> String {
+    if alias.ends_with(".html") {
+        format!("/{}", alias)
+    } else {
+        format!("/{}/", alias)
+    }
+}
+
+/// Returns the file of the redirect page of a normalized alias,
+/// relative to the build directory.
+pub fn alias_file(alias: &str) -> PathBuf {
+    if alias.ends_with(".html") {
+        PathBuf::from(alias)
+    } else {
+        Path::new(alias).join("index.html")
+    }
+}
+
+/// Collects the redirects declared by the `aliases` front matter of the
+/// pages.
+///
+/// # Arguments
+///
+/// * `files` - The content files.
+/// * `routes` - The output paths of the pages.
+///
+/// # Returns
+///
+/// A map of normalized aliases to the URL of their page, or an error if
+/// an alias is invalid (see `check_alias`), is claimed by two pages or
+/// would overwrite a page or a generated file, such as a feed or a page
+/// of a list (see `is_generated_path`).
+pub fn collect_redirects(
+    files: &[FileData],
+    routes: &Routes,
+) -> Result<BTreeMap<String, String>, String> {
+    let page_paths: HashSet<String> =
+        files.iter().map(|file| routes.path(&file.name)).collect();
+    let mut redirects = BTreeMap::new();
+    let mut owners: BTreeMap<String, &str> = BTreeMap::new();
+
+    for file in files {
+        let metadata = extract(&file.content);
+        let Some(aliases) = metadata.get("aliases") else {
+            continue;
+        };
+
+        for alias in split_list(aliases) {
+            let alias = check_alias(&alias).map_err(|error| {
+                format!("{} in `{}`", error, file.name)
+            })?;
+            if is_generated_path(&alias, &page_paths) {
+                return Err(format!(
+                    "Alias `{}` of `{}` would overwrite a generated page",
+                    alias_url(&alias),
+                    file.name
+                ));
+            }
+            if let Some(owner) =
+                owners.insert(alias.clone(), &file.name)
+            {
+                if owner != file.name {
+                    return Err(format!(
+                        "Alias `{}` is declared by both `{}` and `{}`",
+                        alias_url(&alias),
+                        owner,
+                        file.name
+                    ));
+                }
+            }
+            let _ = redirects.insert(alias, routes.url(&file.name));
+        }
+    }
+
+    Ok(redirects)
+}
+
+/// Generates a page that redirects to another URL.
+///
+/// The page uses a meta refresh and declares the target as its
+/// canonical URL, so that search engines index the target.
+///
+/// # Arguments
+///
+/// * `url` - The URL to redirect to.
+///
+/// # Returns
+///
+/// A `String` containing the HTML of the redirect page.
+pub fn generate_redirect_html(url: &str) -> String {
+    format!(
+        "<!DOCTYPE html><html><head><title>{url}</title><link rel=\"canonical\" href=\"{url}\"><meta name=\"robots\" content=\"noindex\"><meta http-equiv=\"refresh\" content=\"0; url={url}\"></head><body><a href=\"{url}\">{url}</a></body></html>",
+        url = url
+    )
+}
+
+/// Generates a `_redirects` manifest, as read by Netlify and Cloudflare
+/// Pages.
+///
+/// # Arguments
+///
+/// * `redirects` - A map of normalized aliases to their target URL.
+///
+/// # Returns
+///
+/// A `String` containing one `from to 301` rule per line.
+pub fn generate_netlify_redirects(
+    redirects: &BTreeMap<String, String>,
+) -> String {
+    redirects
+        .iter()
+        .map(|(alias, url)| {
+            format!("{} {} 301\n", alias_url(alias), url)
+        })
+        .collect()
+}
+
+/// Generates an `.htaccess` file with Apache `RedirectMatch` rules.
+///
+/// # Arguments
+///
+/// * `redirects` - A map of normalized aliases to their target URL.
+///
+/// # Returns
+///
+/// A `String` containing one rule per alias. Directory aliases match
+/// with or without a trailing slash or `index.html`.
+pub fn generate_htaccess(
+    redirects: &BTreeMap<String, String>,
+) -> String {
+    redirects
+        .iter()
+        .map(|(alias, url)| {
+            let pattern = regex::escape(alias);
+            if alias.ends_with(".html") {
+                format!("RedirectMatch 301 ^/{}$ {}\n", pattern, url)
+            } else {
+                format!(
+                    "RedirectMatch 301 ^/{}(/|/index\\.html)?$ {}\n",
+                    pattern, url
+                )
+            }
+        })
+        .collect()
+}
+
+/// Generates an nginx `map` of old URIs to new ones.
+///
+/// The map sets `$redirect_uri`, to be used with a rule such as
+/// `if ($redirect_uri) { return 301 $redirect_uri; }`.
+///
+/// # Arguments
+///
+/// * `redirects` - A map of normalized aliases to their target URL.
+///
+/// # Returns
+///
+/// A `String` containing the `map` block, with quoted URIs.
+pub fn generate_nginx_map(
+    redirects: &BTreeMap<String, String>,
+) -> String {
+    let mut map = String::from("map $uri $redirect_uri {\n");
+    for (alias, url) in redirects {
+        if alias.ends_with(".html") {
+            map.push_str(&format!("    \"/{}\" \"{}\";\n", alias, url));
+        } else {
+            for suffix in ["", "/", "/index.html"] {
+                map.push_str(&format!(
+                    "    \"/{}{}\" \"{}\";\n",
+                    alias, suffix, url
+                ));
+            }
+        }
+    }
+    map.push_str("}\n");
+    map
+}
+
+/// Writes the redirect pages and manifests to the build directory.
+///
+/// Nothing is written when there are no redirects. The manifests are
+/// read by the web server of the domain, so their aliases and targets
+/// are prefixed with the path of the site, except for the manifest of
+/// the development server (see `SERVE_REDIRECTS_FILE`).
+///
+/// # Arguments
+///
+/// * `build_dir_path` - The path to the build directory.
+/// * `redirects` - A map of normalized aliases to their target URL.
+/// * `prefix` - The path of the base URL, as returned by `path_prefix`.
+///
+/// # Returns
+///
+/// Returns an `io::Result<()>` which is `Ok` if all files were written.
+pub fn write_redirects(
+    build_dir_path: &Path,
+    redirects: &BTreeMap<String, String>,
+    prefix: &str,
+) -> io::Result<()> {
+    if redirects.is_empty() {
+        return Ok(());
+    }
+
+    for (alias, url) in redirects {
+        let file_path = build_dir_path.join(alias_file(alias));
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(file_path, generate_redirect_html(url))?;
+    }
+    fs::write(
+        build_dir_path.join(SERVE_REDIRECTS_FILE),
+        generate_netlify_redirects(redirects),
+    )?;
+
+    let redirects: BTreeMap<String, String> = redirects
+        .iter()
+        .map(|(alias, url)| {
+            (
+                normalize_alias(&format!("{}/{}", prefix, alias)),
+                prefix_url(prefix, url),
+            )
+        })
+        .collect();
+    fs::write(
+        build_dir_path.join(NETLIFY_REDIRECTS_FILE),
+        generate_netlify_redirects(&redirects),
+    )?;
+    fs::write(
+        build_dir_path.join(HTACCESS_FILE),
+        generate_htaccess(&redirects),
+    )?;
+    fs::write(
+        build_dir_path.join(NGINX_MAP_FILE),
+        generate_nginx_map(&redirects),
+    )?;
+
+    Ok(())
+}
+
+/// Looks up the target of a request path in a redirects manifest, in
+/// the `_redirects` format.
+///
+/// # Arguments
+///
+/// * `manifest` - The content of the manifest.
+/// * `request_path` - The path of the request (e.g. `/old/post/`).
+///
+/// # Returns
+///
+/// The URL to redirect to, if the path matches a rule.
+pub fn find_redirect(
+    manifest: &str,
+    request_path: &str,
+) -> Option<String> {
+    let request = normalize_alias(
+        request_path.split(['?', '#']).next().unwrap_or_default(),
+    );
+
+    manifest.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        match (parts.next(), parts.next()) {
+            (Some(from), Some(to))
+                if !from.starts_with('#')
+                    && normalize_alias(from) == request =>
+            {
+                Some(to.to_string())
+            }
+            _ => None,
+        }
+    })
+}
@@ -0,0 +1,374 @@
+// Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::models::data::FileData;
+use crate::modules::formats::{is_content_page, is_html_page};
+use crate::modules::frontmatter::extract;
+use crate::modules::markdown::{split_code_blocks, split_code_spans};
+use crate::modules::navigation::NavigationGenerator;
+use crate::modules::permalinks::Routes;
+use crate::utilities::directory::extract_front_matter;
+use regex::{Captures, Regex};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::{Component, Path, PathBuf},
+};
+
+/// The front matter field holding the ID of a page.
+pub const ID_FIELD: &str = "id";
+
+/// The placeholder of the tags page, which is not a reference.
+pub const CONTENT_PLACEHOLDER: &str = "content";
+
+lazy_static::lazy_static! {
+    /// Matches a `[[id#anchor|label]]` reference.
+    static ref WIKI_LINK: Regex =
+        Regex::new(r"\[\[([^\]|#\n]+)(#[^\]|\n]*)?(?:\|([^\]\n]+))?\]\]")
+            .unwrap();
+    /// Matches the target of an inline link or a link definition.
+    static ref MARKDOWN_LINK: Regex = Regex::new(
+        r#"(?m)(\]\(\s*<?|^[ \t]{0,3}\[[^\]\n]+\]:[ \t]*<?)([^)\s>]+)"#
+    )
+    .unwrap();
+}
+
+/// Returns the IDs a page can be referenced by.
+///
+/// A page is referenced by its `id` front matter, its content file name
+/// without extension, its output path and the last segment of its
+/// output path. When several pages share an ID, the `id` front matter
+/// wins over the paths, which win over the last segments.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::references::page_ids;
+/// use std::collections::HashMap;
+///
+/// let metadata = HashMap::new();
+/// assert_eq!(
+///     page_ids("guides/install.md", &metadata),
+///     vec!["guides/install", "install"]
+/// );
+/// ```
+pub fn page_ids(
+    name: &str,
+    metadata: &HashMap<String, String>,
+) -> Vec<String> {
+    let path = NavigationGenerator::page_path(name);
+    let candidates = [
+        metadata.get(ID_FIELD).map(|id| id.trim().to_string()),
+        Some(
+            Path::new(name)
+                .with_extension("")
+                .to_string_lossy()
+                .replace('\\', "/"),
+        ),
+        Some(path.clone()),
+        path.rsplit('/').next().map(str::to_string),
+    ];
+
+    let mut ids: Vec<String> = Vec::new();
+    for id in candidates.into_iter().flatten() {
+        if !id.is_empty() && !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    ids
+}
+
+/// Returns the content file name a relative link points to.
+///
+/// The link is relative to the directory of the linking page, or to the
+/// content directory when it starts with `/`.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::references::link_target;
+///
+/// assert_eq!(
+///     link_target("guides/advanced/tuning.md", "../install.md"),
+///     Some("guides/install.md".to_string())
+/// );
+/// assert_eq!(
+///     link_target("guides/install.md", "/about.md"),
+///     Some("about.md".to_string())
+/// );
+/// assert_eq!(link_target("about.md", "../../secret.md"), None);
+/// ```
+pub fn link_target(from: &str, link: &str) -> Option<String> {
+    let base = match link.strip_prefix('/') {
+        Some(_) => PathBuf::new(),
+        None => Path::new(from).parent()?.to_path_buf(),
+    };
+
+    let mut target = PathBuf::new();
+    for component in
+        base.join(link.trim_start_matches('/')).components()
+    {
+        match component {
+            Component::Normal(part) => target.push(part),
+            Component::ParentDir => {
+                if !target.pop() {
+                    return None;
+                }
+            }
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    Some(target.to_string_lossy().replace('\\', "/"))
+}
+
+/// Resolves the cross-references between the pages to their URLs.
+///
+/// In the Markdown body of every page:
+///
+/// * `[[id]]`, `[[id|label]]` and `[[id#anchor]]` become links to the
+///   page with that ID (see `page_ids`), labelled with its title unless
+///   a label is given.
+/// * Links to content files, such as `[see install](../guides/install.md)`
+///   or `[install]: /guides/install.md`, point to the URL of their page.
+///
+/// Code blocks and inline code are left as is, and so are the bodies of
+/// HTML pages and the `[[content]]` placeholder of the tags page.
+///
+/// # Arguments
+///
+/// * `files` - The pages of the site.
+/// * `routes` - The output paths of the pages.
+///
+/// # Returns
+///
+/// The pages with their references resolved, or an error naming the
+/// first reference to a missing page, or to an ambiguous ID.
+pub fn resolve_references(
+    files: Vec<FileData>,
+    routes: &Routes,
+) -> Result<Vec<FileData>, String> {
+    let titles: HashMap<&str, String> = files
+        .iter()
+        .map(|file| {
+            let title = extract(&file.content)
+                .get("title")
+                .cloned()
+                .unwrap_or_default();
+            (file.name.as_str(), title)
+        })
+        .collect();
+    // An ID matches the pages that set it first, then the pages at this
+    // path, then the pages whose last path segment it is
+    let mut ids: HashMap<String, BTreeMap<u8, BTreeSet<&str>>> =
+        HashMap::new();
+    for file in &files {
+        let metadata = extract(&file.content);
+        let path = NavigationGenerator::page_path(&file.name);
+        for id in page_ids(&file.name, &metadata) {
+            let rank = if metadata.get(ID_FIELD).map(|id| id.trim())
+                == Some(id.as_str())
+            {
+                0
+            } else if id.contains('/') || id == path {
+                1
+            } else {
+                2
+            };
+            let _ = ids
+                .entry(id)
+                .or_default()
+                .entry(rank)
+                .or_default()
+                .insert(file.name.as_str());
+        }
+    }
+
+    let resolved: Vec<(String, String)> = files
+        .iter()
+        .filter(|file| !is_html_page(&file.name))
+        .map(|file| {
+            let body = extract_front_matter(&file.content);
+            let front_matter =
+                &file.content[..file.content.len() - body.len()];
+            let body = rewrite_references(body, |reference| {
+                find_reference(&file.name, reference, &ids, &titles)
+                    .map(|name| {
+                        (routes.url(name), titles[name].clone())
+                    })
+            })?;
+            Ok((file.name.clone(), format!("{}{}", front_matter, body)))
+        })
+        .collect::<Result<_, String>>()?;
+
+    let mut resolved: HashMap<String, String> =
+        resolved.into_iter().collect();
+    Ok(files
+        .into_iter()
+        .map(|mut file| {
+            if let Some(content) = resolved.remove(&file.name) {
+                file.content = content;
+            }
+            file
+        })
+        .collect())
+}
+
+/// Returns the content file name of the page a reference points to.
+fn find_reference<'f>(
+    from: &str,
+    reference: Reference<'_>,
+    ids: &HashMap<String, BTreeMap<u8, BTreeSet<&'f str>>>,
+    titles: &HashMap<&'f str, String>,
+) -> Result<&'f str, String> {
+    match reference {
+        Reference::Id(id) => {
+            let mut names = ids
+                .get(id)
+                .and_then(|ranks| ranks.values().next())
+                .into_iter()
+                .flatten();
+            match (names.next(), names.next()) {
+                (Some(name), None) => Ok(name),
+                (Some(first), Some(second)) => Err(format!(
+                    "Ambiguous reference `[[{}]]` in {}: it matches {} and {}",
+                    id, from, first, second
+                )),
+                _ => Err(format!(
+                    "Broken reference `[[{}]]` in {}: no page has this ID",
+                    id, from
+                )),
+            }
+        }
+        Reference::Link(link) => link_target(from, link)
+            .and_then(|name| titles.get_key_value(name.as_str()))
+            .map(|(name, _)| *name)
+            .ok_or_else(|| {
+                format!(
+                    "Broken link to `{}` in {}: no such page",
+                    link, from
+                )
+            }),
+    }
+}
+
+/// A cross-reference of a page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reference<'a> {
+    /// The ID of a page, from a `[[id]]` reference.
+    Id(&'a str),
+    /// The relative path of a content file, from a Markdown link.
+    Link(&'a str),
+}
+
+/// Rewrites the cross-references of a Markdown body.
+///
+/// References in fenced and indented code blocks and in inline code
+/// are left as is.
+///
+/// # Arguments
+///
+/// * `body` - The Markdown body of the page.
+/// * `resolve` - Returns the URL and title of the page of a reference,
+///   or an error.
+///
+/// # Returns
+///
+/// The body with its references replaced by links, or the first error
+/// of `resolve`.
+pub fn rewrite_references(
+    body: &str,
+    mut resolve: impl FnMut(
+        Reference<'_>,
+    ) -> Result<(String, String), String>,
+) -> Result<String, String> {
+    let mut output = String::with_capacity(body.len());
+    for (block, code) in split_code_blocks(body) {
+        if code {
+            output.push_str(block);
+            continue;
+        }
+        let mut offset = 0;
+        for (part, code) in split_code_spans(block) {
+            let line_start =
+                offset == 0 || block.as_bytes()[offset - 1] == b'\n';
+            offset += part.len();
+            if code {
+                output.push_str(part);
+                continue;
+            }
+            let mut error = None;
+            let part =
+                WIKI_LINK.replace_all(part, |caps: &Captures<'_>| {
+                    let id = caps[1].trim();
+                    if id == CONTENT_PLACEHOLDER
+                        && caps.get(2).is_none()
+                    {
+                        return caps[0].to_string();
+                    }
+                    match resolve(Reference::Id(id)) {
+                        Ok((url, title)) => {
+                            let anchor =
+                                caps.get(2).map_or("", |m| m.as_str());
+                            let label = caps
+                                .get(3)
+                                .map(|m| m.as_str().trim().to_string())
+                                .filter(|label| !label.is_empty())
+                                .unwrap_or(if title.is_empty() {
+                                    id.to_string()
+                                } else {
+                                    title
+                                });
+                            format!(
+                                "[{}]({}{})",
+                                label
+                                    .replace('[', "\\[")
+                                    .replace(']', "\\]"),
+                                url,
+                                anchor
+                            )
+                        }
+                        Err(e) => {
+                            let _ = error.get_or_insert(e);
+                            caps[0].to_string()
+                        }
+                    }
+                });
+            let part = MARKDOWN_LINK.replace_all(
+                &part,
+                |caps: &Captures<'_>| {
+                    let target = &caps[2];
+                    let (path, anchor) = match target.find('#') {
+                        Some(index) => target.split_at(index),
+                        None => (target, ""),
+                    };
+                    // A part following a code span does not start a line
+                    let misplaced = !caps[1].starts_with(']')
+                        && !line_start
+                        && caps.get(0).is_some_and(|m| m.start() == 0);
+                    if misplaced
+                        || path.contains("://")
+                        || path.starts_with("mailto:")
+                        || !is_content_page(path)
+                        || is_html_page(path)
+                    {
+                        return caps[0].to_string();
+                    }
+                    match resolve(Reference::Link(path)) {
+                        Ok((url, _)) => {
+                            format!("{}{}{}", &caps[1], url, anchor)
+                        }
+                        Err(e) => {
+                            let _ = error.get_or_insert(e);
+                            caps[0].to_string()
+                        }
+                    }
+                },
+            );
+            if let Some(error) = error {
+                return Err(error);
+            }
+            output.push_str(&part);
+        }
+    }
+    Ok(output)
+}
@@ -0,0 +1,232 @@
+// Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::models::data::{RelatedConfig, RelatedDocument};
+use crate::modules::frontmatter::split_list;
+use crate::modules::keywords::extract_keywords;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+/// The common English words left out of text similarity.
+const STOP_WORDS: [&str; 40] = [
+    "the", "and", "for", "are", "but", "not", "you", "all", "any",
+    "can", "had", "her", "was", "one", "our", "out", "has", "his",
+    "how", "its", "may", "new", "now", "see", "who", "did", "get",
+    "use", "that", "with", "this", "from", "they", "will", "have",
+    "your", "what", "when", "into", "more",
+];
+
+/// Splits a text into lowercase words, leaving out short and common
+/// words.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::related::tokenize;
+///
+/// assert_eq!(tokenize("The Rust compiler, and Rust's borrow checker"),
+///     vec!["rust", "compiler", "rust", "borrow", "checker"]);
+/// ```
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|word| {
+            word.chars().count() > 2
+                && !STOP_WORDS.contains(&word.as_str())
+        })
+        .collect()
+}
+
+/// Returns the overlap of two sets of labels, between 0 and 1.
+///
+/// Labels are compared without regard to case. The overlap is the
+/// number of shared labels divided by the number of distinct labels.
+pub fn label_similarity(a: &[String], b: &[String]) -> f64 {
+    let a: HashSet<String> = normalize_labels(a);
+    let b: HashSet<String> = normalize_labels(b);
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+/// Computes the related pages of every page.
+///
+/// Text similarity is the cosine similarity of the TF-IDF weighted
+/// words of the pages.
+///
+/// # Arguments
+///
+/// * `documents` - What is compared of every page.
+/// * `config` - The related content settings.
+///
+/// # Returns
+///
+/// A map of content file names to the names of their related pages,
+/// from the most related. Pages that share nothing are left out.
+pub fn find_related(
+    documents: &[RelatedDocument],
+    config: &RelatedConfig,
+) -> HashMap<String, Vec<String>> {
+    let mut related = HashMap::new();
+    if config.count == 0 {
+        return related;
+    }
+
+    let vectors = tf_idf(documents);
+    for (index, document) in documents.iter().enumerate() {
+        let mut scores: Vec<(f64, &str)> = documents
+            .iter()
+            .enumerate()
+            .filter(|(other, _)| *other != index)
+            .map(|(other, candidate)| {
+                let score = f64::from(config.tags_weight)
+                    * label_similarity(&document.tags, &candidate.tags)
+                    + f64::from(config.keywords_weight)
+                        * label_similarity(
+                            &document.keywords,
+                            &candidate.keywords,
+                        )
+                    + f64::from(config.text_weight)
+                        * cosine(&vectors[index], &vectors[other]);
+                (score, candidate.name.as_str())
+            })
+            .filter(|(score, _)| *score > 0.0)
+            .collect();
+
+        scores.sort_by(|a, b| {
+            b.0.partial_cmp(&a.0)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.1.cmp(b.1))
+        });
+        let names = scores
+            .into_iter()
+            .take(config.count)
+            .map(|(_, name)| name.to_string())
+            .collect();
+        let _ = related.insert(document.name.clone(), names);
+    }
+
+    related
+}
+
+/// Creates the `RelatedDocument` of a page.
+///
+/// # Arguments
+///
+/// * `name` - The name of the content file.
+/// * `metadata` - The front matter of the page.
+/// * `text` - The plain text of the page.
+pub fn related_document(
+    name: &str,
+    metadata: &HashMap<String, String>,
+    text: &str,
+) -> RelatedDocument {
+    RelatedDocument {
+        name: name.to_string(),
+        tags: metadata
+            .get("tags")
+            .map(|tags| split_list(tags))
+            .unwrap_or_default(),
+        keywords: extract_keywords(metadata),
+        text: text.to_string(),
+    }
+}
+
+/// Returns the entries of the related pages of a page.
+///
+/// # Arguments
+///
+/// * `names` - The names of the related pages, as found by
+///   `find_related`.
+/// * `entries` - The entries of all pages, as created by `page_entry`.
+///
+/// # Returns
+///
+/// A JSON array of the entries of the related pages, in order.
+pub fn related_entries(names: &[String], entries: &[Value]) -> Value {
+    Value::Array(
+        names
+            .iter()
+            .filter_map(|name| {
+                entries
+                    .iter()
+                    .find(|entry| {
+                        entry["name"].as_str() == Some(name.as_str())
+                    })
+                    .cloned()
+            })
+            .collect(),
+    )
+}
+
+/// Lowercases and trims a list of labels, leaving out empty ones.
+fn normalize_labels(labels: &[String]) -> HashSet<String> {
+    labels
+        .iter()
+        .map(|label| label.trim().to_lowercase())
+        .filter(|label| !label.is_empty())
+        .collect()
+}
+
+/// Returns the TF-IDF weighted word vector of every document.
+fn tf_idf(documents: &[RelatedDocument]) -> Vec<HashMap<String, f64>> {
+    let counts: Vec<HashMap<String, f64>> = documents
+        .iter()
+        .map(|document| {
+            let mut counts = HashMap::new();
+            for word in tokenize(&document.text) {
+                *counts.entry(word).or_insert(0.0) += 1.0;
+            }
+            counts
+        })
+        .collect();
+
+    let mut document_frequency: HashMap<&str, f64> = HashMap::new();
+    for words in &counts {
+        for word in words.keys() {
+            *document_frequency.entry(word.as_str()).or_insert(0.0) +=
+                1.0;
+        }
+    }
+
+    let total = documents.len() as f64;
+    counts
+        .iter()
+        .map(|words| {
+            words
+                .iter()
+                .map(|(word, count)| {
+                    let idf = (total
+                        / document_frequency[word.as_str()])
+                    .ln();
+                    (word.clone(), count * idf)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the cosine similarity of two word vectors.
+fn cosine(a: &HashMap<String, f64>, b: &HashMap<String, f64>) -> f64 {
+    let dot: f64 = a
+        .iter()
+        .filter_map(|(word, weight)| {
+            b.get(word).map(|other| weight * other)
+        })
+        .sum();
+    let norm = |vector: &HashMap<String, f64>| {
+        vector
+            .values()
+            .map(|weight| weight * weight)
+            .sum::<f64>()
+            .sqrt()
+    };
+    let norms = norm(a) * norm(b);
+    if norms == 0.0 {
+        0.0
+    } else {
+        dot / norms
+    }
+}
@@ -61,6 +61,40 @@ pub fn generate_rss(
     Ok(rss_str)
 }
 
+/// Generates an RSS feed with several items.
+///
+/// The channel elements are written from `channel`, like `generate_rss`,
+/// followed by one `item` element for each of `items`.
+pub fn generate_rss_feed(
+    channel: &RssData,
+    items: &[RssData],
+) -> Result<String, Box<dyn Error>> {
+    let mut writer = Writer::new(Vec::new());
+
+    writer.write_event(Event::Decl(
+        BytesDecl::new("1.0", Some("utf-8"), None)
+    ))?;
+
+    let mut rss_start = BytesStart::new("rss");
+    rss_start.push_attribute(("version", "2.0"));
+    rss_start
+        .push_attribute(("xmlns:atom", "http://www.w3.org/2005/Atom"));
+    writer.write_event(Event::Start(rss_start))?;
+    writer.write_event(Event::Start(BytesStart::new("channel")))?;
+
+    write_elements(&mut writer, channel)?;
+    write_image_element(&mut writer, channel)?;
+    write_atom_link_element(&mut writer, channel)?;
+    for item in items {
+        write_item_element(&mut writer, item)?;
+    }
+
+    writer.write_event(Event::End(BytesEnd::new("channel")))?;
+    writer.write_event(Event::End(BytesEnd::new("rss")))?;
+
+    Ok(String::from_utf8(writer.into_inner())?)
+}
+
 /// Write the specified elements to the writer.
 pub fn write_elements<W: std::io::Write>(
     writer: &mut Writer<W>,
@@ -0,0 +1,213 @@
+// Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::models::data::FileData;
+use crate::modules::cascade::merge_front_matter;
+use crate::modules::dates::date_key;
+use crate::modules::frontmatter::extract;
+use crate::modules::navigation::NavigationGenerator;
+use crate::modules::series::field;
+use serde_json::{json, Value};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+
+/// The file stem of the list page of a section.
+pub const SECTION_INDEX: &str = "_index";
+
+/// The default layout of the section list pages, rendered with
+/// `page.html` by themes without a `section.html` template.
+pub const SECTION_LAYOUT: &str = "section";
+
+/// Returns whether a content file is the list page of its section.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::sections::is_section_page;
+///
+/// assert!(is_section_page("posts/_index.md"));
+/// assert!(!is_section_page("posts/index.md"));
+/// ```
+pub fn is_section_page(name: &str) -> bool {
+    Path::new(name)
+        .file_stem()
+        .is_some_and(|stem| stem == SECTION_INDEX)
+}
+
+/// Returns the file name, without extension, of the PDF document of a
+/// page.
+///
+/// The list page of a section is named after the directory of its
+/// section, as every section list page has the same file name. The list
+/// page of the content root is named `index`.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::sections::document_name;
+///
+/// assert_eq!(document_name("guides/_index.md"), "guides");
+/// assert_eq!(document_name("guides/install.md"), "install");
+/// ```
+pub fn document_name(name: &str) -> String {
+    let path = Path::new(name);
+    let stem = if is_section_page(name) {
+        path.parent()
+            .and_then(|parent| parent.file_name())
+            .unwrap_or("index".as_ref())
+    } else {
+        path.file_stem().unwrap_or(name.as_ref())
+    };
+    stem.to_string_lossy().to_string()
+}
+
+/// Gives the section list pages without a layout the `section` layout.
+///
+/// The layout is added to the front matter of the page itself, so it
+/// takes precedence over the front matter defaults of the directory.
+///
+/// # Arguments
+///
+/// * `files` - The content files.
+///
+/// # Returns
+///
+/// The content files, with the layout of the section list pages set.
+pub fn apply_section_layout(files: Vec<FileData>) -> Vec<FileData> {
+    let defaults = BTreeMap::from([(
+        "layout".to_string(),
+        SECTION_LAYOUT.to_string(),
+    )]);
+
+    files
+        .into_iter()
+        .map(|mut file| {
+            if is_section_page(&file.name)
+                && !extract(&file.content).contains_key("layout")
+            {
+                file.content =
+                    merge_front_matter(&file.content, &defaults);
+            }
+            file
+        })
+        .collect()
+}
+
+/// Compares two pages of a section for its list page.
+///
+/// Pages with a `weight` come first, lightest first, followed by the
+/// other pages, newest `date` first, and pages are then sorted by
+/// title.
+///
+/// # Arguments
+///
+/// * `a` - The entry of a page, as created by `page_entry`.
+/// * `b` - The entry of another page.
+///
+/// # Returns
+///
+/// The order of the two pages.
+pub fn compare_pages(a: &Value, b: &Value) -> Ordering {
+    let weight = |entry: &Value| {
+        field(entry, "weight").trim().parse::<f64>().ok()
+    };
+    let date = |entry: &Value| date_key(field(entry, "date"));
+
+    let by_weight = match (weight(a), weight(b)) {
+        (Some(a), Some(b)) => {
+            a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+        }
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    };
+    let by_date = match (date(a), date(b)) {
+        (Some(a), Some(b)) => b.cmp(&a),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    };
+    by_weight
+        .then(by_date)
+        .then_with(|| field(a, "title").cmp(field(b, "title")))
+}
+
+/// Generates the template data of the section list pages.
+///
+/// Every `_index` page gets its section as a JSON object with its
+/// `title`, `url`, `path`, the `count` of its pages, its `pages`, sorted
+/// by `compare_pages`, and its `subsections`, the directories below it
+/// with an `index` or `_index` page, each with its `title`, `url`,
+/// `path` and `count` of pages.
+///
+/// # Arguments
+///
+/// * `entries` - The entries of all pages, as created by `page_entry`.
+///
+/// # Returns
+///
+/// A map of the content file names of the section list pages to their
+/// data.
+pub fn section_data(entries: &[Value]) -> HashMap<String, Value> {
+    let pages_of = |path: &str| {
+        let mut pages: Vec<Value> = entries
+            .iter()
+            .filter(|entry| {
+                field(entry, "section") == path
+                    && !NavigationGenerator::is_index_page(field(
+                        entry, "name",
+                    ))
+            })
+            .cloned()
+            .collect();
+        pages.sort_by(compare_pages);
+        pages
+    };
+    let summary = |entry: &Value, path: &str, count: usize| {
+        json!({
+            "title": field(entry, "title"),
+            "url": field(entry, "url"),
+            "path": path,
+            "count": count,
+        })
+    };
+
+    entries
+        .iter()
+        .filter(|entry| is_section_page(field(entry, "name")))
+        .map(|entry| {
+            let name = field(entry, "name");
+            let path = NavigationGenerator::page_path(name);
+            let pages = pages_of(&path);
+
+            let mut subsections: Vec<&Value> = entries
+                .iter()
+                .filter(|child| {
+                    let child_name = field(child, "name");
+                    NavigationGenerator::is_index_page(child_name)
+                        && field(child, "section") == path
+                        && NavigationGenerator::page_path(child_name)
+                            != path
+                })
+                .collect();
+            subsections.sort_by(|a, b| compare_pages(a, b));
+
+            let mut value = summary(entry, &path, pages.len());
+            value["subsections"] = subsections
+                .into_iter()
+                .map(|child| {
+                    let child_path = NavigationGenerator::page_path(
+                        field(child, "name"),
+                    );
+                    let count = pages_of(&child_path).len();
+                    summary(child, &child_path, count)
+                })
+                .collect();
+            value["pages"] = Value::Array(pages);
+            (name.to_string(), value)
+        })
+        .collect()
+}
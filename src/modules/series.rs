@@ -0,0 +1,293 @@
+// Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::models::data::{FileData, Series, SiteConfig};
+use crate::modules::collections::page_entry;
+use crate::modules::dates::date_key;
+use crate::modules::frontmatter::extract;
+use crate::modules::i18n::{default_language, split_language};
+use crate::modules::permalinks::{slugify, Routes};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+
+/// The directory of the generated series overview pages.
+pub const SERIES_DIR: &str = "series";
+
+/// Groups the pages that have a `series` field into series.
+///
+/// Parts are ordered by their `series_order`, then by date, then by
+/// file name. Parts without a `series_order` come after the numbered
+/// ones. Series of the same name in different languages are kept apart.
+///
+/// # Arguments
+///
+/// * `entries` - The entries of all pages, as created by `page_entry`.
+/// * `config` - The site configuration.
+///
+/// # Returns
+///
+/// The series of the site, ordered by language and slug.
+pub fn collect_series(
+    entries: &[Value],
+    config: &SiteConfig,
+) -> Vec<Series> {
+    let mut groups: BTreeMap<(String, String), (String, Vec<&Value>)> =
+        BTreeMap::new();
+    for entry in entries {
+        let name = field(entry, "series").trim();
+        let slug = slugify(name);
+        if slug.is_empty() {
+            continue;
+        }
+        let (language, _) =
+            split_language(field(entry, "name"), config);
+        groups
+            .entry((language, slug))
+            .or_insert_with(|| (name.to_string(), Vec::new()))
+            .1
+            .push(entry);
+    }
+
+    groups
+        .into_iter()
+        .map(|((language, slug), (name, mut parts))| {
+            parts.sort_by_cached_key(|entry| {
+                let order = field(entry, "series_order")
+                    .trim()
+                    .parse::<i64>()
+                    .ok();
+                let date = date_key(field(entry, "date"));
+                (
+                    order.is_none(),
+                    order,
+                    date.is_none(),
+                    date,
+                    field(entry, "name").to_string(),
+                )
+            });
+            Series {
+                name,
+                slug,
+                language,
+                parts: parts
+                    .into_iter()
+                    .map(|entry| field(entry, "name").to_string())
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// Returns the content file name of the overview page of a series.
+///
+/// # Arguments
+///
+/// * `series` - The series.
+/// * `config` - The site configuration.
+///
+/// # Returns
+///
+/// `series/{slug}.md`, under the directory of the language of the
+/// series when it is not the default language (e.g. `fr/series/rust.md`).
+pub fn overview_name(series: &Series, config: &SiteConfig) -> String {
+    if series.language.is_empty()
+        || series.language == default_language(config)
+    {
+        format!("{}/{}.md", SERIES_DIR, series.slug)
+    } else {
+        format!("{}/{}/{}.md", series.language, SERIES_DIR, series.slug)
+    }
+}
+
+/// Generates the overview page of every series.
+///
+/// The overview page has the front matter of the first part. Its
+/// template lists the parts of the series in order, from its
+/// `paginator`. A content file at the name of the
+/// overview page replaces the generated one.
+///
+/// # Arguments
+///
+/// * `files` - The content files.
+/// * `routes` - The output paths of the pages.
+/// * `config` - The site configuration.
+///
+/// # Returns
+///
+/// The Markdown content files of the overview pages.
+pub fn generate_overview_files(
+    files: &[FileData],
+    routes: &Routes,
+    config: &SiteConfig,
+) -> Vec<FileData> {
+    let entries: Vec<Value> = files
+        .iter()
+        .map(|file| {
+            page_entry(
+                &file.name,
+                &extract(&file.content),
+                &routes.url(&file.name),
+            )
+        })
+        .collect();
+
+    collect_series(&entries, config)
+        .iter()
+        .map(|series| (overview_name(series, config), series))
+        .filter(|(name, _)| {
+            !files.iter().any(|file| &file.name == name)
+        })
+        .map(|(name, series)| {
+            let first_part = files
+                .iter()
+                .find(|file| series.parts.first() == Some(&file.name))
+                .map(|file| extract(&file.content))
+                .unwrap_or_default();
+            FileData {
+                name,
+                content: overview_markdown(series, first_part),
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+/// Generates the series navigation of every page of a series.
+///
+/// Every part gets its series as a JSON object with the `name`, `slug`,
+/// `url` of the overview page, the entries of all `parts`, its 1-based
+/// `index`, the `total` number of parts and the `previous` and `next`
+/// parts, or `null`. The overview page gets the same object with an
+/// `index` of 0.
+///
+/// # Arguments
+///
+/// * `series` - The series of the site, as returned by
+///   `collect_series`.
+/// * `entries` - The entries of all pages, as created by `page_entry`.
+/// * `config` - The site configuration.
+///
+/// # Returns
+///
+/// A map of content file names to their series navigation.
+pub fn series_data(
+    series: &[Series],
+    entries: &[Value],
+    config: &SiteConfig,
+) -> HashMap<String, Value> {
+    let find = |name: &str| {
+        entries
+            .iter()
+            .find(|entry| field(entry, "name") == name)
+            .cloned()
+    };
+
+    let mut data = HashMap::new();
+    for series in series {
+        let overview = overview_name(series, config);
+        let url = find(&overview)
+            .map(|entry| field(&entry, "url").to_string())
+            .unwrap_or_default();
+        let parts: Vec<Value> =
+            series.parts.iter().filter_map(|name| find(name)).collect();
+        let navigation = |index: usize| {
+            let part = |position: Option<usize>| {
+                position
+                    .and_then(|position| parts.get(position))
+                    .cloned()
+                    .unwrap_or(Value::Null)
+            };
+            json!({
+                "name": series.name,
+                "slug": series.slug,
+                "url": url,
+                "parts": parts,
+                "index": index,
+                "total": parts.len(),
+                "previous": part(index.checked_sub(2)),
+                "next": part((index > 0).then_some(index)),
+            })
+        };
+
+        let _ = data.insert(overview, navigation(0));
+        for (position, name) in series.parts.iter().enumerate() {
+            let _ = data.insert(name.clone(), navigation(position + 1));
+        }
+    }
+    data
+}
+
+/// The front matter fields of a page that are not inherited by the
+/// pages generated from it.
+pub(crate) const PAGE_FIELDS: [&str; 15] = [
+    "aliases",
+    "contributors",
+    "date",
+    "draft",
+    "identifier",
+    "menu",
+    "nav_title",
+    "parent",
+    "reading_time",
+    "series",
+    "series_order",
+    "slug",
+    "summary",
+    "tags",
+    "weight",
+];
+
+/// Returns the Markdown content of a generated page.
+///
+/// The front matter is inherited from an existing page, so the
+/// generated page renders with the same template fields, and `fields`
+/// are set over it.
+pub(crate) fn generated_page(
+    base: HashMap<String, String>,
+    fields: &[(&str, &str)],
+    body: &str,
+) -> String {
+    let mut front_matter: BTreeMap<String, String> = base
+        .into_iter()
+        .filter(|(key, _)| !PAGE_FIELDS.contains(&key.as_str()))
+        .collect();
+    for (key, value) in fields {
+        let _ = front_matter.insert(key.to_string(), value.to_string());
+    }
+    let front_matter: Vec<String> = front_matter
+        .iter()
+        .map(|(key, value)| {
+            format!("{}: {}", key, Value::String(value.clone()))
+        })
+        .collect();
+
+    format!("---\n{}\n---\n\n{}\n", front_matter.join("\n"), body)
+}
+
+/// Returns the Markdown content of the overview page of a series.
+fn overview_markdown(
+    series: &Series,
+    first_part: HashMap<String, String>,
+) -> String {
+    let description = format!(
+        "The {} parts of the {} series.",
+        series.parts.len(),
+        series.name
+    );
+
+    generated_page(
+        first_part,
+        &[
+            ("title", &series.name),
+            ("description", &description),
+            ("item_description", &description),
+            ("layout", "page"),
+        ],
+        "",
+    )
+}
+
+/// Returns a string field of a page entry, or an empty string.
+pub(crate) fn field<'a>(entry: &'a Value, key: &str) -> &'a str {
+    entry.get(key).and_then(Value::as_str).unwrap_or_default()
+}
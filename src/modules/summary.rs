@@ -0,0 +1,171 @@
+// Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::models::data::{ContentSummary, SummaryConfig};
+use crate::modules::markdown::split_code_blocks;
+use crate::modules::plaintext::generate_plain_text;
+use std::collections::HashMap;
+
+/// The marker ending the summary of a page.
+pub const MORE_MARKER: &str = "<!-- more -->";
+
+/// Counts the words of a plain text.
+///
+/// Ideographic characters, such as CJK, are counted as one word each,
+/// as these scripts do not separate words with spaces.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::summary::word_count;
+///
+/// assert_eq!(word_count("Hello, brave new world!"), 4);
+/// assert_eq!(word_count("静的サイト generator"), 6);
+/// ```
+pub fn word_count(text: &str) -> usize {
+    text.split_whitespace()
+        .map(|word| {
+            let ideographs =
+                word.chars().filter(|c| is_ideograph(*c)).count();
+            let rest = word.chars().any(|c| !is_ideograph(c));
+            ideographs + usize::from(rest)
+        })
+        .sum()
+}
+
+/// Estimates the reading time of a number of words, in minutes.
+///
+/// Any text takes at least one minute to read, while a page without
+/// words takes none.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::summary::reading_time;
+///
+/// assert_eq!(reading_time(0, 200), 0);
+/// assert_eq!(reading_time(150, 200), 1);
+/// assert_eq!(reading_time(401, 200), 3);
+/// ```
+pub fn reading_time(words: usize, words_per_minute: usize) -> usize {
+    if words == 0 {
+        0
+    } else {
+        let words_per_minute = words_per_minute.max(1);
+        (words + words_per_minute - 1) / words_per_minute
+    }
+}
+
+/// Summarizes the content of a page.
+///
+/// The summary is the text before the `<!-- more -->` marker, or else
+/// the first `config.words` words of the page. A marker written in a
+/// code block is part of the code.
+///
+/// # Arguments
+///
+/// * `content` - The Markdown content of the page, with its front matter.
+/// * `plain_text` - The plain text of the page, as returned by
+///   `generate_plain_text`.
+/// * `config` - The summary settings.
+///
+/// # Returns
+///
+/// The `ContentSummary` of the page.
+pub fn summarize(
+    content: &str,
+    plain_text: &str,
+    config: &SummaryConfig,
+) -> ContentSummary {
+    let words = word_count(plain_text);
+
+    let (summary, truncated) = match text_before_marker(content) {
+        Some(before) => {
+            let text = generate_plain_text(before, "", "", "", "", "")
+                .map(|(text, ..)| text)
+                .unwrap_or_default();
+            (collapse_whitespace(&text), true)
+        }
+        None => {
+            let text: Vec<&str> = plain_text
+                .split_whitespace()
+                .take(config.words)
+                .collect();
+            let truncated =
+                text.len() < plain_text.split_whitespace().count();
+            let mut summary = text.join(" ");
+            if truncated {
+                summary.push('…');
+            }
+            (summary, truncated)
+        }
+    };
+
+    ContentSummary {
+        word_count: words,
+        reading_time: reading_time(words, config.words_per_minute),
+        summary,
+        truncated,
+    }
+}
+
+/// Adds the summary and reading statistics to the front matter of a page.
+///
+/// Sets `summary`, `word_count` and `reading_time`, and uses the summary
+/// as the `description` and `item_description` when these are missing.
+/// Fields set in the front matter are kept.
+///
+/// # Arguments
+///
+/// * `metadata` - The front matter of the page.
+/// * `summary` - The summary of the page.
+pub fn apply_summary(
+    metadata: &mut HashMap<String, String>,
+    summary: &ContentSummary,
+) {
+    let mut set_default = |key: &str, value: String| {
+        let entry = metadata.entry(key.to_string()).or_default();
+        if entry.trim().is_empty() {
+            *entry = value;
+        }
+    };
+
+    set_default("word_count", summary.word_count.to_string());
+    set_default("reading_time", summary.reading_time.to_string());
+    if !summary.summary.is_empty() {
+        set_default("summary", summary.summary.clone());
+        set_default("description", summary.summary.clone());
+        set_default("item_description", summary.summary.clone());
+    }
+}
+
+/// Returns the text of a page before its first `<!-- more -->` marker
+/// outside code blocks.
+fn text_before_marker(content: &str) -> Option<&str> {
+    let mut offset = 0;
+    for (part, code) in split_code_blocks(content) {
+        if !code {
+            if let Some(index) = part.find(MORE_MARKER) {
+                return Some(&content[..offset + index]);
+            }
+        }
+        offset += part.len();
+    }
+    None
+}
+
+/// Returns whether a character is written without spaces between words.
+fn is_ideograph(c: char) -> bool {
+    matches!(
+        c,
+        '\u{3040}'..='\u{30ff}'
+            | '\u{3400}'..='\u{4dbf}'
+            | '\u{4e00}'..='\u{9fff}'
+            | '\u{f900}'..='\u{faff}'
+    )
+}
+
+/// Replaces each run of whitespace with a single space.
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
@@ -2,6 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use crate::models::data::{FileData, PageData, TagsData};
+use crate::modules::pagination::{
+    generate_pagination_links, generate_pagination_nav, paginate,
+    write_paginated_pages,
+};
 use crate::utilities::directory::to_title_case;
 use std::{
     collections::HashMap,
@@ -291,3 +295,59 @@ pub fn write_tags_html_to_file(
 
     Ok(())
 }
+
+/// Writes the tags page, split into pages of `per_page` tags.
+///
+/// Like `write_tags_html_to_file`, this function reads the `tags/index.html`
+/// file of the output directory and replaces its `[[content]]` placeholder.
+/// Each page gets the listing of its tags followed by the pagination
+/// navigation, and `rel="prev"`/`rel="next"` links in its `<head>`. The
+/// first page replaces `tags/index.html`, the following ones are written to
+/// `tags/page/<number>/index.html`.
+///
+/// # Arguments
+///
+/// * `global_tags_data` - A reference to a `HashMap` mapping each tag to the pages that use it.
+/// * `output_path` - The path to the output directory where the `tags/index.html` file is located.
+/// * `per_page` - The maximum number of tags on a page, or `0` to keep all tags on one page.
+///
+/// # Returns
+///
+/// Returns an `io::Result<()>` which is `Ok` if the operation was successful.
+///
+pub fn write_paginated_tags_html(
+    global_tags_data: &HashMap<String, Vec<PageData>>,
+    output_path: &Path,
+    per_page: usize,
+) -> io::Result<()> {
+    let tags_dir = output_path.join("tags");
+    let base_html = fs::read_to_string(tags_dir.join("index.html"))?;
+
+    let mut keys: Vec<&String> = global_tags_data.keys().collect();
+    keys.sort();
+
+    let pages: Vec<String> = paginate(&keys, per_page, "/tags/")
+        .into_iter()
+        .map(|(paginator, page_keys)| {
+            let page_tags: HashMap<String, Vec<PageData>> = page_keys
+                .into_iter()
+                .map(|key| (key.clone(), global_tags_data[key].clone()))
+                .collect();
+            let content = format!(
+                "{}{}",
+                generate_tags_html(&page_tags),
+                generate_pagination_nav(&paginator)
+            );
+            let links = generate_pagination_links(&paginator);
+
+            let html = base_html.replace("[[content]]", &content);
+            if links.is_empty() {
+                html
+            } else {
+                html.replacen("</head>", &format!("{}</head>", links), 1)
+            }
+        })
+        .collect();
+
+    write_paginated_pages(&tags_dir, "/tags/", &pages)
+}
@@ -0,0 +1,283 @@
+// Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::models::data::SiteConfig;
+use regex::{Captures, Regex};
+use std::{fs, io, path::Path};
+
+lazy_static::lazy_static! {
+    /// Matches the root-relative URLs of link attributes, meta refresh
+    /// URLs and CSS `url()` values.
+    static ref LINK_RE: Regex = Regex::new(
+        r#"(?i)(\s(?:href|src|action|poster)=["']?|;\s*url=|url\(\s*["']?)(/[^"'\s>)]*)"#,
+    )
+    .unwrap();
+    /// Matches the value of a `srcset` attribute.
+    static ref SRCSET_RE: Regex =
+        Regex::new(r#"(?i)(\ssrcset=)("[^"]*"|'[^']*')"#).unwrap();
+}
+
+/// Returns the path prefix of a base URL.
+///
+/// # Arguments
+///
+/// * `base_url` - The absolute URL the site is deployed at.
+///
+/// # Returns
+///
+/// The path of the base URL, without a trailing slash, or an empty
+/// string when the site lives at the domain root.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::urls::path_prefix;
+///
+/// assert_eq!(path_prefix("https://example.com/product/docs/"), "/product/docs");
+/// assert_eq!(path_prefix("https://example.com"), "");
+/// assert_eq!(path_prefix("/docs"), "/docs");
+/// ```
+pub fn path_prefix(base_url: &str) -> String {
+    let path = match base_url.split_once("://") {
+        Some((_, rest)) => {
+            rest.find('/').map(|index| &rest[index..]).unwrap_or("")
+        }
+        None => base_url,
+    };
+    let path = path.trim_matches('/');
+    if path.is_empty() {
+        String::new()
+    } else {
+        format!("/{}", path)
+    }
+}
+
+/// Returns the absolute URL of a root-relative URL.
+///
+/// # Arguments
+///
+/// * `base_url` - The absolute URL the site is deployed at.
+/// * `url` - A URL relative to the site root (e.g. `/about/index.html`).
+///
+/// # Returns
+///
+/// The URL joined to the base URL.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::urls::absolute_url;
+///
+/// assert_eq!(
+///     absolute_url("https://example.com/docs/", "/rss.xml"),
+///     "https://example.com/docs/rss.xml"
+/// );
+/// ```
+pub fn absolute_url(base_url: &str, url: &str) -> String {
+    format!(
+        "{}/{}",
+        base_url.trim_end_matches('/'),
+        url.trim_start_matches('/')
+    )
+}
+
+/// Adds a path prefix to a root-relative URL.
+///
+/// # Arguments
+///
+/// * `prefix` - The path prefix, as returned by `path_prefix`.
+/// * `url` - A URL relative to the site root.
+///
+/// # Returns
+///
+/// The prefixed URL.
+pub fn prefix_url(prefix: &str, url: &str) -> String {
+    if prefix.is_empty() {
+        url.to_string()
+    } else {
+        format!("{}{}", prefix, url)
+    }
+}
+
+/// Converts a root-relative URL into a URL relative to a page.
+///
+/// Directory URLs point to their `index.html`, as a file share does not
+/// resolve directory indexes.
+///
+/// # Arguments
+///
+/// * `page_dir` - The directory of the page, relative to the site root
+///   (e.g. `guides/install`, or an empty string for the home page).
+/// * `url` - A URL relative to the site root.
+///
+/// # Returns
+///
+/// The relative URL.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::modules::urls::relative_url;
+///
+/// assert_eq!(
+///     relative_url("guides/install", "/about/index.html"),
+///     "../../about/index.html"
+/// );
+/// assert_eq!(relative_url("", "/"), "index.html");
+/// ```
+pub fn relative_url(page_dir: &str, url: &str) -> String {
+    let split = url.find(['?', '#']).unwrap_or(url.len());
+    let (path, suffix) = url.split_at(split);
+
+    let mut target = path.trim_start_matches('/').to_string();
+    if target.is_empty() || target.ends_with('/') {
+        target.push_str("index.html");
+    }
+
+    let depth = page_dir
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .count();
+    format!("{}{}{}", "../".repeat(depth), target, suffix)
+}
+
+/// Returns the URL of the site root, as used for the web app manifest
+/// `start_url` and `scope`.
+///
+/// # Arguments
+///
+/// * `config` - The site configuration.
+///
+/// # Returns
+///
+/// `./` for relative links, otherwise the path prefix followed by `/`.
+pub fn site_root(config: &SiteConfig) -> String {
+    if config.relative_links {
+        "./".to_string()
+    } else {
+        format!("{}/", path_prefix(&config.base_url))
+    }
+}
+
+/// Rewrites the root-relative links of an HTML document.
+///
+/// The `href`, `src`, `action`, `poster` and `srcset` attributes, meta
+/// refresh URLs and CSS `url()` values that start with a single `/` are
+/// prefixed with the path of the base URL, or made relative to the page
+/// when `relative_links` is set. Absolute and protocol-relative URLs are
+/// left unchanged.
+///
+/// # Arguments
+///
+/// * `html` - The HTML document.
+/// * `page_dir` - The directory of the page, relative to the site root.
+/// * `config` - The site configuration.
+///
+/// # Returns
+///
+/// The HTML document with its links rewritten.
+pub fn rewrite_links(
+    html: &str,
+    page_dir: &str,
+    config: &SiteConfig,
+) -> String {
+    let prefix = path_prefix(&config.base_url);
+    if prefix.is_empty() && !config.relative_links {
+        return html.to_string();
+    }
+
+    let rewrite = |url: &str| {
+        if url.starts_with("//") {
+            url.to_string()
+        } else if config.relative_links {
+            relative_url(page_dir, url)
+        } else {
+            prefix_url(&prefix, url)
+        }
+    };
+
+    let html = LINK_RE.replace_all(html, |caps: &Captures<'_>| {
+        format!("{}{}", &caps[1], rewrite(&caps[2]))
+    });
+    SRCSET_RE
+        .replace_all(&html, |caps: &Captures<'_>| {
+            let value = &caps[2];
+            let (quote, candidates) =
+                (&value[..1], &value[1..value.len() - 1]);
+            let candidates: Vec<String> = candidates
+                .split(',')
+                .map(|candidate| {
+                    let candidate = candidate.trim();
+                    match candidate.split_once(char::is_whitespace) {
+                        Some((url, size)) if url.starts_with('/') => {
+                            format!("{} {}", rewrite(url), size.trim())
+                        }
+                        None if candidate.starts_with('/') => {
+                            rewrite(candidate)
+                        }
+                        _ => candidate.to_string(),
+                    }
+                })
+                .collect();
+            format!(
+                "{}{}{}{}",
+                &caps[1],
+                quote,
+                candidates.join(", "),
+                quote
+            )
+        })
+        .to_string()
+}
+
+/// Rewrites the root-relative links of every HTML and CSS file of a
+/// site.
+///
+/// # Arguments
+///
+/// * `site_dir` - The root directory of the generated site.
+/// * `config` - The site configuration.
+///
+/// # Returns
+///
+/// Returns an `io::Result<()>` which is `Ok` if all files were
+/// rewritten.
+pub fn rewrite_site_links(
+    site_dir: &Path,
+    config: &SiteConfig,
+) -> io::Result<()> {
+    if path_prefix(&config.base_url).is_empty()
+        && !config.relative_links
+    {
+        return Ok(());
+    }
+    rewrite_dir(site_dir, site_dir, config)
+}
+
+/// Rewrites the HTML files of a directory and its subdirectories.
+fn rewrite_dir(
+    site_dir: &Path,
+    dir: &Path,
+    config: &SiteConfig,
+) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            rewrite_dir(site_dir, &path, config)?;
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext == "html" || ext == "css")
+        {
+            let page_dir = path
+                .parent()
+                .and_then(|parent| parent.strip_prefix(site_dir).ok())
+                .map(|parent| {
+                    parent.to_string_lossy().replace('\\', "/")
+                })
+                .unwrap_or_default();
+            let text = fs::read_to_string(&path)?;
+            fs::write(&path, rewrite_links(&text, &page_dir, config))?;
+        }
+    }
+    Ok(())
+}
@@ -1,7 +1,7 @@
 // Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use clap::{Arg, ArgMatches, Command, Error};
+use clap::{Arg, ArgAction, ArgMatches, Command, Error};
 
 /// ## Function: build - returns a Result containing the parsed input options
 ///
@@ -16,6 +16,10 @@ use clap::{Arg, ArgMatches, Command, Error};
 /// - `--output` or `-o`: Specifies the location of the output directory.
 /// - `--template` or `-t`: Specifies the location of the template directory.
 /// - `--serve` or `-s`: Serves the public directory on a local web server.
+/// - `--drafts`: Includes draft pages in the build.
+/// - `--future`: Includes pages with a future publication date in the build.
+/// - `--front-matter`: Shows the effective front matter of a page and the
+///   source of every value, without building the site.
 ///
 /// If the CLI is successfully built and the command-line arguments are
 /// parsed correctly, the function returns an `Ok` result containing the
@@ -80,6 +84,24 @@ pub fn build() -> Result<ArgMatches, Error> {
                 .short('s')
                 .value_name("SERVE")
         )
+        .arg(
+            Arg::new("drafts")
+                .help("Include draft pages in the build.")
+                .long("drafts")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("future")
+                .help("Include pages with a future publication date in the build.")
+                .long("future")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("front-matter")
+                .help("Show the effective front matter of a page and where each value comes from.")
+                .long("front-matter")
+                .value_name("PAGE"),
+        )
         .after_help(
             "\x1b[1;4mDocumentation:\x1b[0m\n\n  https://shokunin.one\n\n\x1b[1;4mLicense:\x1b[0m\n  The project is licensed under the terms of both the MIT license and the Apache License (Version 2.0).",
         )
@@ -1,8 +1,11 @@
 // Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use crate::compiler::compile_with_options;
 use crate::macro_check_directory;
-use crate::{compile, macro_get_args};
+use crate::macro_get_args;
+use crate::models::data::BuildOptions;
+use crate::modules::cascade::explain_front_matter;
 use clap::ArgMatches;
 use std::path::Path;
 
@@ -12,31 +15,48 @@ use std::path::Path;
 /// command-line tool (ssg) and compiles the project.
 ///
 /// - This function parses the `content` directory where the markdown files for
-/// your website are stored and the `output` directory where the compiled site
-/// will be created from the `matches` object.
+///   your website are stored and the `output` directory where the compiled site
+///   will be created from the `matches` object.
 ///
 /// - It then, validates that these directories exist, or creates them on the
-/// fly if they do not. If either directory cannot be found or created, an
-/// error is returned.
+///   fly if they do not. If either directory cannot be found or created, an
+///   error is returned.
 ///
-/// - Finally, it calls the `compile` function to create the new project using
-/// the markdown files in the "content" directory, and returns an error if the
-/// compilation process fails.
+/// - When the `front-matter` argument names a page, it prints the effective
+///   front matter of that page and where each value comes from, and returns
+///   without building.
+///
+/// - Finally, it calls the `compile_with_options` function to create the new
+///   project using the markdown files in the "content" directory, including
+///   drafts and scheduled pages when the `drafts` and `future` flags are
+///   set, and returns an error if the compilation process fails.
 ///
 /// # Arguments
 ///
 /// * `matches` - A reference to an ArgMatches object containing the command-
-/// line arguments passed to the tool. This is created by the `clap` crate.
+///   line arguments passed to the tool. This is created by the `clap` crate.
 ///
 /// # Returns
 ///
 /// * A Result indicating success or failure.
 /// - Ok() if the project was created successfully and the output files were
-/// written to the output directory.
+///   written to the output directory.
 /// - Err(anyhow::Error) if the project could not be created or the output files
-/// could not be written to the output directory.
+///   could not be written to the output directory.
 ///
 pub fn args(matches: &ArgMatches) -> Result<(), String> {
+    // Show the effective front matter of a page instead of building
+    if let Ok(Some(page)) =
+        matches.try_get_one::<String>("front-matter")
+    {
+        let content_dir = macro_get_args!(matches, "content");
+        let explanation =
+            explain_front_matter(Path::new(&content_dir), page)
+                .map_err(|e| format!("❌ Error: {}", e))?;
+        println!("{}", explanation);
+        return Ok(());
+    }
+
     // Set the content elements of the new project
     let content_dir = macro_get_args!(matches, "content");
     let output_dir = macro_get_args!(matches, "output");
@@ -55,9 +75,28 @@ pub fn args(matches: &ArgMatches) -> Result<(), String> {
     macro_check_directory!(site_path, "new");
     macro_check_directory!(template_path, "template");
 
+    // Include drafts and scheduled pages when previewing
+    let flag = |name: &str| {
+        matches
+            .try_get_one::<bool>(name)
+            .ok()
+            .flatten()
+            .copied()
+            .unwrap_or(false)
+    };
+    let options = BuildOptions {
+        drafts: flag("drafts"),
+        future: flag("future"),
+    };
+
     // Create the new project
-    let compilation_result =
-        compile(build_path, content_path, site_path, template_path);
+    let compilation_result = compile_with_options(
+        build_path,
+        content_path,
+        site_path,
+        template_path,
+        &options,
+    );
     match compilation_result {
         Ok(_) => Ok(()),
         Err(e) => Err(format!("❌ Error: {}", e)),
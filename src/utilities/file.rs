@@ -8,6 +8,9 @@ use std::{fs, io, path::Path};
 /// Reads all files in a directory specified by the given path and returns a vector of FileData.
 ///
 /// Each file is represented as a `FileData` struct containing the name and content of the file.
+/// Sub-directories are read recursively; the name of a nested file is its path relative to
+/// `path`, using `/` as the separator (e.g. `guides/install.md`). Hidden files and directories
+/// (starting with `.`) are skipped.
 ///
 /// # Arguments
 ///
@@ -18,61 +21,133 @@ use std::{fs, io, path::Path};
 /// A `Result` containing a vector of `FileData` structs representing all files in the directory,
 /// or an `io::Error` if the directory cannot be read.
 pub fn add(path: &Path) -> io::Result<Vec<FileData>> {
-    let files =
-        fs::read_dir(path)?
-            .filter_map(|entry| {
-                let entry = entry.ok()?;
-                let path = entry.path();
-                if path.is_file() {
-                    let file_name =
-                        path.file_name()?.to_string_lossy().to_string();
-                    if file_name == ".DS_Store" {
-                        return None;
-                    }
-                    let content = fs::read_to_string(&path)
-                        .map_err(|e| {
-                            eprintln!(
-                                "Error reading file {:?}: {}",
-                                path, e
-                            );
-                            e
-                        })
-                        .ok()?;
-                    Some((file_name, content))
-                } else {
-                    None
-                }
-            })
-            .map(|(file_name, content)| {
-                let rss = escape(&content).to_string();
-                let json = serde_json::to_string(&content)
-                    .unwrap_or_else(|e| {
-                        eprintln!(
-                            "Error serializing JSON for file {}: {}",
-                            file_name, e
-                        );
-                        String::new()
-                    });
-                let cname = escape(&content).to_string();
-                let keyword = escape(&content).to_string();
-                let human = escape(&content).to_string();
-                let sitemap = escape(&content).to_string();
-                let txt = escape(&content).to_string();
+    add_matching(path, |_| true)
+}
 
-                FileData {
-                    cname,
-                    content,
-                    json,
-                    human,
-                    keyword,
-                    name: file_name,
-                    rss,
-                    sitemap,
-                    // tags,
-                    txt,
+/// Reads the files of a directory whose name matches a filter.
+///
+/// Like `add`, but the files whose name, relative to `path`, does not
+/// match `filter` are not read at all, so they may be binary files.
+///
+/// # Arguments
+///
+/// * `path` - The directory containing the files to be read.
+/// * `filter` - Returns whether a file, by name, is read.
+///
+/// # Returns
+///
+/// The matching files of the directory, or an `io::Error` if the
+/// directory cannot be read.
+pub fn add_matching(
+    path: &Path,
+    filter: impl Fn(&str) -> bool,
+) -> io::Result<Vec<FileData>> {
+    let mut entries = Vec::new();
+    visit_files(path, "", &mut |name, file_path| {
+        if filter(&name) {
+            match fs::read_to_string(file_path) {
+                Ok(content) => entries.push((name, content)),
+                Err(e) => {
+                    eprintln!(
+                        "Error reading file {:?}: {}",
+                        file_path, e
+                    )
                 }
-            })
-            .collect::<Vec<FileData>>();
+            }
+        }
+    })?;
+
+    let files = entries
+        .into_iter()
+        .map(|(file_name, content)| {
+            let rss = escape(&content).to_string();
+            let json =
+                serde_json::to_string(&content).unwrap_or_else(|e| {
+                    eprintln!(
+                        "Error serializing JSON for file {}: {}",
+                        file_name, e
+                    );
+                    String::new()
+                });
+            let cname = escape(&content).to_string();
+            let keyword = escape(&content).to_string();
+            let human = escape(&content).to_string();
+            let sitemap = escape(&content).to_string();
+            let txt = escape(&content).to_string();
+
+            FileData {
+                cname,
+                content,
+                json,
+                human,
+                keyword,
+                name: file_name,
+                rss,
+                sitemap,
+                // tags,
+                txt,
+            }
+        })
+        .collect::<Vec<FileData>>();
 
     Ok(files)
 }
+
+/// Lists the files of a directory, without reading them.
+///
+/// # Arguments
+///
+/// * `path` - The directory to list.
+///
+/// # Returns
+///
+/// The names of the files, relative to `path` and using `/` as the
+/// separator, with the hidden files and directories left out, or an
+/// `io::Error` if the directory cannot be read.
+pub fn list_files(path: &Path) -> io::Result<Vec<String>> {
+    let mut names = Vec::new();
+    visit_files(path, "", &mut |name, _| names.push(name))?;
+    Ok(names)
+}
+
+/// Recursively visits the files in `dir`, in name order.
+///
+/// # Arguments
+///
+/// * `dir` - The directory to read.
+/// * `prefix` - The relative path of `dir` from the content root, or an empty string.
+/// * `visit` - Called with the relative name and path of every file.
+fn visit_files(
+    dir: &Path,
+    prefix: &str,
+    visit: &mut dyn FnMut(String, &Path),
+) -> io::Result<()> {
+    let mut paths: Vec<_> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        let Some(file_name) =
+            path.file_name().map(|n| n.to_string_lossy().to_string())
+        else {
+            continue;
+        };
+        if file_name.starts_with('.') {
+            continue;
+        }
+        let name = if prefix.is_empty() {
+            file_name
+        } else {
+            format!("{}/{}", prefix, file_name)
+        };
+
+        if path.is_dir() {
+            visit_files(&path, &name, visit)?;
+        } else if path.is_file() {
+            visit(name, &path);
+        }
+    }
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 // Copyright © 2024 Shokunin Static Site Generator. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use crate::modules::redirects::{find_redirect, SERVE_REDIRECTS_FILE};
 use std::fs;
 use std::io::prelude::*;
 use std::net::{TcpListener, TcpStream};
@@ -46,6 +47,28 @@ pub fn start(
     println!("❯ Server is now running at http://{}", server_address);
     println!("  Done.\n");
 
+    serve(listener, document_root)
+}
+
+/// ## Function: `serve` - Serve the public directory on a listener.
+///
+/// This function handles the incoming connections of a listener that is
+/// already bound, such as one bound to port 0 to let the system choose
+/// a free port.
+///
+/// # Arguments
+///
+/// * `listener`      - A bound TcpListener.
+/// * `document_root` - A string for the document root.
+///
+/// # Returns
+///
+/// * A Result indicating success or failure.
+///
+pub fn serve(
+    listener: TcpListener,
+    document_root: &str,
+) -> std::io::Result<()> {
     for stream in listener.incoming() {
         match stream {
             Ok(stream) => {
@@ -67,6 +90,10 @@ pub fn start(
 /// This function takes a TcpStream object and a string for the document
 /// root, and handles a single connection.
 ///
+/// Requests matching a rule of the `.redirects` file in the document
+/// root, the redirects of the page aliases without the path of the
+/// site, are answered with a `301 Moved Permanently` response.
+///
 /// # Arguments
 ///
 /// * `stream`        - A TcpStream object.
@@ -129,6 +156,21 @@ pub fn handle_connection(
         }
     };
 
+    // Honor the redirects generated from the page aliases
+    let redirects_path =
+        Path::new(document_root).join(SERVE_REDIRECTS_FILE);
+    if let Ok(manifest) = fs::read_to_string(redirects_path) {
+        if let Some(location) = find_redirect(&manifest, path) {
+            let response = format!(
+                "HTTP/1.1 301 Moved Permanently\r\nLocation: {}\r\n\r\n",
+                location
+            );
+            stream.write_all(response.as_bytes())?;
+            stream.flush()?;
+            return Ok(());
+        }
+    }
+
     let requested_file = match path {
         "/" => "index.html",
         _ => &path[1..], // Remove the leading "/"
@@ -3,10 +3,13 @@
 
 use crate::macro_render_layout;
 use reqwest;
+use serde_json::{Map, Value};
 use std::{
+    borrow::Cow,
     collections::HashMap,
     fs::{self, File},
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
 #[derive(Debug, Default, PartialEq, Eq, Clone)]
@@ -19,10 +22,20 @@ use std::{
 /// # Arguments
 ///
 /// * `elements` - A `HashMap` containing the elements of the page.
+/// * `data` - A map of structured values (menus, collections, etc.)
+///   that templates can walk with `{{#each}}`, `{{#if}}` and dotted
+///   `{{a.b.c}}` tags.
+/// * `shared` - A map of structured values shared by every page of the
+///   site, such as the list of its pages, which are not copied for
+///   each page.
 ///
 pub struct PageOptions<'a> {
     /// Elements of the page
     pub elements: HashMap<&'a str, &'a str>,
+    /// Structured data of the page
+    pub data: Map<String, Value>,
+    /// Structured data shared between pages
+    pub shared: HashMap<String, Arc<Value>>,
 }
 
 impl<'a> PageOptions<'a> {
@@ -30,6 +43,8 @@ impl<'a> PageOptions<'a> {
     pub fn new() -> PageOptions<'a> {
         PageOptions {
             elements: HashMap::new(),
+            data: Map::new(),
+            shared: HashMap::new(),
         }
     }
     /// ## Function: `set` - Set a page option
@@ -41,6 +56,23 @@ impl<'a> PageOptions<'a> {
     pub fn get(&self, key: &'a str) -> Option<&&'a str> {
         self.elements.get(key)
     }
+
+    /// ## Function: `set_data` - Set a structured page value
+    pub fn set_data(&mut self, key: &str, value: Value) {
+        self.data.insert(key.to_string(), value);
+    }
+
+    /// ## Function: `set_shared_data` - Set a structured value shared between pages
+    pub fn set_shared_data(&mut self, key: &str, value: Arc<Value>) {
+        self.shared.insert(key.to_string(), value);
+    }
+
+    /// ## Function: `get_data` - Get a structured page value
+    pub fn get_data(&self, key: &str) -> Option<&Value> {
+        self.data
+            .get(key)
+            .or_else(|| self.shared.get(key).map(Arc::as_ref))
+    }
 }
 
 /// ## Function: `render_template` - Render a template with the given context
@@ -83,6 +115,322 @@ pub fn render_template(
     }
 }
 
+/// ## Function: `render_template_with_data` - Render a template with structured data
+///
+/// This function renders a template against the flat `{{key}}`
+/// placeholders of `context` and a map of structured values, in a
+/// single pass: the inserted values are never scanned for tags, so a
+/// value may contain `{{` without breaking the page.
+///
+/// The following tags are supported:
+///
+/// * `{{#each path}}...{{/each}}` - Repeats the body for every item of
+///   an array (or every value of an object). Inside the body the item
+///   is available as `{{this}}` / `{{this.field}}` and its zero-based
+///   position as `{{@index}}`.
+/// * `{{#if path}}...{{else}}...{{/if}}` - Renders the first branch
+///   when the value is truthy (not null, false, zero or empty).
+/// * `{{path.to.value}}` - Replaced by the value found at the dotted
+///   path. Arrays and objects are rendered as JSON.
+///
+/// A `{{key}}` tag is replaced by the value of `context` first, so a
+/// front matter field is not hidden by structured data of the same
+/// name, which stays available to `{{#each}}` and dotted tags. A tag
+/// that resolves against neither is reported as an error.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssg::utilities::template::render_template_with_data;
+/// use serde_json::json;
+/// use std::collections::HashMap;
+///
+/// let template = "<ul>{{#each menu}}<li>{{this.title}}</li>{{/each}}</ul>{{footer}}";
+/// let mut context = HashMap::new();
+/// context.insert("footer", "<p>Bye</p>");
+/// let data = json!({ "menu": [{ "title": "Home" }, { "title": "About" }] });
+///
+/// let html = render_template_with_data(template, &context, data.as_object().unwrap()).unwrap();
+/// assert_eq!(html, "<ul><li>Home</li><li>About</li></ul><p>Bye</p>");
+/// ```
+///
+pub fn render_template_with_data(
+    template: &str,
+    context: &HashMap<&str, &str>,
+    data: &Map<String, Value>,
+) -> Result<String, String> {
+    render_template_with_shared_data(
+        template,
+        context,
+        data,
+        &HashMap::new(),
+    )
+}
+
+/// ## Function: `render_template_with_shared_data` - Render a template with page and shared data
+///
+/// Like `render_template_with_data`, with the values of `shared`
+/// available to the template as well. A key of `data` hides the same
+/// key of `shared`. The shared values are read in place, so values
+/// common to every page of a site are not copied for each page.
+///
+pub fn render_template_with_shared_data(
+    template: &str,
+    context: &HashMap<&str, &str>,
+    data: &Map<String, Value>,
+    shared: &HashMap<String, Arc<Value>>,
+) -> Result<String, String> {
+    let root = Root {
+        context,
+        data,
+        shared,
+    };
+    render_blocks(template, &root, &Scope::default())
+}
+
+/// The top-level values of a template.
+struct Root<'v> {
+    context: &'v HashMap<&'v str, &'v str>,
+    data: &'v Map<String, Value>,
+    shared: &'v HashMap<String, Arc<Value>>,
+}
+
+impl<'v> Root<'v> {
+    /// Returns the top-level value of a key.
+    fn get(&self, key: &str) -> Option<&'v Value> {
+        self.data
+            .get(key)
+            .or_else(|| self.shared.get(key).map(Arc::as_ref))
+    }
+}
+
+/// The item and position of the innermost `{{#each}}` block.
+#[derive(Debug, Default, Clone, Copy)]
+struct Scope<'v> {
+    this: Option<&'v Value>,
+    index: Option<usize>,
+}
+
+/// Expands the block and dotted tags of `template`.
+fn render_blocks<'v>(
+    template: &str,
+    root: &Root<'v>,
+    scope: &Scope<'v>,
+) -> Result<String, String> {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            return Err(format!(
+                "Failed to render template, unclosed template tag: {}",
+                &rest[start..]
+            ));
+        };
+        let tag = after[..end].trim();
+        let tail = &after[end + 2..];
+
+        if let Some(path) = tag.strip_prefix("#each ") {
+            let (body, remainder) = split_block(tail, "each")?;
+            let items: Vec<&Value> =
+                match resolve_path(path.trim(), root, scope) {
+                    Some(Cow::Borrowed(Value::Array(items))) => {
+                        items.iter().collect()
+                    }
+                    Some(Cow::Borrowed(Value::Object(map))) => {
+                        map.values().collect()
+                    }
+                    _ => Vec::new(),
+                };
+            for (index, item) in items.into_iter().enumerate() {
+                let inner = Scope {
+                    this: Some(item),
+                    index: Some(index),
+                };
+                output.push_str(&render_blocks(body, root, &inner)?);
+            }
+            rest = remainder;
+        } else if let Some(path) = tag.strip_prefix("#if ") {
+            let (body, remainder) = split_block(tail, "if")?;
+            let (then_branch, else_branch) = split_else(body);
+            let path = path.trim();
+            let branch = if is_truthy(
+                resolve_path(path, root, scope).as_deref(),
+            ) || root
+                .context
+                .get(path)
+                .is_some_and(|value| !value.is_empty())
+            {
+                then_branch
+            } else {
+                else_branch
+            };
+            output.push_str(&render_blocks(branch, root, scope)?);
+            rest = remainder;
+        } else if let Some(value) = root.context.get(tag) {
+            output.push_str(value);
+            rest = tail;
+        } else if let Some(value) = resolve_path(tag, root, scope) {
+            output.push_str(&value_to_string(&value));
+            rest = tail;
+        } else {
+            return Err(format!(
+                "Failed to render template, unresolved template tags: {{{{{}}}}}",
+                tag
+            ));
+        }
+    }
+    output.push_str(rest);
+
+    Ok(output)
+}
+
+/// Splits `content` at the `{{/kind}}` tag closing the current block,
+/// returning the block body and whatever follows the closing tag.
+fn split_block<'t>(
+    content: &'t str,
+    kind: &str,
+) -> Result<(&'t str, &'t str), String> {
+    let open = format!("{{{{#{} ", kind);
+    let close = format!("{{{{/{}}}}}", kind);
+    let mut depth = 1;
+    let mut position = 0;
+
+    while let Some(offset) = content[position..].find("{{") {
+        let at = position + offset;
+        if content[at..].starts_with(&open) {
+            depth += 1;
+        } else if content[at..].starts_with(&close) {
+            depth -= 1;
+            if depth == 0 {
+                return Ok((
+                    &content[..at],
+                    &content[at + close.len()..],
+                ));
+            }
+        }
+        position = at + 2;
+    }
+
+    Err(format!(
+        "Failed to render template, unclosed {{{{#{}}}}} block",
+        kind
+    ))
+}
+
+/// Splits the body of an `{{#if}}` block at its top-level `{{else}}`.
+fn split_else(body: &str) -> (&str, &str) {
+    let mut depth = 0;
+    let mut position = 0;
+
+    while let Some(offset) = body[position..].find("{{") {
+        let at = position + offset;
+        let tag = &body[at..];
+        if tag.starts_with("{{#if ") {
+            depth += 1;
+        } else if tag.starts_with("{{/if}}") {
+            depth -= 1;
+        } else if depth == 0 && tag.starts_with("{{else}}") {
+            return (&body[..at], &body[at + "{{else}}".len()..]);
+        }
+        position = at + 2;
+    }
+
+    (body, "")
+}
+
+/// Looks up a dotted path such as `this.title` or `menus.main`.
+///
+/// Plain keys only resolve when they exist at the top level of the
+/// data; flat `PageOptions` elements are looked up by `render_blocks`.
+fn resolve_path<'v>(
+    path: &str,
+    root: &Root<'v>,
+    scope: &Scope<'v>,
+) -> Option<Cow<'v, Value>> {
+    if path.is_empty() || path.contains(char::is_whitespace) {
+        return None;
+    }
+
+    let mut segments = path.split('.');
+    let mut current = match segments.next()? {
+        "@index" => {
+            return scope
+                .index
+                .map(|index| Cow::Owned(Value::from(index)))
+        }
+        "this" => scope.this?,
+        key => root.get(key)?,
+    };
+
+    for segment in segments {
+        current = match current {
+            Value::Array(items) => {
+                items.get(segment.parse::<usize>().ok()?)?
+            }
+            Value::Object(map) => map.get(segment)?,
+            _ => return None,
+        };
+    }
+
+    Some(Cow::Borrowed(current))
+}
+
+/// Returns `false` for null, `false`, zero and empty values.
+fn is_truthy(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) => false,
+        Some(Value::Bool(flag)) => *flag,
+        Some(Value::Number(number)) => number.as_f64() != Some(0.0),
+        Some(Value::String(text)) => !text.is_empty(),
+        Some(Value::Array(items)) => !items.is_empty(),
+        Some(Value::Object(map)) => !map.is_empty(),
+    }
+}
+
+/// Renders a value the way it should appear in the HTML output.
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        _ => value.to_string(),
+    }
+}
+
+/// ## Function: `layout_file` - Resolve the template file of a layout
+///
+/// Returns `<layout>.html` when the template directory has it, and
+/// otherwise the template the layout falls back to, as described by
+/// `macro_render_layout!`.
+///
+/// # Arguments
+///
+/// * `template_path` - The path to the template directory.
+/// * `layout` - The name of the layout (e.g. `post`).
+///
+/// # Returns
+///
+/// The path to the template file.
+///
+pub fn layout_file(template_path: &Path, layout: &str) -> PathBuf {
+    let file_path = template_path.join(format!("{}.html", layout));
+    if file_path.exists() {
+        return file_path;
+    }
+
+    template_path.join(match layout {
+        "archive" => "page.html",
+        "contact" => "contact.html",
+        "index" => "index.html",
+        "page" => "page.html",
+        "post" => "post.html",
+        "section" => "page.html",
+        _ => "index.html",
+    })
+}
+
 /// Function: `render_page` - Render an HTML page
 ///
 /// Renders an HTML page with given attributes contained within a `PageOptions` struct.
@@ -90,6 +438,8 @@ pub fn render_template(
 /// This function takes a `PageOptions` struct, which contains various elements of an HTML page,
 /// stored as a HashMap. The key-value pairs in the HashMap are used to dynamically construct the page.
 /// The HashMap is passed to the `macro_render_layout!` function along with the template HTML file and layout.
+/// Any structured values set with `PageOptions::set_data` are made available to the template's
+/// `{{#each}}`, `{{#if}}` and dotted tags (see `render_template_with_data`).
 /// The resulting string returned by the macro is the final HTML page that is generated.
 ///
 /// # Arguments
@@ -109,7 +459,13 @@ pub fn render_page(
     layout: &String,
 ) -> Result<String, String> {
     // Renders the page using the specified template and layout
-    macro_render_layout!(layout, template_path, options.elements)
+    macro_render_layout!(
+        layout,
+        template_path,
+        options.elements,
+        options.data,
+        options.shared
+    )
 }
 
 /// Custom error type to handle both reqwest and io errors
@@ -34,6 +34,12 @@ const INDEX_FILES: [&str; 7] =
 
 /// Writes the files to the build directory.
 ///
+/// The root `index` file is written to the build directory itself, along
+/// with the site-wide files. Any other file is written to a directory
+/// named after its path without the extension (e.g. `guides/install.md`
+/// to `guides/install/`), and a nested `index` file to its own directory
+/// (e.g. `guides/index.md` to `guides/`).
+///
 /// # Arguments
 ///
 /// * `build_dir_path` - The path to the build directory.
@@ -62,6 +68,12 @@ pub fn write_files_to_build_directory(
         _ => file.name.to_string(),
     };
 
+    // A nested `index` file is the page of its directory
+    let file_name = match file_name.strip_suffix("/index") {
+        Some(dir_name) => dir_name.to_string(),
+        None => file_name,
+    };
+
     let index_html_minified = file_name == "index";
     let dir_name = build_dir_path.join(&file_name);
 
@@ -0,0 +1,38 @@
+//! Fixtures shared by the integration tests.
+
+// Each test crate uses only some of the fixtures.
+#![allow(dead_code)]
+
+use serde_json::Value;
+use ssg::models::data::FileData;
+use ssg::modules::collections::page_entry;
+use ssg::modules::permalinks::Routes;
+use std::collections::HashMap;
+
+/// Returns a content file.
+pub fn file(name: &str, content: &str) -> FileData {
+    FileData {
+        name: name.to_string(),
+        content: content.to_string(),
+        ..Default::default()
+    }
+}
+
+/// Returns a content file with the given front matter and a short body.
+pub fn page(name: &str, front_matter: &str) -> FileData {
+    file(name, &format!("---\n{}\n---\n\nBody", front_matter))
+}
+
+/// Returns the front matter of a page from its fields.
+pub fn metadata(fields: &[(&str, &str)]) -> HashMap<String, String> {
+    fields
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+/// Returns the collection entry of a page, at the URL derived from its
+/// file name.
+pub fn entry(name: &str, fields: &[(&str, &str)]) -> Value {
+    page_entry(name, &metadata(fields), &Routes::new().url(name))
+}
@@ -0,0 +1,164 @@
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use ssg::models::data::{
+        ArchiveConfig, ArchivePeriod, FileData, SiteConfig,
+    };
+    use ssg::modules::archives::{
+        archive_data, archive_name, archive_title, collect_archives,
+        generate_archive_files, ARCHIVE_INDEX,
+    };
+    use ssg::modules::frontmatter::extract;
+    use ssg::modules::permalinks::resolve_routes;
+
+    fn config(section: &str) -> SiteConfig {
+        SiteConfig {
+            archives: ArchiveConfig {
+                enabled: true,
+                section: section.to_string(),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn entries() -> Vec<Value> {
+        vec![
+            json!({"name": "posts/a.md", "section": "posts",
+                "title": "A", "url": "/posts/a/index.html",
+                "date": "2024-07-02"}),
+            json!({"name": "posts/b.md", "section": "posts",
+                "title": "B", "url": "/posts/b/index.html",
+                "date": "2024-07-20"}),
+            json!({"name": "posts/c.md", "section": "posts",
+                "title": "C", "url": "/posts/c/index.html",
+                "date": "2024-05-01"}),
+            json!({"name": "posts/d.md", "section": "posts",
+                "title": "D", "url": "/posts/d/index.html",
+                "date": "December 1, 2023"}),
+            json!({"name": "posts/index.md", "section": "posts",
+                "date": "2024-01-01"}),
+            json!({"name": "about.md", "section": "",
+                "date": "2022-01-01"}),
+            json!({"name": "posts/undated.md", "section": "posts"}),
+        ]
+    }
+
+    fn period(
+        year: i32,
+        month: Option<u32>,
+        pages: &[&str],
+    ) -> ArchivePeriod {
+        ArchivePeriod {
+            year,
+            month,
+            pages: pages.iter().map(|page| page.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_collect_archives() {
+        assert_eq!(
+            collect_archives(&entries(), &config("posts")),
+            vec![
+                period(
+                    2024,
+                    None,
+                    &["posts/b.md", "posts/a.md", "posts/c.md"]
+                ),
+                period(2024, Some(7), &["posts/b.md", "posts/a.md"]),
+                period(2024, Some(5), &["posts/c.md"]),
+                period(2023, None, &["posts/d.md"]),
+                period(2023, Some(12), &["posts/d.md"]),
+            ]
+        );
+
+        let all = collect_archives(&entries(), &config(""));
+        assert_eq!(
+            all.last(),
+            Some(&period(2022, Some(1), &["about.md"]))
+        );
+    }
+
+    #[test]
+    fn test_collect_archives_disabled() {
+        assert!(collect_archives(&entries(), &SiteConfig::default())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_archive_name_and_title() {
+        let year = period(2024, None, &[]);
+        let month = period(2024, Some(7), &[]);
+
+        assert_eq!(archive_name(&year), "2024/index.md");
+        assert_eq!(archive_name(&month), "2024/07/index.md");
+        assert_eq!(archive_title(&year, "en"), "2024");
+        assert_eq!(archive_title(&month, "en"), "July 2024");
+        assert_eq!(archive_title(&month, "fr"), "juillet 2024");
+    }
+
+    #[test]
+    fn test_archive_data() {
+        let config = config("posts");
+        let entries = entries();
+        let periods = collect_archives(&entries, &config);
+        let data = archive_data(&periods, &entries, &config);
+
+        let july = &data["2024/07/index.md"];
+        assert_eq!(july["title"], "July 2024");
+        assert_eq!(july["month"], 7);
+        assert_eq!(july["count"], 2);
+        assert_eq!(july["pages"][0]["title"], "B");
+        assert!(data["2024/index.md"]["month"].is_null());
+
+        let index = &data[ARCHIVE_INDEX];
+        assert_eq!(index["count"], 4);
+        assert_eq!(index["years"][0]["year"], 2024);
+        assert_eq!(index["years"][0]["count"], 3);
+        assert_eq!(
+            index["years"][0]["months"][1]["url"],
+            "/2024/05/index.html"
+        );
+        assert_eq!(
+            index["years"][1]["months"][0]["title"],
+            "December 2023"
+        );
+    }
+
+    #[test]
+    fn test_generate_archive_files() {
+        let files: Vec<FileData> = [
+            ("posts/one.md", "title: One\ndate: 2024-07-02"),
+            ("posts/two.md", "title: Two\ndate: 2024-05-01"),
+            ("2024/index.md", "title: My 2024"),
+        ]
+        .iter()
+        .map(|(name, front_matter)| FileData {
+            name: name.to_string(),
+            content: format!("---\n{}\n---\n\nBody", front_matter),
+            ..Default::default()
+        })
+        .collect();
+        let config = config("posts");
+        let routes =
+            resolve_routes(&files, &config.permalinks).unwrap();
+        let generated =
+            generate_archive_files(&files, &routes, &config);
+
+        let names: Vec<&str> =
+            generated.iter().map(|file| file.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["2024/07/index.md", "2024/05/index.md", ARCHIVE_INDEX]
+        );
+
+        let front_matter = extract(&generated[0].content);
+        assert_eq!(front_matter["title"], "July 2024");
+        assert_eq!(front_matter["layout"], "archive");
+        assert!(!front_matter.contains_key("date"));
+        assert!(generated[0].content.ends_with("---\n\n\n"));
+        assert!(generated[2].content.ends_with(
+            "- [2024](/2024/index.html) (2)\n  - [July 2024](/2024/07/index.html) (1)\n  - [May 2024](/2024/05/index.html) (1)\n"
+        ));
+    }
+}
@@ -0,0 +1,216 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{metadata, page};
+    use serde_json::json;
+    use ssg::models::data::{AuthorProfile, SiteConfig};
+    use ssg::modules::authors::{
+        apply_authors, author_data, author_feeds, author_json_ld,
+        collect_author_pages, generate_author_files, page_authors,
+    };
+    use ssg::modules::frontmatter::extract;
+    use ssg::modules::permalinks::resolve_routes;
+
+    fn config() -> SiteConfig {
+        let mut config = SiteConfig::default();
+        let _ = config.authors.insert(
+            "jane".to_string(),
+            AuthorProfile {
+                name: "Jane Doe".to_string(),
+                email: "jane@example.com".to_string(),
+                bio: "Jane writes the tutorials.".to_string(),
+                social: [
+                    ("twitter".to_string(), "janedoe".to_string()),
+                    (
+                        "github".to_string(),
+                        "https://github.com/janedoe".to_string(),
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+                ..Default::default()
+            },
+        );
+        let _ = config.authors.insert(
+            "john".to_string(),
+            AuthorProfile {
+                name: "John Roe".to_string(),
+                ..Default::default()
+            },
+        );
+        config
+    }
+
+    #[test]
+    fn test_page_authors() {
+        let config = config();
+
+        assert_eq!(
+            page_authors(&metadata(&[("author", "jane")]), &config),
+            vec!["jane"]
+        );
+        assert_eq!(
+            page_authors(
+                &metadata(&[(
+                    "authors",
+                    r#"["john","ghost","jane","john"]"#
+                )]),
+                &config
+            ),
+            vec!["john", "jane"]
+        );
+        assert!(page_authors(
+            &metadata(&[("author", "jane@example.com (Jane Doe)")]),
+            &config
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_apply_authors() {
+        let config = config();
+        let mut page = metadata(&[("authors", "jane, john")]);
+        apply_authors(&mut page, &config);
+
+        assert_eq!(page["author"], "jane@example.com (Jane Doe)");
+        assert_eq!(page["authors"], "jane, john");
+        assert_eq!(page["twitter_creator"], "@janedoe");
+        assert_eq!(page_authors(&page, &config), vec!["jane", "john"]);
+
+        let mut page = metadata(&[
+            ("author", "Someone else"),
+            ("twitter_creator", "@someone"),
+        ]);
+        apply_authors(&mut page, &config);
+        assert_eq!(page["author"], "Someone else");
+        assert_eq!(page["twitter_creator"], "@someone");
+        assert!(!page.contains_key("authors"));
+    }
+
+    #[test]
+    fn test_author_json_ld() {
+        let config = config();
+
+        let json: serde_json::Value = serde_json::from_str(
+            &author_json_ld(&metadata(&[("author", "jane")]), &config),
+        )
+        .unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "@type": "Person",
+                "name": "Jane Doe",
+                "email": "jane@example.com",
+                "description": "Jane writes the tutorials.",
+                "sameAs": ["https://github.com/janedoe"],
+            })
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&author_json_ld(
+                &metadata(&[("authors", "jane, john")]),
+                &config,
+            ))
+            .unwrap();
+        assert_eq!(
+            json[1],
+            json!({"@type": "Person", "name": "John Roe"})
+        );
+
+        assert_eq!(
+            author_json_ld(
+                &metadata(&[("author", "A </script>")]),
+                &config
+            ),
+            r#"{"@type":"Person","name":"A <\/script>"}"#
+        );
+    }
+
+    #[test]
+    fn test_collect_author_pages() {
+        let files = vec![
+            page("old.md", "author: jane\ndate: 2023-01-01"),
+            page("new.md", "authors: [jane, john]\ndate: 2024-01-01"),
+            page("other.md", "author: Someone"),
+            page("authors/jane.md", "author: jane"),
+        ];
+        let pages = collect_author_pages(&files, &config());
+
+        assert_eq!(pages["jane"], vec!["new.md", "old.md"]);
+        assert_eq!(pages["john"], vec!["new.md"]);
+        assert_eq!(pages.len(), 2);
+    }
+
+    #[test]
+    fn test_generate_author_files() {
+        let config = config();
+        let files = vec![
+            page(
+                "one.md",
+                "title: One\nauthor: jane\ndate: 2023-01-01",
+            ),
+            page(
+                "two.md",
+                "title: Two\nauthor: jane\ndate: 2024-01-01",
+            ),
+            page("three.md", "title: Three\nauthor: john"),
+            page("authors/john.md", "title: About John"),
+        ];
+        let author_pages = collect_author_pages(&files, &config);
+        let generated =
+            generate_author_files(&files, &author_pages, &config);
+
+        assert_eq!(generated.len(), 1);
+        assert_eq!(generated[0].name, "authors/jane.md");
+        let front_matter = extract(&generated[0].content);
+        assert_eq!(front_matter["title"], "Jane Doe");
+        assert_eq!(
+            front_matter["description"],
+            "Jane writes the tutorials."
+        );
+        assert_eq!(front_matter["author"], "jane");
+        assert!(generated[0].content.ends_with("---\n\n\n"));
+    }
+
+    #[test]
+    fn test_author_data_and_feeds() {
+        let mut config = config();
+        config.base_url = "https://example.com".to_string();
+        let files = vec![page("one.md", "title: One\nauthor: jane")];
+        let routes =
+            resolve_routes(&files, &config.permalinks).unwrap();
+        let author_pages = collect_author_pages(&files, &config);
+
+        let data = author_data(
+            &metadata(&[("authors", "jane, john")]),
+            &config,
+            &routes,
+            &author_pages,
+        );
+        assert_eq!(data[0]["id"], "jane");
+        assert_eq!(data[0]["name"], "Jane Doe");
+        assert_eq!(data[0]["url"], "/authors/jane/index.html");
+        assert_eq!(data[1]["url"], "");
+
+        let entries = vec![json!({
+            "name": "one.md",
+            "title": "One",
+            "url": "/one/index.html",
+            "date": "2024-05-01",
+        })];
+        let feeds = author_feeds(&author_pages, &entries, &config, 0);
+        let items = &feeds["authors/jane.md"];
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].item_title, "One");
+        assert_eq!(
+            items[0].item_link,
+            "https://example.com/one/index.html"
+        );
+        assert_eq!(items[0].author, "jane@example.com (Jane Doe)");
+        assert_eq!(
+            items[0].item_pub_date,
+            "Wed, 01 May 2024 00:00:00 +0000"
+        );
+    }
+}
@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests {
+    use ssg::modules::bundles::{
+        bundle_assets, bundle_pages, copy_assets, is_bundle_page,
+    };
+    use ssg::modules::permalinks::Routes;
+    use std::fs;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn pages() -> Vec<String> {
+        names(&[
+            "index.md",
+            "launch/index.md",
+            "launch/index.fr.md",
+            "launch/faq.md",
+            "drafts/index.md",
+        ])
+    }
+
+    #[test]
+    fn test_is_bundle_page() {
+        assert!(is_bundle_page("launch/index.md"));
+        assert!(is_bundle_page("launch/index.json"));
+        assert!(is_bundle_page("launch/index.fr.md"));
+        assert!(!is_bundle_page("launch/indexes.md"));
+        assert!(!is_bundle_page("launch/index.png"));
+    }
+
+    #[test]
+    fn test_bundle_pages() {
+        let pages = pages();
+
+        assert_eq!(
+            bundle_pages("launch/img/hero.png", &pages),
+            vec!["launch/index.md", "launch/index.fr.md"]
+        );
+        assert_eq!(bundle_pages("logo.png", &pages), vec!["index.md"]);
+        assert!(
+            bundle_pages("logo.png", &names(&["about.md"])).is_empty()
+        );
+    }
+
+    #[test]
+    fn test_bundle_assets() {
+        let mut routes = Routes::new();
+        routes.insert("index.md", "");
+        routes.insert("launch/index.md", "2024/launch");
+        routes.insert("launch/index.fr.md", "fr/2024/launch");
+
+        let assets = names(&[
+            "logo.png",
+            "launch/img/hero.png",
+            "drafts/plan.pdf",
+        ]);
+        let copies = bundle_assets(&assets, &pages(), &routes);
+
+        assert_eq!(
+            copies,
+            [
+                ("logo.png", "logo.png"),
+                ("launch/img/hero.png", "2024/launch/img/hero.png"),
+                ("launch/img/hero.png", "fr/2024/launch/img/hero.png"),
+            ]
+            .iter()
+            .map(|(asset, destination)| {
+                (asset.to_string(), destination.to_string())
+            })
+            .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_copy_assets() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = dir.path().join("content");
+        let build = dir.path().join("build");
+        fs::create_dir_all(content.join("launch")).unwrap();
+        fs::write(content.join("launch/hero.png"), [0x89, 0x50, 0xff])
+            .unwrap();
+
+        copy_assets(
+            &content,
+            &build,
+            &[(
+                "launch/hero.png".to_string(),
+                "2024/launch/hero.png".to_string(),
+            )],
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read(build.join("2024/launch/hero.png")).unwrap(),
+            vec![0x89, 0x50, 0xff]
+        );
+    }
+}
@@ -0,0 +1,215 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::file;
+    use ssg::models::data::{CascadeRule, FileData, SiteConfig};
+    use ssg::modules::cascade::{
+        apply_defaults, cascade_layers, collect_defaults,
+        effective_front_matter, explain_front_matter, glob_match,
+        merge_front_matter,
+    };
+    use ssg::modules::frontmatter::extract;
+    use std::{collections::BTreeMap, fs};
+
+    fn config() -> SiteConfig {
+        SiteConfig {
+            cascade: vec![
+                CascadeRule {
+                    path: "**".to_string(),
+                    values: [
+                        ("layout", "page"),
+                        ("robots", "index"),
+                        ("charset", "utf-8"),
+                    ]
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+                },
+                CascadeRule {
+                    path: "posts/*.md".to_string(),
+                    values: [(
+                        "robots".to_string(),
+                        "noindex".to_string(),
+                    )]
+                    .into_iter()
+                    .collect(),
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn files() -> Vec<FileData> {
+        vec![
+            file("_defaults.yml", "author: Site Team\nlayout: home\n"),
+            file("posts/_defaults.yml", "layout: post\n"),
+            file(
+                "posts/hello.md",
+                "---\ntitle: Hello\nauthor: Jane\n---\n\nBody",
+            ),
+            file("about.md", "# About"),
+        ]
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("posts/**", "posts/a.md"));
+        assert!(glob_match("/posts/**/*.md", "posts/2024/07/a.md"));
+        assert!(glob_match("posts/**/*.md", "posts/a.md"));
+        assert!(glob_match("p?sts/*", "posts/a.md"));
+        assert!(!glob_match("posts/*", "posts/2024/a.md"));
+        assert!(!glob_match("posts/**", "docs/a.md"));
+        assert!(glob_match("*.md", "about.md"));
+        assert!(!glob_match("*.md", "about.mdx"));
+    }
+
+    #[test]
+    fn test_cascade_layers() {
+        let defaults = collect_defaults(&files()).unwrap();
+        let sources: Vec<String> =
+            cascade_layers("posts/hello.md", &config(), &defaults)
+                .into_iter()
+                .map(|(source, _)| source)
+                .collect();
+
+        assert_eq!(
+            sources,
+            vec![
+                "ssg.toml [[cascade]] **",
+                "ssg.toml [[cascade]] posts/*.md",
+                "_defaults.yml",
+                "posts/_defaults.yml",
+            ]
+        );
+        assert_eq!(
+            cascade_layers("about.md", &config(), &defaults).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_effective_front_matter() {
+        let files = files();
+        let defaults = collect_defaults(&files).unwrap();
+        let front_matter =
+            effective_front_matter(&files[2], &config(), &defaults);
+
+        let field = |key: &str| {
+            let (value, source) = &front_matter[key];
+            (value.as_str(), source.as_str())
+        };
+        assert_eq!(field("title"), ("Hello", "page"));
+        assert_eq!(field("author"), ("Jane", "page"));
+        assert_eq!(field("layout"), ("post", "posts/_defaults.yml"));
+        assert_eq!(
+            field("robots"),
+            ("noindex", "ssg.toml [[cascade]] posts/*.md")
+        );
+        assert_eq!(
+            field("charset"),
+            ("utf-8", "ssg.toml [[cascade]] **")
+        );
+    }
+
+    #[test]
+    fn test_apply_defaults() {
+        let pages = apply_defaults(files(), &config()).unwrap();
+
+        let names: Vec<&str> =
+            pages.iter().map(|page| page.name.as_str()).collect();
+        assert_eq!(names, vec!["posts/hello.md", "about.md"]);
+
+        let hello = extract(&pages[0].content);
+        assert_eq!(hello["author"], "Jane");
+        assert_eq!(hello["layout"], "post");
+        assert_eq!(hello["robots"], "noindex");
+        assert!(pages[0].content.ends_with("---\n\nBody"));
+
+        let about = extract(&pages[1].content);
+        assert_eq!(about["layout"], "home");
+        assert_eq!(about["author"], "Site Team");
+        assert!(pages[1].content.ends_with("---\n\n# About"));
+
+        let error = apply_defaults(
+            vec![file("docs/_defaults.yml", "- not a mapping")],
+            &config(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            error,
+            "Error parsing docs/_defaults.yml: not a mapping"
+        );
+    }
+
+    #[test]
+    fn test_merge_front_matter_formats() {
+        let defaults: BTreeMap<String, String> = [
+            ("layout".to_string(), "post".to_string()),
+            ("title".to_string(), "Untitled".to_string()),
+        ]
+        .into_iter()
+        .collect();
+
+        let toml = merge_front_matter(
+            "+++\ntitle = \"Hello\"\n\n[extra]\nkey = 1\n+++\nBody",
+            &defaults,
+        );
+        assert_eq!(
+            toml,
+            "+++\nlayout = \"post\"\ntitle = \"Hello\"\n\n[extra]\nkey = 1\n+++\nBody"
+        );
+        assert_eq!(extract(&toml)["layout"], "post");
+
+        let json = merge_front_matter(
+            r#"{"frontmatter": {"title": "Hello"}, "content": "Body"}"#,
+            &defaults,
+        );
+        let front_matter = extract(&json);
+        assert_eq!(front_matter["layout"], "post");
+        assert_eq!(front_matter["title"], "Hello");
+
+        let yaml = merge_front_matter(
+            "---\ntitle: Hello\nmenu:\n  main: {}\n---\nBody",
+            &BTreeMap::from([(
+                "menu".to_string(),
+                "footer".to_string(),
+            )]),
+        );
+        assert_eq!(
+            yaml,
+            "---\ntitle: Hello\nmenu:\n  main: {}\n---\nBody"
+        );
+    }
+
+    #[test]
+    fn test_explain_front_matter() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = dir.path().join("content");
+        fs::create_dir_all(content.join("posts")).unwrap();
+        fs::write(
+            dir.path().join("ssg.toml"),
+            "[[cascade]]\npath = \"posts/**\"\nvalues = { robots = \"noindex\" }\n",
+        )
+        .unwrap();
+        fs::write(content.join("posts/_defaults.yml"), "layout: post")
+            .unwrap();
+        fs::write(
+            content.join("posts/hello.md"),
+            "---\ntitle: Hello\n---\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            explain_front_matter(&content, "posts/hello.md").unwrap(),
+            [
+                "Front matter of posts/hello.md:",
+                "  layout = \"post\"     (posts/_defaults.yml)",
+                "  robots = \"noindex\"  (ssg.toml [[cascade]] posts/**)",
+                "  title = \"Hello\"     (page)",
+            ]
+            .join("\n")
+        );
+        assert!(explain_front_matter(&content, "missing.md").is_err());
+    }
+}
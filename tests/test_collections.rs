@@ -0,0 +1,156 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::entry;
+    use serde_json::Value;
+    use ssg::models::data::CollectionQuery;
+    use ssg::modules::collections::{
+        group_pages, query_pages, run_queries,
+    };
+    use ssg::utilities::template::render_template_with_data;
+    use std::collections::{BTreeMap, HashMap};
+
+    fn titles(pages: &[Value]) -> Vec<&str> {
+        pages
+            .iter()
+            .map(|page| page["title"].as_str().unwrap())
+            .collect()
+    }
+
+    fn site() -> Vec<Value> {
+        vec![
+            entry(
+                "index.md",
+                &[("title", "Home"), ("layout", "index")],
+            ),
+            entry(
+                "posts/first.md",
+                &[
+                    ("title", "First"),
+                    ("layout", "post"),
+                    ("date", "2024-01-05"),
+                    ("tags", "rust, web"),
+                ],
+            ),
+            entry(
+                "posts/second.md",
+                &[
+                    ("title", "Second"),
+                    ("layout", "post"),
+                    ("date", "Mon, 12 Feb 2024 10:00:00 GMT"),
+                    ("tags", "rust"),
+                    ("featured", "true"),
+                ],
+            ),
+            entry(
+                "posts/third.md",
+                &[("title", "Third"), ("layout", "post")],
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_page_entry() {
+        let page = entry("posts/first.md", &[("title", "First")]);
+        assert_eq!(page["title"], "First");
+        assert_eq!(page["name"], "posts/first.md");
+        assert_eq!(page["url"], "/posts/first/index.html");
+        assert_eq!(page["section"], "posts");
+        assert_eq!(entry("about.md", &[])["section"], "");
+    }
+
+    #[test]
+    fn test_group_pages() {
+        let grouped = group_pages(&site());
+        assert_eq!(grouped["pages"].as_array().unwrap().len(), 4);
+        assert_eq!(
+            grouped["sections"]["posts"].as_array().unwrap().len(),
+            3
+        );
+        assert_eq!(grouped["layouts"]["index"][0]["title"], "Home");
+        assert_eq!(
+            grouped["tags"]["rust"].as_array().unwrap().len(),
+            2
+        );
+        assert_eq!(grouped["tags"]["web"][0]["title"], "First");
+    }
+
+    #[test]
+    fn test_query_pages_sorts_by_date_and_limits() {
+        let query = CollectionQuery {
+            section: "posts".to_string(),
+            sort: "date".to_string(),
+            order: "desc".to_string(),
+            limit: 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            titles(&query_pages(&site(), &query)),
+            vec!["Second", "First"]
+        );
+
+        let ascending = CollectionQuery {
+            order: "asc".to_string(),
+            ..query
+        };
+        assert_eq!(
+            titles(&query_pages(&site(), &ascending)),
+            vec!["First", "Second"]
+        );
+    }
+
+    #[test]
+    fn test_query_pages_filters() {
+        let by_tag = CollectionQuery {
+            tag: "web".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            titles(&query_pages(&site(), &by_tag)),
+            vec!["First"]
+        );
+
+        let mut filter = BTreeMap::new();
+        filter.insert("featured".to_string(), "true".to_string());
+        let by_field = CollectionQuery {
+            layout: "post".to_string(),
+            filter,
+            ..Default::default()
+        };
+        assert_eq!(
+            titles(&query_pages(&site(), &by_field)),
+            vec!["Second"]
+        );
+    }
+
+    #[test]
+    fn test_run_queries_in_template() {
+        let mut queries = BTreeMap::new();
+        queries.insert(
+            "recent".to_string(),
+            CollectionQuery {
+                section: "posts".to_string(),
+                sort: "date".to_string(),
+                order: "desc".to_string(),
+                ..Default::default()
+            },
+        );
+        let mut data = serde_json::Map::new();
+        data.insert(
+            "collections".to_string(),
+            run_queries(&site(), &queries),
+        );
+
+        let result = render_template_with_data(
+            "{{#each collections.recent}}<a href=\"{{this.url}}\">{{this.title}}</a>{{/each}}",
+            &HashMap::new(),
+            &data,
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            "<a href=\"/posts/second/index.html\">Second</a><a href=\"/posts/first/index.html\">First</a><a href=\"/posts/third/index.html\">Third</a>"
+        );
+    }
+}
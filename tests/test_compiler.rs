@@ -1,9 +1,10 @@
 #[cfg(test)]
 mod tests {
-    use std::error::Error;
+    use std::{error::Error, fs};
 
     use ssg::{
-        models::data::RssData,
+        compiler::compile_with_options,
+        models::data::{BuildOptions, RssData},
         modules::{
             frontmatter::extract, html::generate_html,
             rss::generate_rss,
@@ -85,4 +86,97 @@ fn test_generate_html() {
 
         Ok(())
     }
+
+    #[test]
+    fn test_compile_with_options_builds_the_site(
+    ) -> Result<(), Box<dyn Error>> {
+        let dir = tempfile::tempdir()?;
+        let root = dir.path();
+        let write = |path: &str, content: &str| {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+        write(
+            "ssg.toml",
+            r#"base_url = "https://example.com"
+
+[permalinks]
+posts = "/:year/:month/:slug/"
+
+[authors.jane]
+name = "Jane Doe"
+
+[generators.products]
+data = "products"
+path = "products/:id"
+title = "name"
+description = "summary"
+fields = { author = "author" }
+"#,
+        );
+        write(
+            "data/products.json",
+            r#"[{"id": "w-1", "name": "Widget", "summary": "A widget.", "author": "jane"}]"#,
+        );
+        write(
+            "content/index.md",
+            "---\ntitle: Home\ndescription: The home page\n---\n\nWelcome",
+        );
+        write(
+            "content/tags.md",
+            "---\ntitle: Tags\ndescription: The tags\nlayout: page\n---\n\n[[content]]",
+        );
+        write(
+            "content/posts/hello.md",
+            "---\ntitle: Hello\ndescription: The first post\ndate: 2024-05-06\nauthor: jane\n---\n\nHello world",
+        );
+        let layout = "<!DOCTYPE html><html><head><title>{{title}}</title></head><body><nav>{{navigation}}</nav><main>{{content}}</main></body></html>";
+        write("template/index.html", layout);
+        write("template/page.html", layout);
+        write("template/main.js", "");
+        write("template/sw.js", "");
+
+        compile_with_options(
+            &root.join("build"),
+            &root.join("content"),
+            &root.join("public"),
+            &root.join("template"),
+            &BuildOptions::default(),
+        )?;
+
+        let read = |path: &str| {
+            fs::read_to_string(root.join("public").join(path)).unwrap()
+        };
+        let post = "/2024/05/hello/index.html";
+        let product = "/products/w-1/index.html";
+
+        // The permalinked and the generated pages are written at their
+        // resolved paths
+        assert!(read("2024/05/hello/index.html").contains("Hello world"));
+        assert!(read("products/w-1/index.html").contains("Widget"));
+
+        // and listed in the navigation bar, the sitemap and the feed of
+        // their author
+        let home = read("index.html");
+        assert!(home.contains(&format!("href={}>Hello</a>", post)));
+        assert!(home.contains(&format!("href={}>Widget</a>", product)));
+        let sitemap = read("sitemap.xml");
+        assert!(sitemap
+            .contains(&format!("<loc>https://example.com{}</loc>", post)));
+        assert!(sitemap.contains(&format!(
+            "<loc>https://example.com{}</loc>",
+            product
+        )));
+        let feed = read("authors/jane/rss.xml");
+        assert!(feed
+            .contains(&format!("<link>https://example.com{}</link>", post)));
+        assert!(feed.contains(&format!(
+            "<link>https://example.com{}</link>",
+            product
+        )));
+        assert!(!root.join("build").exists());
+
+        Ok(())
+    }
 }
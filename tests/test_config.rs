@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use ssg::modules::config::{config_path, load_config};
+    use std::{fs, path::Path};
+
+    #[test]
+    fn test_config_path_is_next_to_content() {
+        assert_eq!(
+            config_path(Path::new("site/content")),
+            Path::new("site/ssg.toml")
+        );
+        assert_eq!(
+            config_path(Path::new("content")),
+            Path::new("ssg.toml")
+        );
+    }
+
+    #[test]
+    fn test_load_config_missing_file_returns_default() {
+        let config =
+            load_config(Path::new("does/not/exist/ssg.toml")).unwrap();
+        assert!(config.menus.is_empty());
+        assert_eq!(config.pagination.per_page, 0);
+        assert!(config.collections.is_empty());
+    }
+
+    #[test]
+    fn test_load_config_reads_menus() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ssg.toml");
+        fs::write(
+            &path,
+            r#"
+[[menus.main]]
+identifier = "docs"
+title = "Docs"
+url = "/docs/index.html"
+weight = 10
+
+[[menus.footer]]
+title = "Privacy"
+url = "/privacy/index.html"
+"#,
+        )
+        .unwrap();
+
+        let config = load_config(&path).unwrap();
+
+        assert_eq!(config.menus["main"][0].identifier, "docs");
+        assert_eq!(config.menus["main"][0].weight, 10);
+        assert_eq!(config.menus["footer"][0].title, "Privacy");
+    }
+
+    #[test]
+    fn test_load_config_reads_base_url() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ssg.toml");
+        fs::write(
+            &path,
+            "base_url = \"https://example.com/product/docs/\"\nrelative_links = true\n",
+        )
+        .unwrap();
+
+        let config = load_config(&path).unwrap();
+
+        assert_eq!(
+            config.base_url,
+            "https://example.com/product/docs/"
+        );
+        assert!(config.relative_links);
+    }
+
+    #[test]
+    fn test_load_config_invalid_toml_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ssg.toml");
+        fs::write(&path, "menus = [").unwrap();
+
+        assert!(load_config(&path).is_err());
+    }
+}
@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use ssg::modules::datafiles::{load_data, parse_data};
+    use ssg::utilities::template::render_template_with_data;
+    use std::{collections::HashMap, fs};
+
+    #[test]
+    fn test_parse_data_formats() {
+        assert_eq!(
+            parse_data(r#"{"plans": [{"price": 9.5}]}"#, "json")
+                .unwrap(),
+            json!({"plans": [{"price": 9.5}]})
+        );
+        assert_eq!(
+            parse_data(
+                "[[plans]]\nname = \"Basic\"\nprice = 9\nyearly = true\n",
+                "toml"
+            )
+            .unwrap(),
+            json!({"plans": [{"name": "Basic", "price": 9, "yearly": true}]})
+        );
+        assert_eq!(
+            parse_data(
+                "- name: Jane\n  age: 30\n  score: 1.5\n",
+                "yml"
+            )
+            .unwrap(),
+            json!([{"name": "Jane", "age": 30, "score": 1.5}])
+        );
+        assert!(parse_data("{", "json").is_err());
+        assert!(parse_data("a: [", "yaml").is_err());
+        assert!(parse_data("", "xml").is_err());
+    }
+
+    #[test]
+    fn test_parse_data_csv() {
+        let csv = "version,notes\r\n1.0,\"First, \"\"stable\"\" release\"\r\n\r\n1.1,\"Two\nlines\"\r\n";
+
+        assert_eq!(
+            parse_data(csv, "csv").unwrap(),
+            json!([
+                {"version": "1.0", "notes": "First, \"stable\" release"},
+                {"version": "1.1", "notes": "Two\nlines"},
+            ])
+        );
+        assert_eq!(
+            parse_data("a,b\n1\n", "csv").unwrap(),
+            json!([{"a": "1", "b": ""}])
+        );
+        assert!(parse_data("a\n\"open", "csv").is_err());
+    }
+
+    #[test]
+    fn test_load_data() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("team")).unwrap();
+        fs::write(
+            dir.path().join("team/members.yaml"),
+            "- name: Jane\n- name: John\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("pricing.toml"),
+            "currency = \"EUR\"",
+        )
+        .unwrap();
+        fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let data = load_data(dir.path()).unwrap();
+        assert_eq!(
+            serde_json::Value::Object(data.clone()),
+            json!({
+                "pricing": {"currency": "EUR"},
+                "team": {"members": [{"name": "Jane"}, {"name": "John"}]},
+            })
+        );
+
+        let mut wrapper = serde_json::Map::new();
+        let _ = wrapper.insert(
+            "data".to_string(),
+            serde_json::Value::Object(data),
+        );
+        assert_eq!(
+            render_template_with_data(
+                "{{#each data.team.members}}{{this.name}} {{/each}}{{data.pricing.currency}}",
+                &HashMap::new(),
+                &wrapper,
+            )
+            .unwrap(),
+            "Jane John EUR"
+        );
+    }
+
+    #[test]
+    fn test_load_data_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load_data(&dir.path().join("missing"))
+            .unwrap()
+            .is_empty());
+
+        fs::write(dir.path().join("team.json"), "[]").unwrap();
+        fs::write(dir.path().join("team.csv"), "name\nJane\n").unwrap();
+        let error = load_data(dir.path()).unwrap_err().to_string();
+        assert!(error.contains("Duplicate data key `team`"));
+
+        fs::remove_file(dir.path().join("team.csv")).unwrap();
+        fs::write(dir.path().join("broken.json"), "{").unwrap();
+        let error = load_data(dir.path()).unwrap_err().to_string();
+        assert!(error.starts_with("Error parsing data file"));
+    }
+}
@@ -0,0 +1,170 @@
+#[cfg(test)]
+mod tests {
+    use ssg::models::data::PageDate;
+    use ssg::modules::dates::{
+        check_dates, date_data, date_key, format_date, normalize_date,
+        parse_date, parse_offset, site_offset, to_rfc2822, to_rfc3339,
+        to_w3c,
+    };
+    use std::collections::HashMap;
+
+    fn ymd(text: &str) -> Option<(i32, u32, u32)> {
+        date_key(text)
+    }
+
+    #[test]
+    fn test_parse_iso_dates() {
+        assert_eq!(ymd("2023-07-12"), Some((2023, 7, 12)));
+        assert_eq!(ymd("2023/7/1"), Some((2023, 7, 1)));
+
+        let date =
+            parse_date("2023-07-12T09:30:15.250+02:00", 0).unwrap();
+        assert_eq!(
+            date,
+            PageDate {
+                year: 2023,
+                month: 7,
+                day: 12,
+                hour: 9,
+                minute: 30,
+                second: 15,
+                offset: 120,
+                has_time: true,
+            }
+        );
+        assert_eq!(
+            parse_date("2023-07-12 09:30Z", 60).unwrap().offset,
+            0
+        );
+    }
+
+    #[test]
+    fn test_parse_rfc2822_dates() {
+        let date =
+            parse_date("Thu, 11 Apr 2024 06:06:06 +0000", 120).unwrap();
+
+        assert_eq!((date.year, date.month, date.day), (2024, 4, 11));
+        assert_eq!((date.hour, date.minute, date.second), (6, 6, 6));
+        assert_eq!(date.offset, 0);
+        assert_eq!(
+            parse_date("Mon, 19 Feb 2024 15:15:15 GMT", 0)
+                .unwrap()
+                .hour,
+            15
+        );
+    }
+
+    #[test]
+    fn test_parse_human_dates() {
+        assert_eq!(ymd("July 12, 2023"), Some((2023, 7, 12)));
+        assert_eq!(ymd("12 July 2023"), Some((2023, 7, 12)));
+        assert_eq!(ymd("Sept. 3rd, 2023"), Some((2023, 9, 3)));
+        assert_eq!(ymd("Wednesday, July 12 2023"), Some((2023, 7, 12)));
+    }
+
+    #[test]
+    fn test_parse_invalid_dates() {
+        assert_eq!(ymd(""), None);
+        assert_eq!(ymd("soon"), None);
+        assert_eq!(ymd("2023-02-29"), None);
+        assert_eq!(ymd("2024-02-29"), Some((2024, 2, 29)));
+        assert_eq!(ymd("2023-13-01"), None);
+        assert_eq!(ymd("12 July 2023 25:00"), None);
+    }
+
+    #[test]
+    fn test_dates_without_timezone_use_the_default_offset() {
+        let date = parse_date("2023-07-12 09:30", -300).unwrap();
+
+        assert_eq!(date.offset, -300);
+        assert_eq!(to_rfc3339(&date), "2023-07-12T09:30:00-05:00");
+    }
+
+    #[test]
+    fn test_parse_offset() {
+        assert_eq!(parse_offset("UTC"), Some(0));
+        assert_eq!(parse_offset("+05:30"), Some(330));
+        assert_eq!(parse_offset("-08"), Some(-480));
+        assert_eq!(parse_offset("CEST"), Some(120));
+        assert_eq!(parse_offset("+25:00"), None);
+        assert_eq!(parse_offset(""), None);
+    }
+
+    #[test]
+    fn test_site_offset() {
+        assert_eq!(site_offset(""), Ok(0));
+        assert_eq!(site_offset("-05:00"), Ok(-300));
+        assert!(site_offset("Europe/Paris")
+            .unwrap_err()
+            .contains("IANA timezone names are not supported"));
+        assert!(site_offset("Mars")
+            .unwrap_err()
+            .contains("Unknown timezone `Mars`"));
+    }
+
+    #[test]
+    fn test_output_formats() {
+        let date = parse_date("2023-07-12T09:30:00+02:00", 0).unwrap();
+
+        assert_eq!(
+            to_rfc2822(&date),
+            "Wed, 12 Jul 2023 09:30:00 +0200"
+        );
+        assert_eq!(to_rfc3339(&date), "2023-07-12T09:30:00+02:00");
+        assert_eq!(to_w3c(&date), "2023-07-12T09:30:00+02:00");
+        assert_eq!(
+            to_w3c(&parse_date("July 12, 2023", 0).unwrap()),
+            "2023-07-12"
+        );
+    }
+
+    #[test]
+    fn test_normalize_date_keeps_unparseable_text() {
+        assert_eq!(
+            normalize_date("2024-01-01", 0, to_rfc2822),
+            "Mon, 01 Jan 2024 00:00:00 +0000"
+        );
+        assert_eq!(normalize_date("tbd", 0, to_rfc2822), "tbd");
+    }
+
+    #[test]
+    fn test_format_date() {
+        let date = parse_date("2024-03-01T08:05:09Z", 0).unwrap();
+
+        assert_eq!(format_date(&date, "", "en-GB"), "March 1, 2024");
+        assert_eq!(format_date(&date, "", "de"), "1. März 2024");
+        assert_eq!(format_date(&date, "", "es"), "1 de marzo de 2024");
+        assert_eq!(
+            format_date(&date, "%a %d %b %Y %H:%M:%S %%", "fr"),
+            "ven 01 mar 2024 08:05:09 %"
+        );
+        assert_eq!(format_date(&date, "%A", "xx"), "Friday");
+    }
+
+    #[test]
+    fn test_date_data() {
+        let mut metadata = HashMap::new();
+        metadata
+            .insert("date".to_string(), "July 12, 2023".to_string());
+        metadata.insert("pub_date".to_string(), "soon".to_string());
+
+        let dates = date_data(&metadata, 0, "", "fr");
+
+        assert_eq!(dates["date"]["display"], "12 juillet 2023");
+        assert_eq!(dates["date"]["w3c"], "2023-07-12");
+        assert_eq!(dates["date"]["iso"], "2023-07-12T00:00:00+00:00");
+        assert!(dates.get("pub_date").is_none());
+    }
+
+    #[test]
+    fn test_check_dates() {
+        let mut metadata = HashMap::new();
+        metadata.insert("date".to_string(), "2023-07-12".to_string());
+        metadata.insert("expiry_date".to_string(), "never".to_string());
+
+        assert_eq!(
+            check_dates("post.md", &metadata),
+            vec!["Unparseable date `never` in the `expiry_date` field of `post.md`"]
+        );
+    }
+}
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use ssg::utilities::file::add;
+    use ssg::utilities::file::{add, add_matching, list_files};
     use std::{fs::File, io::Write, path::Path};
 
     #[test]
@@ -51,6 +51,57 @@ mod tests {
         assert_eq!(file2.sitemap, "File 2 content");
     }
 
+    #[test]
+    fn test_add_nested_directories() {
+        let temp_dir = tempfile::tempdir()
+            .expect("Failed to create temporary directory");
+        let temp_path = temp_dir.path();
+        std::fs::create_dir_all(temp_path.join("guides/advanced"))
+            .unwrap();
+        std::fs::create_dir_all(temp_path.join(".git")).unwrap();
+
+        create_test_file(temp_path, "index.md", "Home");
+        create_test_file(temp_path, "guides/install.md", "Install");
+        create_test_file(temp_path, "guides/advanced/tuning.md", "Tune");
+        create_test_file(temp_path, ".git/config", "Skipped");
+
+        let files = add(temp_path).unwrap();
+        let names: Vec<&str> =
+            files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "guides/advanced/tuning.md",
+                "guides/install.md",
+                "index.md"
+            ]
+        );
+        assert_eq!(files[1].content, "Install");
+    }
+
+    #[test]
+    fn test_add_matching_and_list_files() {
+        let temp_dir = tempfile::tempdir()
+            .expect("Failed to create temporary directory");
+        let temp_path = temp_dir.path();
+        std::fs::create_dir_all(temp_path.join("launch")).unwrap();
+
+        create_test_file(temp_path, "launch/index.md", "Launch");
+        std::fs::write(temp_path.join("launch/hero.png"), [0x89, 0xff])
+            .unwrap();
+
+        let files =
+            add_matching(temp_path, |name| name.ends_with(".md"))
+                .unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].name, "launch/index.md");
+
+        assert_eq!(
+            list_files(temp_path).unwrap(),
+            vec!["launch/hero.png", "launch/index.md"]
+        );
+    }
+
     fn create_test_file<P: AsRef<Path>>(
         dir: P,
         name: &str,
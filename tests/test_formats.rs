@@ -0,0 +1,154 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::file;
+    use serde_json::json;
+    use ssg::modules::formats::{
+        html_body, is_data_page, is_html_page, markdown_page,
+        normalize_pages, parse_data_page, renders_layout,
+    };
+    use ssg::modules::frontmatter::extract;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_is_data_page() {
+        assert!(is_data_page("index.json"));
+        assert!(is_data_page("docs/setup.TOML"));
+        assert!(is_data_page("about.yaml"));
+        assert!(!is_data_page("about.md"));
+        assert!(!is_data_page("json"));
+    }
+
+    #[test]
+    fn test_parse_data_page() {
+        let (front_matter, body) = parse_data_page(
+            "page.json",
+            r#"{"frontmatter": {"title": "Hello"}, "content": "Hi"}"#,
+        )
+        .unwrap();
+        assert_eq!(front_matter["title"], "Hello");
+        assert_eq!(body, "Hi");
+
+        let (front_matter, body) = parse_data_page(
+            "page.json",
+            r#"{"title": "Hello", "tags": ["a", "b"]}"#,
+        )
+        .unwrap();
+        assert_eq!(front_matter["tags"], json!(["a", "b"]));
+        assert_eq!(body, "");
+
+        let (front_matter, body) = parse_data_page(
+            "page.toml",
+            "title = \"Hello\"\ncontent = \"\"\"\n<p>Hi</p>\n\"\"\"\n",
+        )
+        .unwrap();
+        assert_eq!(front_matter["title"], "Hello");
+        assert_eq!(body, "<p>Hi</p>\n");
+
+        let (front_matter, body) = parse_data_page(
+            "page.yml",
+            "title: Hello\nweight: 3\ncontent: |\n  Body\n",
+        )
+        .unwrap();
+        assert_eq!(front_matter["weight"], 3);
+        assert_eq!(body, "Body\n");
+    }
+
+    #[test]
+    fn test_parse_data_page_errors() {
+        assert_eq!(
+            parse_data_page("page.json", "[1, 2]").unwrap_err(),
+            "Error parsing page.json: not an object"
+        );
+        assert_eq!(
+            parse_data_page("page.json", r#"{"content": 1}"#)
+                .unwrap_err(),
+            "Error parsing page.json: `content` is not a string"
+        );
+        assert!(parse_data_page("page.toml", "title = ").is_err());
+    }
+
+    #[test]
+    fn test_markdown_page() {
+        let front_matter = json!({
+            "title": "Hello: world",
+            "tags": ["a", "b"],
+            "menu": {"main": {"weight": 2}},
+            "og:title": "Hello",
+        });
+        let page =
+            markdown_page(front_matter.as_object().unwrap(), "Body");
+
+        assert!(page.contains("\"og:title\": \"Hello\"\n"));
+        assert!(page.ends_with("---\n\nBody"));
+        let values = extract(&page);
+        assert_eq!(values["title"], "Hello: world");
+        assert_eq!(values["tags"], "a, b");
+        assert_eq!(values["og:title"], "Hello");
+
+        assert_eq!(
+            markdown_page(json!({}).as_object().unwrap(), "Body"),
+            "Body"
+        );
+    }
+
+    #[test]
+    fn test_normalize_pages() {
+        let pages = normalize_pages(vec![
+            file("about.md", "---\ntitle: About\n---\n\nBody"),
+            file("posts/_defaults.yml", "layout: post\n"),
+            file(
+                "products.json",
+                r#"{"frontmatter": {"title": "Shop"}, "content": "Shop"}"#,
+            ),
+        ])
+        .unwrap();
+
+        assert_eq!(pages[0].content, "---\ntitle: About\n---\n\nBody");
+        assert_eq!(pages[1].content, "layout: post\n");
+        assert_eq!(
+            pages[2].content,
+            "---\ntitle: \"Shop\"\n---\n\nShop"
+        );
+
+        let error =
+            normalize_pages(vec![file("broken.yml", "- a\n- b\n")])
+                .unwrap_err();
+        assert_eq!(error, "Error parsing broken.yml: not an object");
+    }
+
+    #[test]
+    fn test_html_pages() {
+        assert!(is_html_page("landing.html"));
+        assert!(is_html_page("offers/spring.htm"));
+        assert!(!is_html_page("landing.md"));
+
+        let body =
+            "<div class=\"hero\">\n  <h1>*Not* Markdown</h1>\n</div>\n";
+        assert_eq!(
+            html_body(&format!("---\ntitle: Landing\n---\n{}", body)),
+            body
+        );
+        assert_eq!(
+            html_body(&format!(
+                "+++\ntitle = \"Landing\"\n+++\n{}",
+                body
+            )),
+            body
+        );
+        assert_eq!(html_body(body), body);
+    }
+
+    #[test]
+    fn test_renders_layout() {
+        let metadata = |render: &str| {
+            HashMap::from([("render".to_string(), render.to_string())])
+        };
+
+        assert!(renders_layout(&HashMap::new()));
+        assert!(renders_layout(&metadata("true")));
+        assert!(!renders_layout(&metadata("false")));
+        assert!(!renders_layout(&metadata(" False ")));
+    }
+}
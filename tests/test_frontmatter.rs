@@ -5,7 +5,7 @@ mod tests {
     use ssg::modules::frontmatter::{
         extract, extract_front_matter_str, extract_json_object_str,
         parse_json_object, parse_toml_table, parse_yaml_document,
-        parse_yaml_hash,
+        parse_yaml_hash, parse_yaml_values, split_list,
     };
     use yaml_rust::YamlLoader;
 
@@ -220,4 +220,48 @@ mod tests {
     .collect();
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_parse_yaml_values_keeps_scalars_and_lists() {
+        let yaml_str = r#"
+            title: Hello
+            weight: 10
+            draft: true
+            ratio: 1.5
+            menu: [main, footer]
+        "#;
+        let docs = YamlLoader::load_from_str(yaml_str).unwrap();
+        let yaml_hash = docs[0].as_hash().unwrap();
+        let result = parse_yaml_values(yaml_hash);
+        assert_eq!(result["title"], "Hello");
+        assert_eq!(result["weight"], "10");
+        assert_eq!(result["draft"], "true");
+        assert_eq!(result["ratio"], "1.5");
+        assert_eq!(result["menu"], "main, footer");
+    }
+
+    #[test]
+    fn test_extract_keeps_typed_yaml_values() {
+        let content = "---\ntitle: Hello\nweight: 5\n---\nBody";
+        let result = extract(content);
+        assert_eq!(result["weight"], "5");
+    }
+
+    #[test]
+    fn test_parse_toml_table_keeps_scalars_and_arrays() {
+        let toml: toml::Value =
+            toml::from_str("weight = 3\ndraft = false\nmenu = [\"main\"]")
+                .unwrap();
+        let result = parse_toml_table(toml.as_table().unwrap());
+        assert_eq!(result["weight"], "3");
+        assert_eq!(result["draft"], "false");
+        assert_eq!(result["menu"], "main");
+    }
+
+    #[test]
+    fn test_split_list() {
+        assert_eq!(split_list("a, b,,c "), vec!["a", "b", "c"]);
+        assert_eq!(split_list(r#"["a", "b"]"#), vec!["a", "b"]);
+        assert!(split_list("").is_empty());
+    }
 }
@@ -0,0 +1,134 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::page;
+    use serde_json::{json, Value};
+    use ssg::models::data::{PageGenerator, SiteConfig};
+    use ssg::modules::frontmatter::extract;
+    use ssg::modules::generators::{
+        generate_data_files, generator_records, lookup, record_data,
+    };
+
+    fn data() -> Value {
+        json!({
+            "shop": {
+                "products": [
+                    {"id": "W-1", "name": "Widget", "summary": "A widget.",
+                        "tags": ["tools", "small"], "body": "# Specs"},
+                    {"id": "G-2", "name": "Gadget", "price": 12.5},
+                ]
+            }
+        })
+    }
+
+    fn config() -> SiteConfig {
+        let mut config = SiteConfig::default();
+        let _ = config.generators.insert(
+            "products".to_string(),
+            PageGenerator {
+                data: "shop.products".to_string(),
+                path: "products/:id".to_string(),
+                title: "name".to_string(),
+                description: "summary".to_string(),
+                content: "body".to_string(),
+                fields: [("tags".to_string(), "tags".to_string())]
+                    .into_iter()
+                    .collect(),
+                ..Default::default()
+            },
+        );
+        config
+    }
+
+    #[test]
+    fn test_lookup() {
+        let data = data();
+
+        assert_eq!(
+            lookup(&data, "shop.products.1.name"),
+            Some(&json!("Gadget"))
+        );
+        assert_eq!(lookup(&data, "shop.missing"), None);
+    }
+
+    #[test]
+    fn test_generator_records() {
+        let data = data();
+        let config = config();
+        let records = generator_records(
+            "products",
+            &config.generators["products"],
+            &data,
+        )
+        .unwrap();
+
+        let names: Vec<&str> =
+            records.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["products/w-1.md", "products/g-2.md"]);
+        assert_eq!(records[1].1["price"], 12.5);
+
+        let generator = PageGenerator {
+            data: "shop.products".to_string(),
+            title: "name".to_string(),
+            ..Default::default()
+        };
+        let records =
+            generator_records("items", &generator, &data).unwrap();
+        assert_eq!(records[0].0, "items/widget.md");
+    }
+
+    #[test]
+    fn test_generator_records_errors() {
+        let data = data();
+        let generator = PageGenerator {
+            data: "shop.products".to_string(),
+            path: "products/:summary".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            generator_records("products", &generator, &data).unwrap_err(),
+            "Record 2 of the `products` page generator has no `summary` field"
+        );
+
+        let generator = PageGenerator {
+            data: "missing".to_string(),
+            ..Default::default()
+        };
+        assert!(
+            generator_records("products", &generator, &data).is_err()
+        );
+    }
+
+    #[test]
+    fn test_generate_data_files() {
+        let files = vec![
+            page("index.md", "title: Home\nbanner: home.webp"),
+            page("products/index.md", "title: Shop\nbanner: shop.webp"),
+            page("products/g-2.md", "title: My gadget"),
+        ];
+        let generated =
+            generate_data_files(&files, &config(), &data()).unwrap();
+
+        assert_eq!(generated.len(), 1);
+        assert_eq!(generated[0].name, "products/w-1.md");
+        let front_matter = extract(&generated[0].content);
+        assert_eq!(front_matter["title"], "Widget");
+        assert_eq!(front_matter["description"], "A widget.");
+        assert_eq!(front_matter["layout"], "page");
+        assert_eq!(front_matter["tags"], "tools, small");
+        assert_eq!(front_matter["banner"], "shop.webp");
+        assert!(generated[0].content.ends_with("\n\n# Specs\n"));
+    }
+
+    #[test]
+    fn test_record_data() {
+        let records = record_data(&config(), &data()).unwrap();
+
+        assert_eq!(records["products/w-1.md"]["name"], "Widget");
+        assert_eq!(records.len(), 2);
+        assert!(record_data(&SiteConfig::default(), &data())
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,197 @@
+#[cfg(test)]
+mod tests {
+    use ssg::models::data::{FileData, GitConfig, GitInfo};
+    use ssg::modules::git::{
+        apply_git_info, collect_git_info, directory_history, edit_url,
+        file_history, parse_git_history, parse_git_log,
+        repository_prefix,
+    };
+    use std::{
+        collections::{HashMap, HashSet},
+        fs,
+        path::Path,
+        process::Command,
+    };
+
+    fn git(dir: &Path, args: &[&str], date: &str, author: &str) {
+        let status = Command::new("git")
+            .current_dir(dir)
+            .args(["-c", "commit.gpgsign=false"])
+            .args(args)
+            .env("GIT_AUTHOR_NAME", author)
+            .env("GIT_AUTHOR_EMAIL", "author@example.com")
+            .env("GIT_AUTHOR_DATE", date)
+            .env("GIT_COMMITTER_NAME", author)
+            .env("GIT_COMMITTER_EMAIL", "author@example.com")
+            .env("GIT_COMMITTER_DATE", date)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    fn info() -> GitInfo {
+        GitInfo {
+            created: "2023-01-02T10:00:00+00:00".to_string(),
+            modified: "2024-03-04T12:00:00+01:00".to_string(),
+            contributors: vec!["Ada".to_string(), "Grace".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_parse_git_log() {
+        let output = "2024-03-04T12:00:00+01:00\tAda\n2023-06-01T09:00:00+00:00\tGrace\n2023-01-02T10:00:00+00:00\tAda\n";
+
+        assert_eq!(
+            parse_git_log(output),
+            Some(GitInfo {
+                created: "2023-01-02T10:00:00+00:00".to_string(),
+                modified: "2024-03-04T12:00:00+01:00".to_string(),
+                contributors: vec![
+                    "Ada".to_string(),
+                    "Grace".to_string()
+                ],
+            })
+        );
+        assert_eq!(parse_git_log(""), None);
+    }
+
+    #[test]
+    fn test_parse_git_history() {
+        let output = "\u{1}2024-03-04T12:00:00+01:00\tGrace\nM\tguides/install.md\n\n\u{1}2023-06-01T09:00:00+00:00\tAda\nR100\tinstall.md\tguides/install.md\nA\tabout.md\n\n\u{1}2023-01-02T10:00:00+00:00\tAda\nA\tinstall.md\n";
+
+        let history = parse_git_history(output);
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(
+            history["guides/install.md"].created,
+            "2023-01-02T10:00:00+00:00"
+        );
+        assert_eq!(
+            history["guides/install.md"].contributors,
+            vec!["Ada", "Grace"]
+        );
+        assert_eq!(
+            history["about.md"].modified,
+            "2023-06-01T09:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_apply_git_info_keeps_front_matter() {
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "last_build_date".to_string(),
+            "July 12, 2023".to_string(),
+        );
+        metadata.insert("pub_date".to_string(), "".to_string());
+        metadata
+            .insert("lastmod".to_string(), "2020-01-01".to_string());
+        let page_fields: HashSet<String> =
+            HashSet::from(["last_build_date".to_string()]);
+        let config = GitConfig {
+            dates: true,
+            contributors: true,
+            ..Default::default()
+        };
+
+        apply_git_info(&mut metadata, &page_fields, &info(), &config);
+
+        // The page's own value is kept, the default of `lastmod` is not
+        assert_eq!(metadata["last_build_date"], "July 12, 2023");
+        assert_eq!(metadata["pub_date"], "2023-01-02T10:00:00+00:00");
+        assert_eq!(metadata["lastmod"], "2024-03-04T12:00:00+01:00");
+        assert_eq!(metadata["contributors"], "Ada, Grace");
+    }
+
+    #[test]
+    fn test_apply_git_info_respects_settings() {
+        let mut metadata = HashMap::new();
+
+        apply_git_info(
+            &mut metadata,
+            &HashSet::new(),
+            &info(),
+            &GitConfig::default(),
+        );
+
+        assert!(metadata.is_empty());
+    }
+
+    #[test]
+    fn test_edit_url() {
+        assert_eq!(
+            edit_url(
+                "https://git.example.com/site/-/edit/main/{path}",
+                "docs/a.md"
+            ),
+            "https://git.example.com/site/-/edit/main/docs/a.md"
+        );
+    }
+
+    #[test]
+    fn test_file_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = dir.path().join("content");
+        fs::create_dir_all(&content).unwrap();
+        git(dir.path(), &["init", "-q"], "2023-01-02T10:00:00Z", "Ada");
+
+        fs::write(content.join("about.md"), "one").unwrap();
+        git(dir.path(), &["add", "."], "2023-01-02T10:00:00Z", "Ada");
+        git(
+            dir.path(),
+            &["commit", "-q", "-m", "Add about"],
+            "2023-01-02T10:00:00+00:00",
+            "Ada",
+        );
+        fs::write(content.join("about.md"), "two").unwrap();
+        git(
+            dir.path(),
+            &["commit", "-q", "-am", "Edit about"],
+            "2024-03-04T12:00:00+01:00",
+            "Grace",
+        );
+        fs::write(content.join("untracked.md"), "three").unwrap();
+
+        let history = file_history(&content.join("about.md")).unwrap();
+        assert_eq!(history.created, "2023-01-02T10:00:00+00:00");
+        assert_eq!(history.modified, "2024-03-04T12:00:00+01:00");
+        assert_eq!(history.contributors, vec!["Ada", "Grace"]);
+        assert_eq!(
+            repository_prefix(&content).as_deref(),
+            Some("content/")
+        );
+        assert_eq!(repository_prefix(dir.path()).as_deref(), Some(""));
+        assert_eq!(file_history(&content.join("untracked.md")), None);
+
+        let files = vec![
+            FileData {
+                name: "about.md".to_string(),
+                ..Default::default()
+            },
+            FileData {
+                name: "untracked.md".to_string(),
+                ..Default::default()
+            },
+        ];
+        let config = GitConfig {
+            dates: true,
+            ..Default::default()
+        };
+        let infos = collect_git_info(&content, &files, &config);
+        assert_eq!(infos.len(), 1);
+        assert_eq!(infos["about.md"], history);
+        assert_eq!(
+            directory_history(&content)
+                .unwrap()
+                .keys()
+                .collect::<Vec<_>>(),
+            vec!["about.md"]
+        );
+        assert!(collect_git_info(
+            &content,
+            &files,
+            &GitConfig::default()
+        )
+        .is_empty());
+    }
+}
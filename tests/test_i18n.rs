@@ -0,0 +1,291 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::page;
+    use serde_json::json;
+    use ssg::models::data::{
+        FileData, LanguageConfig, SiteConfig, SiteMapData, Translation,
+    };
+    use ssg::modules::i18n::{
+        default_language, generate_hreflang_links,
+        generate_translations, interface_strings, language_feeds,
+        language_menus, language_pages, language_urls,
+        resolve_localized_routes, split_language,
+    };
+    use ssg::modules::json::sitemap_from_urls;
+
+    fn language(name: &str, weight: i64) -> LanguageConfig {
+        LanguageConfig {
+            name: name.to_string(),
+            weight,
+            ..Default::default()
+        }
+    }
+
+    fn config() -> SiteConfig {
+        let mut config = SiteConfig::default();
+        let mut en = language("English", 1);
+        en.strings
+            .insert("read_more".to_string(), "Read more".to_string());
+        en.strings
+            .insert("search".to_string(), "Search".to_string());
+        let mut fr = language("Français", 2);
+        fr.strings.insert(
+            "read_more".to_string(),
+            "Lire la suite".to_string(),
+        );
+        config.languages.insert("en".to_string(), en);
+        config.languages.insert("fr".to_string(), fr);
+        config
+    }
+
+    fn files() -> Vec<FileData> {
+        vec![
+            page("index.md", "title: Page"),
+            page("about.md", "title: Page"),
+            page("about.fr.md", "title: Page"),
+            page("fr/index.md", "title: Page"),
+            page("fr/contact.md", "title: Page"),
+        ]
+    }
+
+    #[test]
+    fn test_default_language() {
+        assert_eq!(default_language(&SiteConfig::default()), "");
+        assert_eq!(default_language(&config()), "en");
+
+        let mut config = config();
+        config.default_language = "fr".to_string();
+        assert_eq!(default_language(&config), "fr");
+    }
+
+    #[test]
+    fn test_split_language() {
+        let config = config();
+
+        assert_eq!(
+            split_language("about.fr.md", &config),
+            ("fr".to_string(), "about.md".to_string())
+        );
+        assert_eq!(
+            split_language("fr/docs/intro.md", &config),
+            ("fr".to_string(), "docs/intro.md".to_string())
+        );
+        assert_eq!(
+            split_language("docs/intro.de.md", &config),
+            ("en".to_string(), "docs/intro.de.md".to_string())
+        );
+        assert_eq!(
+            split_language("about.md", &config),
+            ("en".to_string(), "about.md".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_localized_routes() {
+        let routes =
+            resolve_localized_routes(&files(), &config()).unwrap();
+
+        assert_eq!(routes.path("index.md"), "");
+        assert_eq!(routes.path("about.md"), "about");
+        assert_eq!(routes.path("about.fr.md"), "fr/about");
+        assert_eq!(routes.path("fr/index.md"), "fr");
+        assert_eq!(
+            routes.url("fr/contact.md"),
+            "/fr/contact/index.html"
+        );
+    }
+
+    #[test]
+    fn test_resolve_localized_routes_detects_collisions() {
+        let files = vec![
+            page("about.fr.md", "title: Page"),
+            page("fr/about.md", "title: Page"),
+        ];
+
+        assert!(resolve_localized_routes(&files, &config()).is_err());
+    }
+
+    #[test]
+    fn test_generate_translations() {
+        let files = files();
+        let config = config();
+        let routes = resolve_localized_routes(&files, &config).unwrap();
+
+        let translations =
+            generate_translations(&files, &routes, &config);
+
+        assert_eq!(
+            translations["about.fr.md"],
+            vec![
+                Translation {
+                    language: "en".to_string(),
+                    name: "English".to_string(),
+                    url: "/about/index.html".to_string(),
+                    active: false,
+                },
+                Translation {
+                    language: "fr".to_string(),
+                    name: "Français".to_string(),
+                    url: "/fr/about/index.html".to_string(),
+                    active: true,
+                },
+            ]
+        );
+        assert_eq!(translations["fr/index.md"].len(), 2);
+        assert_eq!(translations["fr/contact.md"].len(), 1);
+    }
+
+    #[test]
+    fn test_generate_translations_without_languages() {
+        let files = files();
+        let config = SiteConfig::default();
+        let routes = resolve_localized_routes(&files, &config).unwrap();
+
+        assert!(
+            generate_translations(&files, &routes, &config).is_empty()
+        );
+    }
+
+    #[test]
+    fn test_generate_hreflang_links() {
+        let files = files();
+        let config = config();
+        let routes = resolve_localized_routes(&files, &config).unwrap();
+        let translations =
+            generate_translations(&files, &routes, &config);
+
+        let links = generate_hreflang_links(
+            &translations["about.md"],
+            "https://example.com/",
+            "en",
+        );
+
+        assert_eq!(
+            links,
+            "<link rel=\"alternate\" hreflang=\"en\" href=\"https://example.com/about/index.html\">\n<link rel=\"alternate\" hreflang=\"fr\" href=\"https://example.com/fr/about/index.html\">\n<link rel=\"alternate\" hreflang=\"x-default\" href=\"https://example.com/about/index.html\">"
+        );
+        assert_eq!(
+            generate_hreflang_links(
+                &translations["fr/contact.md"],
+                "",
+                "en"
+            ),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_interface_strings_fall_back_to_default_language() {
+        let strings = interface_strings(&config(), "fr");
+
+        assert_eq!(strings["read_more"], "Lire la suite");
+        assert_eq!(strings["search"], "Search");
+    }
+
+    #[test]
+    fn test_language_urls_and_sitemap() {
+        let files = files();
+        let config = config();
+        let routes = resolve_localized_routes(&files, &config).unwrap();
+
+        let urls = language_urls(&files, &routes, &config);
+        let sitemap = sitemap_from_urls(
+            &SiteMapData::new(
+                "https://example.com".to_string(),
+                "2024-01-01".to_string(),
+                "weekly".to_string(),
+            ),
+            &urls["fr"]
+                .iter()
+                .map(|url| (url.clone(), String::new()))
+                .collect::<Vec<_>>(),
+        );
+
+        assert_eq!(urls["fr"].len(), 3);
+        assert!(sitemap.contains(
+            "<loc>https://example.com/fr/about/index.html</loc>"
+        ));
+        assert!(!sitemap.contains(
+            "<loc>https://example.com/about/index.html</loc>"
+        ));
+    }
+
+    #[test]
+    fn test_language_feeds() {
+        let entry = |name: &str, date: &str| {
+            json!({
+                "name": name,
+                "title": name,
+                "date": date,
+                "url": format!("/{}", name),
+            })
+        };
+        let entries = vec![
+            entry("fr/index.md", "2024-01-01"),
+            entry("fr/old.md", "2023-01-01"),
+            entry("fr/new.md", "2024-02-01"),
+            entry("about.md", "2024-03-01"),
+            json!({ "name": "fr/undated.md", "url": "/fr/undated" }),
+        ];
+
+        let feeds = language_feeds(&entries, &config(), 0);
+
+        let titles = |language: &str| -> Vec<String> {
+            feeds[language]
+                .iter()
+                .map(|item| item.item_title.clone())
+                .collect()
+        };
+        assert_eq!(titles("fr"), vec!["fr/new.md", "fr/old.md"]);
+        assert_eq!(titles("en"), vec!["about.md"]);
+        assert_eq!(
+            feeds["fr"][0].item_pub_date,
+            "Thu, 01 Feb 2024 00:00:00 +0000"
+        );
+        assert!(language_feeds(&entries, &SiteConfig::default(), 0)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_language_pages_without_languages() {
+        let entries = vec![
+            json!({ "name": "index.md", "date": "2024-04-01" }),
+            json!({ "name": "old.md", "date": "2023-01-01" }),
+            json!({ "name": "new.md", "date": "2024-02-01" }),
+            json!({ "name": "undated.md" }),
+        ];
+
+        let pages = language_pages(&entries, &SiteConfig::default());
+
+        let names: Vec<&str> = pages[""]
+            .iter()
+            .map(|entry| entry["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["new.md", "old.md"]);
+    }
+
+    #[test]
+    fn test_language_menus() {
+        let files = files();
+        let config = config();
+        let routes = resolve_localized_routes(&files, &config).unwrap();
+
+        let menus = language_menus(&files, &routes, &config).unwrap();
+
+        let urls = |language: &str| -> Vec<String> {
+            menus[language]["main"]
+                .iter()
+                .map(|item| item.url.clone())
+                .collect()
+        };
+        assert!(
+            urls("fr").contains(&"/fr/about/index.html".to_string())
+        );
+        assert!(
+            urls("fr").contains(&"/fr/contact/index.html".to_string())
+        );
+        assert!(urls("en").iter().all(|url| !url.starts_with("/fr/")));
+    }
+}
@@ -3,8 +3,8 @@ mod tests {
     // Import necessary dependencies
     use serde_json::{json, Value};
     use ssg::{
-        models::data::{CnameData, ManifestData, TxtData},
-        modules::json::{cname, manifest, txt},
+        models::data::{CnameData, ManifestData, SiteMapData, TxtData},
+        modules::json::{cname, manifest, sitemap_from_urls, txt},
     };
 
     #[test]
@@ -104,4 +104,29 @@ mod tests {
         let result = txt(&txt_options);
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_sitemap_from_urls() {
+        let options = SiteMapData::new(
+            "https://example.com/".to_string(),
+            "2024-01-01T00:00:00+00:00".to_string(),
+            "weekly".to_string(),
+        );
+        let urls = vec![
+            (
+                "/a&b/index.html".to_string(),
+                "2023-05-06T07:08:09+00:00".to_string(),
+            ),
+            ("/index.html".to_string(), String::new()),
+        ];
+
+        let sitemap = sitemap_from_urls(&options, &urls);
+
+        assert!(sitemap.contains(
+            "<lastmod>2023-05-06T07:08:09+00:00</lastmod><loc>https://example.com/a&amp;b/index.html</loc>"
+        ));
+        assert!(sitemap.contains(
+            "<lastmod>2024-01-01T00:00:00+00:00</lastmod><loc>https://example.com/index.html</loc>"
+        ));
+    }
 }
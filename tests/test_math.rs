@@ -0,0 +1,200 @@
+#[cfg(test)]
+mod tests {
+    use ssg::modules::html::generate_html;
+    use ssg::modules::math::{
+        ascii_math, extract_math, latex_to_mathml, latex_to_text,
+        math_to_text, replace_math, restore_math,
+    };
+    use ssg::modules::plaintext::generate_plain_text;
+
+    /// Returns the presentation MathML of a formula.
+    fn mathml(tex: &str, display: bool) -> String {
+        let mathml = latex_to_mathml(tex, display);
+        let start = mathml.find("<semantics><mrow>").unwrap() + 17;
+        let end = mathml.find("</mrow><annotation").unwrap();
+        mathml[start..end].to_string()
+    }
+
+    #[test]
+    fn test_latex_to_mathml() {
+        assert_eq!(
+            mathml("x^2 + 1", false),
+            "<msup><mi>x</mi><mn>2</mn></msup><mo>+</mo><mn>1</mn>"
+        );
+        assert_eq!(
+            mathml(r"\sqrt[3]{a_1}", false),
+            "<mroot><msub><mi>a</mi><mn>1</mn></msub><mn>3</mn></mroot>"
+        );
+        assert_eq!(
+            mathml(r"\sum_{i=1}^n i", true),
+            "<munderover><mo>∑</mo><mrow><mi>i</mi><mo>=</mo>\
+             <mn>1</mn></mrow><mi>n</mi></munderover><mi>i</mi>"
+        );
+        assert_eq!(
+            mathml(r"\sum_{i=1}^n i", false),
+            "<msubsup><mo>∑</mo><mrow><mi>i</mi><mo>=</mo>\
+             <mn>1</mn></mrow><mi>n</mi></msubsup><mi>i</mi>"
+        );
+        assert_eq!(
+            mathml(r"\left( \frac{a}{b} \right)", false),
+            "<mrow><mo fence=\"true\">(</mo><mfrac><mi>a</mi>\
+             <mi>b</mi></mfrac><mo fence=\"true\">)</mo></mrow>"
+        );
+        assert_eq!(
+            mathml(r"\mathbb{R} \text{ if } x < y", false),
+            "<mi mathvariant=\"double-struck\">R</mi>\
+             <mtext> if </mtext><mi>x</mi><mo>&lt;</mo><mi>y</mi>"
+        );
+        assert_eq!(
+            mathml(r"\begin{pmatrix} 1 & 0 \\ 0 & 1 \end{pmatrix}", true),
+            "<mrow><mo fence=\"true\">(</mo><mtable><mtr><mtd><mn>1</mn>\
+             </mtd><mtd><mn>0</mn></mtd></mtr><mtr><mtd><mn>0</mn></mtd>\
+             <mtd><mn>1</mn></mtd></mtr></mtable>\
+             <mo fence=\"true\">)</mo></mrow>"
+        );
+        assert_eq!(
+            mathml(r"\foo", false),
+            "<merror><mtext>\\foo</mtext></merror>"
+        );
+
+        let formula = latex_to_mathml("a<b", true);
+        assert!(formula.contains("display=\"block\""));
+        assert!(formula.ends_with(
+            "<annotation encoding=\"application/x-tex\">a&lt;b\
+             </annotation></semantics></math>"
+        ));
+    }
+
+    #[test]
+    fn test_latex_to_text() {
+        assert_eq!(latex_to_text("E = mc^2"), "E = mc²");
+        assert_eq!(latex_to_text(r"x_{n+1} - 1"), "xₙ₊₁ − 1");
+        assert_eq!(latex_to_text("-x^{-1}"), "−x⁻¹");
+        assert_eq!(latex_to_text(r"a^{b+c}"), "a^(b + c)");
+        assert_eq!(
+            latex_to_text(r"\frac{-b \pm \sqrt{b^2-4ac}}{2a}"),
+            "(−b ± √(b² − 4ac))/2a"
+        );
+        assert_eq!(latex_to_text(r"\sum_{i=1}^n i"), "∑ᵢ₌₁ⁿi");
+        assert_eq!(latex_to_text(r"\sin x \leq 1"), "sin x ≤ 1");
+        assert_eq!(latex_to_text(r"\mathbb{R}^3"), "ℝ³");
+        assert_eq!(
+            latex_to_text(
+                r"|x| = \begin{cases} x & x \geq 0 \\ -x & x < 0 \end{cases}"
+            ),
+            "|x| = { x, x ≥ 0; −x, x < 0"
+        );
+        assert_eq!(
+            latex_to_text(
+                r"\begin{aligned} a &= b \\ &= c \end{aligned}"
+            ),
+            "a = b; = c"
+        );
+    }
+
+    #[test]
+    fn test_replace_math() {
+        let replace = |markdown: &str| {
+            replace_math(markdown, |tex, display| {
+                format!("<{}:{}>", if display { "D" } else { "I" }, tex)
+            })
+        };
+
+        assert_eq!(
+            replace("Let $x$ and $y_1$."),
+            "Let <I:x> and <I:y_1>."
+        );
+        assert_eq!(
+            replace("$$\na + b\n$$\n\nNext"),
+            "<D:a + b>\n\nNext"
+        );
+        assert_eq!(
+            replace("It costs $5 and $10, or $20."),
+            "It costs $5 and $10, or $20."
+        );
+        assert_eq!(replace("Spaced $ x $ here"), "Spaced $ x $ here");
+        assert_eq!(replace(r"Escaped \$x$ here"), r"Escaped \$x$ here");
+        assert_eq!(
+            replace("Code `$x$` and ``a ` $y$``, then $z$"),
+            "Code `$x$` and ``a ` $y$``, then <I:z>"
+        );
+        assert_eq!(
+            replace("```\n$x$\n```\n$y$\n~~~tex\n$$z$$\n~~~\n"),
+            "```\n$x$\n```\n<I:y>\n~~~tex\n$$z$$\n~~~\n"
+        );
+        assert_eq!(replace("Ünïcode $α$ ok"), "Ünïcode <I:α> ok");
+        assert_eq!(
+            replace("Shell:\n\n    echo $x$\n\n\techo $y$\n\n$z$"),
+            "Shell:\n\n    echo $x$\n\n\techo $y$\n\n<I:z>"
+        );
+        assert_eq!(
+            replace("- item $a$\n\n    more $b$\n\nText\n    $c$"),
+            "- item <I:a>\n\n    more <I:b>\n\nText\n    <I:c>"
+        );
+    }
+
+    #[test]
+    fn test_extract_and_restore_math() {
+        let (markdown, formulas) =
+            extract_math("Inline $x$.\n\n$$y$$\n");
+        assert_eq!(formulas.len(), 2);
+        assert!(!markdown.contains('$'));
+
+        let html = format!(
+            "<p>{}</p>",
+            markdown.trim_end().replace("\n\n", "</p>\n<p>")
+        );
+        let html = restore_math(&html, &formulas);
+        assert_eq!(
+            html,
+            format!("<p>Inline {}.</p>\n{}", formulas[0], formulas[1])
+        );
+        assert_eq!(restore_math("<p>a</p>", &[]), "<p>a</p>");
+    }
+
+    #[test]
+    fn test_generate_html_with_math() {
+        let html = generate_html(
+            "## Area of $\\pi r^2$\n\n$$\n\\int_0^1 x\\,dx\n$$\n\n\
+             `$code$`",
+            "Title",
+            "Description",
+            None,
+        )
+        .unwrap();
+
+        assert!(html.contains("<math xmlns"));
+        assert!(html.contains("<msup><mi>r</mi><mn>2</mn></msup>"));
+        assert!(html.contains("display=\"block\""));
+        assert!(html.contains("<code>$code$</code>"));
+        assert!(
+            !html.contains('\u{E000}') && !html.contains('\u{E001}')
+        );
+    }
+
+    #[test]
+    fn test_math_text_fallbacks() {
+        assert_eq!(
+            math_to_text("Euler: $e^{i\\pi} + 1 = 0$"),
+            "Euler: e^(iπ) + 1 = 0"
+        );
+
+        let (plain_text, ..) = generate_plain_text(
+            "The mean is $\\frac{a_1 + a_2}{2}$.",
+            "Title",
+            "Description",
+            "Author",
+            "Creator",
+            "Keywords",
+        )
+        .unwrap();
+        assert_eq!(plain_text, "The mean is (a₁ + a₂)/2.");
+
+        assert_eq!(
+            ascii_math("(a₁ + a₂)/2 ≤ β²"),
+            "(a_1 + a_2)/2 <= beta²"
+        );
+        assert_eq!(ascii_math("x − ∞ ∈ ℝ"), "x - inf in R");
+        assert_eq!(ascii_math("√(b² − 4ac)"), "sqrt(b² - 4ac)");
+    }
+}
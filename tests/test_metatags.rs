@@ -1,7 +1,10 @@
 #[cfg(test)]
 mod tests {
     use regex::Regex;
-    use ssg::modules::metatags::generate_metatags;
+    use ssg::models::data::PageLink;
+    use ssg::modules::metatags::{
+        generate_breadcrumb_json_ld, generate_metatags,
+    };
 
     // Test general functionality of the generate_metatags function
     #[test]
@@ -254,4 +257,52 @@ mod tests {
 
         assert_eq!(result, expected_output, "Generated meta tags should handle whitespace appropriately");
     }
+
+    #[test]
+    fn test_generate_breadcrumb_json_ld() {
+        let breadcrumbs = vec![
+            PageLink::new("Home".to_string(), "/index.html".to_string()),
+            PageLink::new("Guides".to_string(), String::new()),
+            PageLink::new(
+                "Install".to_string(),
+                "/guides/install/index.html".to_string(),
+            ),
+        ];
+        let result = generate_breadcrumb_json_ld(
+            &breadcrumbs,
+            "https://example.com/",
+        );
+
+        assert!(result.starts_with("<script type=\"application/ld+json\">"));
+        let json: serde_json::Value = serde_json::from_str(
+            result
+                .trim_start_matches("<script type=\"application/ld+json\">")
+                .trim_end_matches("</script>"),
+        )
+        .unwrap();
+        assert_eq!(json["@type"], "BreadcrumbList");
+        let items = json["itemListElement"].as_array().unwrap();
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0]["item"], "https://example.com/index.html");
+        assert!(items[1].get("item").is_none());
+        assert_eq!(items[2]["position"], 3);
+        assert_eq!(items[2]["name"], "Install");
+    }
+
+    #[test]
+    fn test_generate_breadcrumb_json_ld_escapes_closing_tags() {
+        let breadcrumbs = vec![PageLink::new(
+            "</script><script>alert(1)</script>".to_string(),
+            String::new(),
+        )];
+        let result = generate_breadcrumb_json_ld(&breadcrumbs, "");
+
+        assert_eq!(result.matches("</").count(), 1);
+        assert!(result.contains("<\\/script><script>alert(1)<\\/script>"));
+    }
+
+    #[test]
+    fn test_generate_breadcrumb_json_ld_empty() {
+        assert_eq!(generate_breadcrumb_json_ld(&[], "https://a.b"), "");
+    }
 }
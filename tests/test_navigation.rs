@@ -1,7 +1,12 @@
+mod common;
+
 #[cfg(test)]
 mod tests {
-    use ssg::models::data::FileData;
+    use crate::common::page;
+    use ssg::models::data::{FileData, MenuItem, PageLink};
     use ssg::modules::navigation::NavigationGenerator;
+    use ssg::modules::permalinks::Routes;
+    use std::collections::BTreeMap;
 
     #[test]
     fn test_generate_navigation_empty_input() {
@@ -136,4 +141,320 @@ mod tests {
             "Navigation is not empty for file with no extension"
         );
     }
+
+    #[test]
+    fn test_page_url() {
+        assert_eq!(
+            NavigationGenerator::page_url("index.md"),
+            "/index.html"
+        );
+        assert_eq!(
+            NavigationGenerator::page_url("about.md"),
+            "/about/index.html"
+        );
+    }
+
+    #[test]
+    fn test_generate_menus_from_front_matter() {
+        let files = vec![
+            page("about.md", "title: About us\nmenu: main\nweight: 20"),
+            page(
+                "blog.md",
+                "title: Blog\nnav_title: Articles\nmenu: [main, footer]\nweight: 10",
+            ),
+            page("privacy.md", "title: Privacy\nmenu: footer"),
+            page("hidden.md", "title: Hidden"),
+        ];
+
+        let menus = NavigationGenerator::generate_menus(
+            &files,
+            &BTreeMap::new(),
+            &Routes::new(),
+        )
+        .unwrap();
+
+        let main: Vec<&str> =
+            menus["main"].iter().map(|i| i.title.as_str()).collect();
+        assert_eq!(main, vec!["Articles", "About us"]);
+        assert_eq!(menus["main"][0].url, "/blog/index.html");
+        assert_eq!(menus["main"][1].weight, 20);
+
+        let footer: Vec<&str> =
+            menus["footer"].iter().map(|i| i.title.as_str()).collect();
+        assert_eq!(footer, vec!["Privacy", "Articles"]);
+    }
+
+    #[test]
+    fn test_generate_menus_nests_children_under_parent() {
+        let files = vec![
+            page("guides.md", "title: Guides\nmenu: main"),
+            page(
+                "install.md",
+                "title: Install\nmenu: main\nparent: guides\nweight: 1",
+            ),
+            page(
+                "orphan.md",
+                "title: Orphan\nmenu: main\nparent: missing",
+            ),
+        ];
+
+        let menus = NavigationGenerator::generate_menus(
+            &files,
+            &BTreeMap::new(),
+            &Routes::new(),
+        )
+        .unwrap();
+        let main = &menus["main"];
+
+        assert_eq!(main.len(), 2);
+        assert_eq!(main[0].title, "Guides");
+        assert_eq!(main[0].children.len(), 1);
+        assert_eq!(main[0].children[0].title, "Install");
+        assert_eq!(main[1].title, "Orphan");
+    }
+
+    #[test]
+    fn test_generate_menus_merges_configured_entries() {
+        let mut configured = BTreeMap::new();
+        configured.insert(
+            "main".to_string(),
+            vec![MenuItem {
+                title: "GitHub".to_string(),
+                url: "https://github.com/".to_string(),
+                weight: 100,
+                ..Default::default()
+            }],
+        );
+        let files = vec![page("about.md", "title: About\nmenu: main")];
+
+        let menus = NavigationGenerator::generate_menus(
+            &files,
+            &configured,
+            &Routes::new(),
+        )
+        .unwrap();
+        let main = &menus["main"];
+
+        assert_eq!(main.len(), 2);
+        assert_eq!(main[0].title, "About");
+        assert_eq!(main[1].identifier, "github");
+    }
+
+    #[test]
+    fn test_mark_active_sets_trail() {
+        let files = vec![
+            page("guides.md", "title: Guides\nmenu: main"),
+            page(
+                "install.md",
+                "title: Install\nmenu: main\nparent: guides",
+            ),
+            page("about.md", "title: About\nmenu: main"),
+        ];
+        let menus = NavigationGenerator::generate_menus(
+            &files,
+            &BTreeMap::new(),
+            &Routes::new(),
+        )
+        .unwrap();
+
+        let marked = NavigationGenerator::mark_active(
+            &menus,
+            "/install/index.html",
+        );
+        let main = &marked["main"];
+
+        assert!(!main[0].active);
+        assert!(!main[0].active_trail);
+        assert!(!main[1].active);
+        assert!(main[1].active_trail);
+        assert!(main[1].children[0].active);
+    }
+
+    #[test]
+    fn test_page_path() {
+        assert_eq!(NavigationGenerator::page_path("index.md"), "");
+        assert_eq!(NavigationGenerator::page_path("about.md"), "about");
+        assert_eq!(
+            NavigationGenerator::page_path("guides/index.md"),
+            "guides"
+        );
+        assert_eq!(
+            NavigationGenerator::page_path("guides/install.md"),
+            "guides/install"
+        );
+        assert_eq!(
+            NavigationGenerator::page_path("guides/_index.md"),
+            "guides"
+        );
+        assert_eq!(NavigationGenerator::page_path("_index.md"), "");
+        assert!(NavigationGenerator::is_index_page("guides/_index.md"));
+        assert!(!NavigationGenerator::is_index_page(
+            "guides/indexes.md"
+        ));
+    }
+
+    #[test]
+    fn test_generate_breadcrumbs() {
+        let files = vec![
+            page("index.md", "title: Welcome\nnav_title: Start"),
+            page("guides/index.md", "title: All Guides"),
+            page("guides/setup/linux.md", "title: Linux"),
+        ];
+
+        let breadcrumbs = NavigationGenerator::generate_breadcrumbs(
+            &files,
+            &Routes::new(),
+        );
+
+        assert_eq!(
+            breadcrumbs["guides/setup/linux.md"],
+            vec![
+                PageLink::new(
+                    "Start".to_string(),
+                    "/index.html".to_string()
+                ),
+                PageLink::new(
+                    "All Guides".to_string(),
+                    "/guides/index.html".to_string()
+                ),
+                PageLink::new("Setup".to_string(), String::new()),
+                PageLink::new(
+                    "Linux".to_string(),
+                    "/guides/setup/linux/index.html".to_string()
+                ),
+            ]
+        );
+        assert_eq!(breadcrumbs["index.md"].len(), 1);
+        assert_eq!(breadcrumbs["guides/index.md"].len(), 2);
+    }
+
+    #[test]
+    fn test_generate_adjacent_pages() {
+        let files = vec![
+            page("index.md", "title: Home"),
+            page("about.md", "title: About"),
+            page("guides/index.md", "title: Guides"),
+            page("guides/b.md", "title: B\nweight: 1"),
+            page("guides/a.md", "title: A\nweight: 2"),
+            page(
+                "guides/c.md",
+                "title: C\nweight: 2\ndate: 2024-01-01",
+            ),
+            page("posts/one.md", "title: One"),
+        ];
+
+        let adjacent = NavigationGenerator::generate_adjacent_pages(
+            &files,
+            &Routes::new(),
+        );
+
+        let first = &adjacent["guides/b.md"];
+        assert!(first.previous.is_none());
+        assert_eq!(first.next.as_ref().unwrap().title, "C");
+
+        let middle = &adjacent["guides/c.md"];
+        assert_eq!(middle.previous.as_ref().unwrap().title, "B");
+        assert_eq!(middle.next.as_ref().unwrap().title, "A");
+
+        let last = &adjacent["guides/a.md"];
+        assert!(last.next.is_none());
+
+        let alone = &adjacent["posts/one.md"];
+        assert!(alone.previous.is_none() && alone.next.is_none());
+
+        assert!(!adjacent.contains_key("about.md"));
+        assert!(!adjacent.contains_key("guides/index.md"));
+    }
+
+    #[test]
+    fn test_navigation_uses_routes() {
+        let files = vec![
+            page("index.md", "title: Home"),
+            page("posts/hello.md", "title: Hello\nmenu: main"),
+        ];
+        let mut routes = Routes::new();
+        routes.insert("posts/hello.md", "2024/05/hello");
+
+        let menus = NavigationGenerator::generate_menus(
+            &files,
+            &BTreeMap::new(),
+            &routes,
+        )
+        .unwrap();
+        assert_eq!(menus["main"][0].url, "/2024/05/hello/index.html");
+
+        let breadcrumbs =
+            NavigationGenerator::generate_breadcrumbs(&files, &routes);
+        let trail = &breadcrumbs["posts/hello.md"];
+        assert_eq!(
+            trail[1],
+            PageLink::new("Posts".to_string(), String::new())
+        );
+        assert_eq!(trail[2].url, "/2024/05/hello/index.html");
+
+        let navigation =
+            NavigationGenerator::render_navigation(&menus["main"]);
+        assert!(
+            navigation.contains("href=\"/2024/05/hello/index.html\"")
+        );
+        assert!(!navigation.contains("/index/"));
+    }
+
+    #[test]
+    fn test_generate_navigation_keeps_pages_sharing_a_stem() {
+        let files = vec![
+            page("docs/install.md", "title: Install"),
+            page("guides/install.md", "title: Install"),
+        ];
+
+        let navigation =
+            NavigationGenerator::generate_navigation(&files);
+
+        assert!(
+            navigation.contains("href=\"/docs/install/index.html\"")
+        );
+        assert!(
+            navigation.contains("href=\"/guides/install/index.html\"")
+        );
+    }
+
+    #[test]
+    fn test_generate_menus_defaults_main_to_every_page() {
+        let files = vec![
+            page("index.md", "title: Home"),
+            page("about.md", "title: About"),
+            page("privacy.md", "title: Privacy\nmenu: footer"),
+        ];
+
+        let menus = NavigationGenerator::generate_menus(
+            &files,
+            &BTreeMap::new(),
+            &Routes::new(),
+        )
+        .unwrap();
+
+        let main: Vec<&str> =
+            menus["main"].iter().map(|i| i.title.as_str()).collect();
+        assert_eq!(main, vec!["About"]);
+        assert_eq!(menus["footer"][0].title, "Privacy");
+    }
+
+    #[test]
+    fn test_generate_menus_rejects_duplicate_identifiers() {
+        let files = vec![
+            page("a.md", "title: A\nmenu: main\nidentifier: docs"),
+            page("b.md", "title: B\nmenu: main\nidentifier: docs"),
+        ];
+
+        let result = NavigationGenerator::generate_menus(
+            &files,
+            &BTreeMap::new(),
+            &Routes::new(),
+        );
+
+        assert_eq!(
+            result.unwrap_err(),
+            "Duplicate identifier `docs` in the `main` menu"
+        );
+    }
 }
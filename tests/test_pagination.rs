@@ -0,0 +1,220 @@
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use ssg::models::data::{PageData, SiteConfig};
+    use ssg::modules::pagination::{
+        generate_pagination_links, generate_pagination_nav, list_items,
+        page_url, paginate, paginator_data, write_paginated_pages,
+    };
+    use ssg::modules::tags::write_paginated_tags_html;
+    use std::{
+        collections::{BTreeMap, HashMap},
+        fs,
+    };
+
+    #[test]
+    fn test_page_url() {
+        assert_eq!(page_url("/tags/", 1), "/tags/index.html");
+        assert_eq!(page_url("/tags/", 3), "/tags/page/3/index.html");
+    }
+
+    #[test]
+    fn test_paginate_splits_items() {
+        let items: Vec<u32> = (1..=5).collect();
+        let pages = paginate(&items, 2, "/posts/");
+
+        assert_eq!(pages.len(), 3);
+        assert_eq!(pages[0].1, vec![1, 2]);
+        assert_eq!(pages[2].1, vec![5]);
+
+        let (first, _) = &pages[0];
+        assert_eq!(first.current, 1);
+        assert_eq!(first.total_pages, 3);
+        assert_eq!(first.total_items, 5);
+        assert!(first.prev_url.is_empty());
+        assert_eq!(first.next_url, "/posts/page/2/index.html");
+
+        let (last, _) = &pages[2];
+        assert_eq!(last.prev_url, "/posts/page/2/index.html");
+        assert!(last.next_url.is_empty());
+        assert_eq!(last.first_url, "/posts/index.html");
+        assert_eq!(last.last_url, "/posts/page/3/index.html");
+    }
+
+    #[test]
+    fn test_paginate_without_limit_or_items() {
+        let items = vec!["a", "b", "c"];
+        assert_eq!(paginate(&items, 0, "/").len(), 1);
+
+        let empty: Vec<&str> = vec![];
+        let pages = paginate(&empty, 10, "/");
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].0.total_pages, 1);
+    }
+
+    #[test]
+    fn test_generate_pagination_links_and_nav() {
+        let pages = paginate(&[1, 2, 3], 1, "/tags/");
+        let (middle, _) = &pages[1];
+
+        assert_eq!(
+            generate_pagination_links(middle),
+            "<link rel=\"prev\" href=\"/tags/index.html\">\n<link rel=\"next\" href=\"/tags/page/3/index.html\">"
+        );
+
+        let nav = generate_pagination_nav(middle);
+        assert!(nav.starts_with("<nav class=\"pagination\">"));
+        assert!(nav.contains("Page 2 of 3"));
+        assert!(nav
+            .contains("href=\"/tags/page/3/index.html\" rel=\"next\""));
+
+        let single = paginate(&[1], 10, "/tags/");
+        assert_eq!(generate_pagination_nav(&single[0].0), "");
+        assert_eq!(generate_pagination_links(&single[0].0), "");
+    }
+
+    #[test]
+    fn test_paginator_data() {
+        let items = vec![json!(1), json!(2), json!(3)];
+        let pages = paginate(&items, 2, "/posts/");
+        let (paginator, page_items) = &pages[1];
+
+        let data = paginator_data(paginator, page_items);
+        assert_eq!(data["current"], 2);
+        assert_eq!(data["prev_url"], "/posts/index.html");
+        assert_eq!(data["items"], json!([3]));
+        assert_eq!(
+            data["pages"],
+            json!([
+                { "number": 1, "url": "/posts/index.html", "current": false },
+                { "number": 2, "url": "/posts/page/2/index.html", "current": true },
+            ])
+        );
+
+        let single = paginate(&items, 0, "/posts/");
+        let data = paginator_data(&single[0].0, &single[0].1);
+        assert_eq!(data["items"], json!([1, 2, 3]));
+        assert_eq!(data["pages"], json!([]));
+    }
+
+    #[test]
+    fn test_list_items() {
+        let entries = vec![
+            json!({ "name": "index.md", "date": "2024-01-01" }),
+            json!({ "name": "one.md", "date": "2024-02-01" }),
+            json!({ "name": "two.md", "date": "2024-03-01" }),
+        ];
+        let sections = HashMap::from([(
+            "blog/_index.md".to_string(),
+            json!({ "pages": [entries[1]] }),
+        )]);
+        let archives = HashMap::from([
+            (
+                "2024/index.md".to_string(),
+                json!({ "pages": [entries[2]] }),
+            ),
+            ("archives.md".to_string(), json!({ "years": [] })),
+        ]);
+        let series = HashMap::from([
+            (
+                "series/guide.md".to_string(),
+                json!({ "index": 0, "parts": [entries[1], entries[2]] }),
+            ),
+            (
+                "one.md".to_string(),
+                json!({ "index": 1, "parts": [entries[1], entries[2]] }),
+            ),
+        ]);
+        let author_pages = BTreeMap::from([(
+            "jane".to_string(),
+            vec!["two.md".to_string()],
+        )]);
+
+        let items = list_items(
+            &entries,
+            &sections,
+            &archives,
+            &series,
+            &author_pages,
+            &SiteConfig::default(),
+        );
+
+        let names = |name: &str| -> Vec<&str> {
+            items[name]
+                .iter()
+                .map(|entry| entry["name"].as_str().unwrap())
+                .collect()
+        };
+        assert_eq!(names("index.md"), vec!["two.md", "one.md"]);
+        assert_eq!(names("blog/_index.md"), vec!["one.md"]);
+        assert_eq!(names("2024/index.md"), vec!["two.md"]);
+        assert_eq!(names("series/guide.md"), vec!["one.md", "two.md"]);
+        assert_eq!(names("authors/jane.md"), vec!["two.md"]);
+        assert!(!items.contains_key("archives.md"));
+        assert!(!items.contains_key("one.md"));
+    }
+
+    #[test]
+    fn test_write_paginated_pages() {
+        let dir = tempfile::tempdir().unwrap();
+        let pages = vec!["one".to_string(), "two".to_string()];
+
+        write_paginated_pages(dir.path(), "/list/", &pages).unwrap();
+
+        let read = |path: &str| {
+            fs::read_to_string(dir.path().join(path)).unwrap()
+        };
+        assert_eq!(read("index.html"), "one");
+        assert_eq!(read("page/2/index.html"), "two");
+        assert!(read("page/1/index.html")
+            .contains("content=\"0; url=/list/index.html\""));
+
+        let single = tempfile::tempdir().unwrap();
+        write_paginated_pages(single.path(), "/list/", &pages[..1])
+            .unwrap();
+        assert!(!single.path().join("page").exists());
+    }
+
+    #[test]
+    fn test_write_paginated_tags_html() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("tags")).unwrap();
+        fs::write(
+            dir.path().join("tags/index.html"),
+            "<html><head></head><body>[[content]]</body></html>",
+        )
+        .unwrap();
+
+        let mut tags = HashMap::new();
+        for tag in ["alpha", "beta", "gamma"] {
+            tags.insert(
+                tag.to_string(),
+                vec![PageData {
+                    title: format!("{} post", tag),
+                    ..Default::default()
+                }],
+            );
+        }
+
+        write_paginated_tags_html(&tags, dir.path(), 2).unwrap();
+
+        let first =
+            fs::read_to_string(dir.path().join("tags/index.html"))
+                .unwrap();
+        assert!(first.contains("alpha post"));
+        assert!(first.contains("beta post"));
+        assert!(!first.contains("gamma post"));
+        assert!(first.contains(
+            "<link href=/tags/page/2/index.html rel=next></head>"
+        ));
+
+        let second = fs::read_to_string(
+            dir.path().join("tags/page/2/index.html"),
+        )
+        .unwrap();
+        assert!(second.contains("gamma post"));
+        assert!(
+            second.contains("<link href=/tags/index.html rel=prev>")
+        );
+    }
+}
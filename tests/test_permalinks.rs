@@ -0,0 +1,193 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::metadata;
+    use ssg::models::data::FileData;
+    use ssg::modules::permalinks::{
+        output_name, resolve_path, resolve_routes, slugify, Routes,
+    };
+    use std::collections::BTreeMap;
+
+    fn patterns(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Hello, World!"), "hello-world");
+        assert_eq!(slugify("  Über straße  "), "uber-strasse");
+        assert_eq!(slugify("Ελληνικά"), "ellinika");
+        assert_eq!(slugify("Объявление"), "obyavlenie");
+        assert_eq!(slugify("日本語 ガイド"), "日本語-ガイド");
+        assert_eq!(slugify("---"), "");
+    }
+
+    #[test]
+    fn test_resolve_path_without_pattern() {
+        let none = BTreeMap::new();
+        assert_eq!(
+            resolve_path("about.md", &metadata(&[]), &none).unwrap(),
+            "about"
+        );
+        assert_eq!(
+            resolve_path(
+                "guides/install.md",
+                &metadata(&[("slug", "Getting Started")]),
+                &none
+            )
+            .unwrap(),
+            "guides/getting-started"
+        );
+        assert_eq!(
+            resolve_path(
+                "index.md",
+                &metadata(&[("slug", "home")]),
+                &none
+            )
+            .unwrap(),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_resolve_path_with_patterns() {
+        let patterns = patterns(&[
+            ("posts", "/:year/:month/:slug/"),
+            ("docs", "/docs/:section/:title/"),
+        ]);
+
+        assert_eq!(
+            resolve_path(
+                "posts/hello.md",
+                &metadata(&[
+                    ("title", "Héllo Wörld"),
+                    ("date", "2024-05-03"),
+                ]),
+                &patterns
+            )
+            .unwrap(),
+            "2024/05/hello-world"
+        );
+        assert_eq!(
+            resolve_path(
+                "posts/hello.md",
+                &metadata(&[("date", "2024-05-03"), ("slug", "hi")]),
+                &patterns
+            )
+            .unwrap(),
+            "2024/05/hi"
+        );
+        assert_eq!(
+            resolve_path(
+                "docs/api/client.md",
+                &metadata(&[("title", "The Client")]),
+                &patterns
+            )
+            .unwrap(),
+            "docs/api/the-client"
+        );
+        assert_eq!(
+            resolve_path("docs/index.md", &metadata(&[]), &patterns)
+                .unwrap(),
+            "docs"
+        );
+    }
+
+    #[test]
+    fn test_resolve_path_errors() {
+        let patterns = patterns(&[("posts", "/:year/:slug/")]);
+
+        let error = resolve_path(
+            "posts/hello.md",
+            &metadata(&[("title", "Hello")]),
+            &patterns,
+        )
+        .unwrap_err();
+        assert!(error.contains("`posts/hello.md` has no valid `date`"));
+
+        for title in ["", "!!!"] {
+            let error = resolve_path(
+                "posts/hello.md",
+                &metadata(&[("title", title), ("date", "2024-05-03")]),
+                &patterns,
+            )
+            .unwrap_err();
+            assert!(error.contains("empty permalink slug"));
+        }
+        assert!(resolve_path(
+            "about.md",
+            &metadata(&[("slug", "?")]),
+            &BTreeMap::new()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_resolve_routes_detects_collisions() {
+        let file = |name: &str, front_matter: &str| FileData {
+            name: name.to_string(),
+            content: format!("---\n{}\n---\nBody", front_matter),
+            ..Default::default()
+        };
+
+        let routes = resolve_routes(
+            &[file("a.md", "slug: one"), file("b.md", "title: B")],
+            &BTreeMap::new(),
+        )
+        .unwrap();
+        assert_eq!(routes.path("a.md"), "one");
+        assert_eq!(routes.url("b.md"), "/b/index.html");
+
+        let error = resolve_routes(
+            &[
+                file("a.md", "slug: same"),
+                file("same.md", "title: Same"),
+            ],
+            &BTreeMap::new(),
+        )
+        .unwrap_err();
+        assert!(error.contains("`a.md` and `same.md`"));
+    }
+
+    #[test]
+    fn test_resolve_routes_detects_generated_paths() {
+        let file = |name: &str| FileData {
+            name: name.to_string(),
+            content: "---\ntitle: Page\n---\nBody".to_string(),
+            ..Default::default()
+        };
+
+        for name in
+            ["sitemap.xml.md", "blog/page/2.md", "tags/page/3.md"]
+        {
+            let error = resolve_routes(
+                &[file("blog.md"), file("tags.md"), file(name)],
+                &BTreeMap::new(),
+            )
+            .unwrap_err();
+            assert!(
+                error.contains("generated by the build"),
+                "{}",
+                name
+            );
+        }
+        assert!(resolve_routes(
+            &[file("blog.md"), file("tags.md"), file("blog/page.md")],
+            &BTreeMap::new(),
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_routes_fallback_and_output_name() {
+        let routes = Routes::new();
+        assert_eq!(routes.path("guides/index.md"), "guides");
+        assert_eq!(routes.url("index.md"), "/index.html");
+        assert_eq!(output_name(""), "index.md");
+        assert_eq!(output_name("2024/05/hi"), "2024/05/hi/index.md");
+    }
+}
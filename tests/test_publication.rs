@@ -0,0 +1,137 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::metadata;
+    use ssg::models::data::{
+        BuildOptions, FileData, PublicationStatus,
+    };
+    use ssg::modules::publication::{
+        filter_published, is_included, publication_status,
+        status_marker, today,
+    };
+
+    const TODAY: (i32, u32, u32) = (2024, 6, 15);
+
+    #[test]
+    fn test_publication_status() {
+        assert_eq!(
+            publication_status(
+                &metadata(&[("date", "2024-06-15")]),
+                TODAY
+            ),
+            PublicationStatus::Published
+        );
+        assert_eq!(
+            publication_status(&metadata(&[("draft", "true")]), TODAY),
+            PublicationStatus::Draft
+        );
+        assert_eq!(
+            publication_status(
+                &metadata(&[("date", "2024-07-01")]),
+                TODAY
+            ),
+            PublicationStatus::Scheduled
+        );
+        assert_eq!(
+            publication_status(
+                &metadata(&[
+                    ("date", "2024-07-01"),
+                    ("publish_date", "2024-06-01")
+                ]),
+                TODAY
+            ),
+            PublicationStatus::Published
+        );
+        assert_eq!(
+            publication_status(
+                &metadata(&[("expiry_date", "June 15, 2024")]),
+                TODAY
+            ),
+            PublicationStatus::Expired
+        );
+        assert_eq!(
+            publication_status(
+                &metadata(&[
+                    ("draft", "false"),
+                    ("date", "not a date")
+                ]),
+                TODAY
+            ),
+            PublicationStatus::Published
+        );
+    }
+
+    #[test]
+    fn test_is_included() {
+        let default = BuildOptions::default();
+        let preview = BuildOptions {
+            drafts: true,
+            future: true,
+        };
+
+        assert!(is_included(PublicationStatus::Published, &default));
+        assert!(!is_included(PublicationStatus::Draft, &default));
+        assert!(!is_included(PublicationStatus::Scheduled, &default));
+        assert!(is_included(PublicationStatus::Draft, &preview));
+        assert!(is_included(PublicationStatus::Scheduled, &preview));
+        assert!(!is_included(PublicationStatus::Expired, &preview));
+    }
+
+    #[test]
+    fn test_filter_published() {
+        let file = |name: &str, front_matter: &str| FileData {
+            name: name.to_string(),
+            content: format!("---\n{}\n---\nBody", front_matter),
+            ..Default::default()
+        };
+        let files = vec![
+            file("live.md", "title: Live"),
+            file("draft.md", "draft: true"),
+            file("future.md", "date: 2030-01-01"),
+        ];
+
+        let published = filter_published(
+            files.clone(),
+            &BuildOptions::default(),
+            TODAY,
+        );
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].name, "live.md");
+
+        let with_drafts = filter_published(
+            files,
+            &BuildOptions {
+                drafts: true,
+                future: false,
+            },
+            TODAY,
+        );
+        assert_eq!(with_drafts.len(), 2);
+    }
+
+    #[test]
+    fn test_today_is_a_valid_date() {
+        let (year, month, day) = today(0);
+
+        assert!(year >= 2024);
+        assert!((1..=12).contains(&month));
+        assert!((1..=31).contains(&day));
+
+        // The site timezone can move the date by one day at most
+        let ahead = today(14 * 60);
+        let behind = today(-12 * 60);
+        assert!(ahead >= (year, month, day));
+        assert!(behind <= (year, month, day));
+        assert_ne!(ahead, behind);
+    }
+
+    #[test]
+    fn test_status_marker() {
+        assert_eq!(status_marker(PublicationStatus::Published), "");
+        assert!(status_marker(PublicationStatus::Draft)
+            .contains("<strong>Draft</strong>"));
+        assert!(status_marker(PublicationStatus::Scheduled)
+            .contains("class=\"publication-status\""));
+    }
+}
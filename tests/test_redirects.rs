@@ -0,0 +1,198 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::page;
+    use ssg::modules::permalinks::Routes;
+    use ssg::modules::redirects::{
+        alias_file, check_alias, collect_redirects, find_redirect,
+        generate_htaccess, generate_netlify_redirects,
+        generate_nginx_map, generate_redirect_html, normalize_alias,
+        write_redirects,
+    };
+    use std::{collections::BTreeMap, fs, path::Path};
+
+    fn redirects() -> BTreeMap<String, String> {
+        let mut redirects = BTreeMap::new();
+        redirects.insert(
+            "old/post".to_string(),
+            "/posts/new/index.html".to_string(),
+        );
+        redirects.insert(
+            "legacy.html".to_string(),
+            "/about/index.html".to_string(),
+        );
+        redirects
+    }
+
+    #[test]
+    fn test_normalize_alias() {
+        assert_eq!(normalize_alias("/old/post/"), "old/post");
+        assert_eq!(normalize_alias("old/post/index.html"), "old/post");
+        assert_eq!(normalize_alias("/legacy.html"), "legacy.html");
+        assert_eq!(
+            alias_file("old/post"),
+            Path::new("old/post/index.html")
+        );
+    }
+
+    #[test]
+    fn test_check_alias() {
+        assert_eq!(check_alias("/old/post/").unwrap(), "old/post");
+        for alias in [
+            "../../etc/x",
+            "old/../../x",
+            "./old",
+            "old//post",
+            "/",
+            "old post",
+            "old\"post",
+        ] {
+            assert!(
+                check_alias(alias)
+                    .unwrap_err()
+                    .starts_with("Invalid alias"),
+                "{}",
+                alias
+            );
+        }
+    }
+
+    #[test]
+    fn test_collect_redirects() {
+        let files = vec![
+            page("posts/new.md", "aliases: [/old/post/, /older/]"),
+            page("about.md", "title: About"),
+        ];
+        let result = collect_redirects(&files, &Routes::new()).unwrap();
+        assert_eq!(result["old/post"], "/posts/new/index.html");
+        assert_eq!(result["older"], "/posts/new/index.html");
+    }
+
+    #[test]
+    fn test_collect_redirects_conflicts() {
+        let shadowing = vec![
+            page("posts/new.md", "aliases: /about/"),
+            page("about.md", "title: About"),
+        ];
+        assert!(collect_redirects(&shadowing, &Routes::new())
+            .unwrap_err()
+            .contains("would overwrite a generated page"));
+
+        for alias in ["tags", "page/2", "about/page/3", "rss.xml"] {
+            let generated = vec![
+                page("index.md", &format!("aliases: /{}/", alias)),
+                page("about.md", "title: About"),
+            ];
+            assert!(
+                collect_redirects(&generated, &Routes::new())
+                    .unwrap_err()
+                    .contains("would overwrite a generated page"),
+                "{}",
+                alias
+            );
+        }
+
+        let outside = vec![page("a.md", "aliases: ../../etc/x")];
+        assert!(collect_redirects(&outside, &Routes::new())
+            .unwrap_err()
+            .ends_with("in `a.md`"));
+
+        let duplicated = vec![
+            page("a.md", "aliases: /old/"),
+            page("b.md", "aliases: /old/"),
+        ];
+        assert!(collect_redirects(&duplicated, &Routes::new())
+            .unwrap_err()
+            .contains("declared by both `a.md` and `b.md`"));
+    }
+
+    #[test]
+    fn test_generate_manifests() {
+        let redirects = redirects();
+
+        assert_eq!(
+            generate_netlify_redirects(&redirects),
+            "/legacy.html /about/index.html 301\n/old/post/ /posts/new/index.html 301\n"
+        );
+        assert_eq!(
+            generate_htaccess(&redirects),
+            "RedirectMatch 301 ^/legacy\\.html$ /about/index.html\nRedirectMatch 301 ^/old/post(/|/index\\.html)?$ /posts/new/index.html\n"
+        );
+        let nginx = generate_nginx_map(&redirects);
+        assert!(nginx.starts_with("map $uri $redirect_uri {\n"));
+        assert!(nginx.contains(
+            "    \"/old/post/index.html\" \"/posts/new/index.html\";\n"
+        ));
+        assert!(nginx.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_write_redirects() {
+        let dir = tempfile::tempdir().unwrap();
+        write_redirects(dir.path(), &redirects(), "").unwrap();
+
+        let page =
+            fs::read_to_string(dir.path().join("old/post/index.html"))
+                .unwrap();
+        assert_eq!(
+            page,
+            generate_redirect_html("/posts/new/index.html")
+        );
+        assert!(page.contains(
+            "<link rel=\"canonical\" href=\"/posts/new/index.html\">"
+        ));
+        assert!(dir.path().join("legacy.html").is_file());
+        assert!(dir.path().join("_redirects").is_file());
+        assert!(dir.path().join(".htaccess").is_file());
+        assert!(dir.path().join("redirects.map").is_file());
+
+        let empty = tempfile::tempdir().unwrap();
+        write_redirects(empty.path(), &BTreeMap::new(), "").unwrap();
+        assert!(!empty.path().join("_redirects").exists());
+    }
+
+    #[test]
+    fn test_write_redirects_under_a_path() {
+        let dir = tempfile::tempdir().unwrap();
+        write_redirects(dir.path(), &redirects(), "/docs").unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.path().join("old/post/index.html"))
+                .unwrap(),
+            generate_redirect_html("/posts/new/index.html")
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("_redirects")).unwrap(),
+            "/docs/legacy.html /docs/about/index.html 301\n\
+             /docs/old/post/ /docs/posts/new/index.html 301\n"
+        );
+        assert!(fs::read_to_string(dir.path().join(".htaccess"))
+            .unwrap()
+            .contains(
+                "^/docs/old/post(/|/index\\.html)?$ /docs/posts/"
+            ));
+        assert!(fs::read_to_string(dir.path().join("redirects.map"))
+            .unwrap()
+            .contains("    \"/docs/legacy.html\" \"/docs/about/index.html\";\n"));
+        assert_eq!(
+            fs::read_to_string(dir.path().join(".redirects")).unwrap(),
+            "/legacy.html /about/index.html 301\n\
+             /old/post/ /posts/new/index.html 301\n"
+        );
+    }
+
+    #[test]
+    fn test_find_redirect() {
+        let manifest = generate_netlify_redirects(&redirects());
+        assert_eq!(
+            find_redirect(&manifest, "/old/post"),
+            Some("/posts/new/index.html".to_string())
+        );
+        assert_eq!(
+            find_redirect(&manifest, "/old/post/index.html?ref=1"),
+            Some("/posts/new/index.html".to_string())
+        );
+        assert_eq!(find_redirect(&manifest, "/missing/"), None);
+    }
+}
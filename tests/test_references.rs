@@ -0,0 +1,170 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::file;
+    use ssg::models::data::FileData;
+    use ssg::modules::permalinks::Routes;
+    use ssg::modules::references::{
+        link_target, page_ids, resolve_references, rewrite_references,
+        Reference,
+    };
+    use std::collections::HashMap;
+
+    fn resolve(
+        reference: Reference<'_>,
+    ) -> Result<(String, String), String> {
+        match reference {
+            Reference::Id("install")
+            | Reference::Link("install.md") => Ok((
+                "/docs/install/index.html".to_string(),
+                "Install".to_string(),
+            )),
+            Reference::Id("untitled") => {
+                Ok(("/untitled/index.html".to_string(), String::new()))
+            }
+            reference => Err(format!("{:?}", reference)),
+        }
+    }
+
+    #[test]
+    fn test_page_ids() {
+        let metadata =
+            HashMap::from([("id".to_string(), " setup ".to_string())]);
+        assert_eq!(
+            page_ids("guides/install.md", &metadata),
+            vec!["setup", "guides/install", "install"]
+        );
+        assert_eq!(
+            page_ids("about.md", &HashMap::new()),
+            vec!["about"]
+        );
+        assert_eq!(
+            page_ids("guides/_index.md", &HashMap::new()),
+            vec!["guides/_index", "guides"]
+        );
+    }
+
+    #[test]
+    fn test_link_target() {
+        assert_eq!(
+            link_target("guides/install.md", "setup.md"),
+            Some("guides/setup.md".to_string())
+        );
+        assert_eq!(
+            link_target("guides/install.md", "./../about.md"),
+            Some("about.md".to_string())
+        );
+        assert_eq!(
+            link_target("index.md", "/guides/install.md"),
+            Some("guides/install.md".to_string())
+        );
+        assert_eq!(
+            link_target("guides/install.md", "../../a.md"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_rewrite_references() {
+        let body = "See [[install]], [[install#linux|Linux setup]] \
+                    and [[untitled]].\n\
+                    [Install](install.md#usage), [Site](https://a.b/c.md)\n\
+                    [install]: install.md\n\
+                    `[[missing]]` and [[content]]\n\
+                    ``a ` [[missing]]`` then [[install]]\n\
+                    ```\n[[missing]]\n```\n\n    [[missing]]";
+        assert_eq!(
+            rewrite_references(body, resolve).unwrap(),
+            "See [Install](/docs/install/index.html), \
+             [Linux setup](/docs/install/index.html#linux) \
+             and [untitled](/untitled/index.html).\n\
+             [Install](/docs/install/index.html#usage), \
+             [Site](https://a.b/c.md)\n\
+             [install]: /docs/install/index.html\n\
+             `[[missing]]` and [[content]]\n\
+             ``a ` [[missing]]`` then \
+             [Install](/docs/install/index.html)\n\
+             ```\n[[missing]]\n```\n\n    [[missing]]"
+        );
+
+        assert_eq!(
+            rewrite_references("[[missing]]", resolve).unwrap_err(),
+            "Id(\"missing\")"
+        );
+        assert_eq!(
+            rewrite_references("`a` [b]: b.md", resolve).unwrap(),
+            "`a` [b]: b.md"
+        );
+        assert_eq!(
+            rewrite_references("[a](../a.md)", resolve).unwrap_err(),
+            "Link(\"../a.md\")"
+        );
+    }
+
+    #[test]
+    fn test_resolve_references() {
+        let mut routes = Routes::new();
+        routes.insert("guides/install.md", "docs/setup");
+        routes.insert("guides/tuning.md", "guides/tuning");
+        routes.insert("legacy/install.md", "legacy/install");
+        routes.insert("index.md", "");
+
+        let files = resolve_references(
+            vec![
+                file(
+                    "guides/install.md",
+                    "---\ntitle: Install\nid: install\n---\n\nHi",
+                ),
+                file(
+                    "guides/tuning.md",
+                    "---\ntitle: Tuning\n---\n\n\
+                     [[install]], [back](install.md), [[index|Home]]",
+                ),
+                file("legacy/install.md", "---\ntitle: Old\n---\n"),
+                file("index.md", "[[guides/tuning]]"),
+                file("page.html", "<p>[[missing]]</p>"),
+            ],
+            &routes,
+        )
+        .unwrap();
+
+        assert_eq!(
+            files[1].content,
+            "---\ntitle: Tuning\n---\n\n\
+             [Install](/docs/setup/index.html), \
+             [back](/docs/setup/index.html), [Home](/index.html)"
+        );
+        assert_eq!(
+            files[3].content,
+            "[Tuning](/guides/tuning/index.html)"
+        );
+        assert_eq!(files[4].content, "<p>[[missing]]</p>");
+    }
+
+    #[test]
+    fn test_resolve_references_errors() {
+        let routes = Routes::new();
+        let error = |files: Vec<FileData>| {
+            resolve_references(files, &routes).unwrap_err()
+        };
+
+        assert_eq!(
+            error(vec![file("a.md", "[[b]]")]),
+            "Broken reference `[[b]]` in a.md: no page has this ID"
+        );
+        assert_eq!(
+            error(vec![file("a.md", "[b](b.md)")]),
+            "Broken link to `b.md` in a.md: no such page"
+        );
+        assert_eq!(
+            error(vec![
+                file("a.md", "[[install]]"),
+                file("guides/install.md", ""),
+                file("setup/install.md", ""),
+            ]),
+            "Ambiguous reference `[[install]]` in a.md: it matches \
+             guides/install.md and setup/install.md"
+        );
+    }
+}
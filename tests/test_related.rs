@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use ssg::models::data::{RelatedConfig, RelatedDocument};
+    use ssg::modules::related::{
+        find_related, label_similarity, related_document,
+        related_entries, tokenize,
+    };
+    use std::collections::HashMap;
+
+    fn document(
+        name: &str,
+        tags: &[&str],
+        keywords: &[&str],
+        text: &str,
+    ) -> RelatedDocument {
+        RelatedDocument {
+            name: name.to_string(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            keywords: keywords.iter().map(|k| k.to_string()).collect(),
+            text: text.to_string(),
+        }
+    }
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("The CAT sat on a mat, with 42 dogs."),
+            vec!["cat", "sat", "mat", "dogs"]
+        );
+        assert!(tokenize("").is_empty());
+    }
+
+    #[test]
+    fn test_label_similarity() {
+        let a = strings(&["Rust", "web"]);
+        let b = strings(&["rust", "cli"]);
+        assert!((label_similarity(&a, &b) - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(label_similarity(&a, &a), 1.0);
+        assert_eq!(label_similarity(&a, &[]), 0.0);
+        assert_eq!(label_similarity(&[], &[]), 0.0);
+    }
+
+    #[test]
+    fn test_find_related_by_tags() {
+        let documents = vec![
+            document("a.md", &["rust", "web"], &[], ""),
+            document("b.md", &["rust", "web"], &[], ""),
+            document("c.md", &["rust"], &[], ""),
+            document("d.md", &["cooking"], &[], ""),
+        ];
+        let related =
+            find_related(&documents, &RelatedConfig::default());
+
+        assert_eq!(related["a.md"], strings(&["b.md", "c.md"]));
+        assert!(related["d.md"].is_empty());
+    }
+
+    #[test]
+    fn test_find_related_by_text() {
+        let documents = vec![
+            document("a.md", &[], &[], "Borrow checker and lifetimes"),
+            document(
+                "b.md",
+                &[],
+                &[],
+                "Lifetimes explain the borrow checker",
+            ),
+            document(
+                "c.md",
+                &[],
+                &[],
+                "Baking sourdough bread at home",
+            ),
+        ];
+        let related =
+            find_related(&documents, &RelatedConfig::default());
+
+        assert_eq!(related["a.md"], strings(&["b.md"]));
+        assert!(related["c.md"].is_empty());
+    }
+
+    #[test]
+    fn test_find_related_weights_and_count() {
+        let documents = vec![
+            document("a.md", &["rust"], &["async"], ""),
+            document("b.md", &["rust"], &[], ""),
+            document("c.md", &[], &["async"], ""),
+        ];
+
+        let config = RelatedConfig {
+            count: 1,
+            tags_weight: 1,
+            keywords_weight: 5,
+            text_weight: 0,
+        };
+        assert_eq!(
+            find_related(&documents, &config)["a.md"],
+            strings(&["c.md"])
+        );
+
+        let config = RelatedConfig {
+            count: 0,
+            ..RelatedConfig::default()
+        };
+        assert!(find_related(&documents, &config).is_empty());
+    }
+
+    #[test]
+    fn test_related_document() {
+        let metadata: HashMap<String, String> = [
+            ("tags".to_string(), "rust, web".to_string()),
+            ("keywords".to_string(), "async, tokio".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        let document = related_document("a.md", &metadata, "Text");
+
+        assert_eq!(document.tags, strings(&["rust", "web"]));
+        assert_eq!(document.keywords, strings(&["async", "tokio"]));
+        assert_eq!(document.text, "Text");
+    }
+
+    #[test]
+    fn test_related_entries() {
+        let entries = vec![
+            json!({"name": "a.md", "url": "/a/"}),
+            json!({"name": "b.md", "url": "/b/"}),
+        ];
+        let names = strings(&["b.md", "missing.md", "a.md"]);
+
+        assert_eq!(
+            related_entries(&names, &entries),
+            json!([
+                {"name": "b.md", "url": "/b/"},
+                {"name": "a.md", "url": "/a/"}
+            ])
+        );
+    }
+}
@@ -5,7 +5,7 @@ mod tests {
     use quick_xml::{escape::escape, Writer};
     use ssg::{
         macro_write_element, models::data::RssData,
-        modules::rss::generate_rss,
+        modules::rss::{generate_rss, generate_rss_feed},
     };
 
     /// # RssData Tests
@@ -111,6 +111,30 @@ mod tests {
             assert!(rss_str.is_ok());
         }
 
+        // Test generating an RSS feed with several items
+        #[test]
+        fn test_generate_rss_feed() {
+            let mut channel = RssData::new();
+            channel.title = "Jane Doe".to_string();
+            let items: Vec<RssData> = ["One", "Two"]
+                .iter()
+                .map(|title| {
+                    let mut item = RssData::new();
+                    item.item_title = title.to_string();
+                    item
+                })
+                .collect();
+
+            let rss_str = generate_rss_feed(&channel, &items).unwrap();
+
+            assert!(rss_str.contains("<title>Jane Doe</title>"));
+            assert_eq!(rss_str.matches("<item>").count(), 2);
+            assert!(
+                rss_str.find("<title>One</title>")
+                    < rss_str.find("<title>Two</title>")
+            );
+        }
+
         // Test generating an RSS feed with empty title
         #[test]
         fn test_generate_rss_with_empty_title() {
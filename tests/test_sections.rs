@@ -0,0 +1,130 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::entry;
+    use serde_json::{json, Value};
+    use ssg::models::data::FileData;
+    use ssg::modules::frontmatter::extract;
+    use ssg::modules::sections::{
+        apply_section_layout, compare_pages, document_name,
+        is_section_page, section_data,
+    };
+    use std::cmp::Ordering;
+
+    fn entries() -> Vec<Value> {
+        vec![
+            entry("index.md", &[("title", "Home")]),
+            entry("guides/_index.md", &[("title", "Guides")]),
+            entry("guides/tuning.md", &[("title", "Tuning")]),
+            entry(
+                "guides/setup.md",
+                &[("title", "Setup"), ("weight", "2")],
+            ),
+            entry(
+                "guides/install.md",
+                &[("title", "Install"), ("weight", "1")],
+            ),
+            entry(
+                "guides/upgrade.md",
+                &[("title", "Upgrade"), ("date", "2024-05-01")],
+            ),
+            entry(
+                "guides/advanced/_index.md",
+                &[("title", "Advanced")],
+            ),
+            entry("guides/advanced/deep.md", &[("title", "Deep dive")]),
+            entry("guides/faq/index.md", &[("title", "FAQ")]),
+        ]
+    }
+
+    #[test]
+    fn test_document_name() {
+        assert_eq!(document_name("guides/_index.md"), "guides");
+        assert_eq!(document_name("docs/api/_index.md"), "api");
+        assert_eq!(document_name("_index.md"), "index");
+        assert_eq!(document_name("guides/index.md"), "index");
+        assert_eq!(document_name("about.md"), "about");
+    }
+
+    #[test]
+    fn test_is_section_page() {
+        assert!(is_section_page("_index.md"));
+        assert!(is_section_page("guides/_index.json"));
+        assert!(!is_section_page("guides/index.md"));
+        assert!(!is_section_page("guides/my_index.md"));
+    }
+
+    #[test]
+    fn test_apply_section_layout() {
+        let files = apply_section_layout(vec![
+            FileData {
+                name: "guides/_index.md".to_string(),
+                content: "---\ntitle: Guides\n---\n\nIntro".to_string(),
+                ..Default::default()
+            },
+            FileData {
+                name: "news/_index.md".to_string(),
+                content: "---\nlayout: news\n---\n".to_string(),
+                ..Default::default()
+            },
+            FileData {
+                name: "guides/install.md".to_string(),
+                content: "---\ntitle: Install\n---\n".to_string(),
+                ..Default::default()
+            },
+        ]);
+
+        assert_eq!(extract(&files[0].content)["layout"], "section");
+        assert!(files[0].content.ends_with("\n\nIntro"));
+        assert_eq!(extract(&files[1].content)["layout"], "news");
+        assert!(!extract(&files[2].content).contains_key("layout"));
+    }
+
+    #[test]
+    fn test_compare_pages() {
+        let light = json!({ "weight": "1", "title": "B" });
+        let heavy = json!({ "weight": "10", "title": "A" });
+        let old = json!({ "date": "2023-01-01", "title": "A" });
+        let new = json!({ "date": "2024-01-01", "title": "Z" });
+        let plain = json!({ "title": "C" });
+
+        assert_eq!(compare_pages(&light, &heavy), Ordering::Less);
+        assert_eq!(compare_pages(&heavy, &new), Ordering::Less);
+        assert_eq!(compare_pages(&new, &old), Ordering::Less);
+        assert_eq!(compare_pages(&old, &plain), Ordering::Less);
+    }
+
+    #[test]
+    fn test_section_data() {
+        let data = section_data(&entries());
+        assert_eq!(data.len(), 2);
+
+        let guides = &data["guides/_index.md"];
+        assert_eq!(guides["title"], "Guides");
+        assert_eq!(guides["url"], "/guides/index.html");
+        assert_eq!(guides["count"], 4);
+        let titles: Vec<&str> = guides["pages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|page| page["title"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            titles,
+            vec!["Install", "Setup", "Upgrade", "Tuning"]
+        );
+
+        let subsections = guides["subsections"].as_array().unwrap();
+        assert_eq!(subsections.len(), 2);
+        assert_eq!(subsections[0]["title"], "Advanced");
+        assert_eq!(subsections[0]["path"], "guides/advanced");
+        assert_eq!(subsections[0]["count"], 1);
+        assert_eq!(subsections[1]["title"], "FAQ");
+        assert_eq!(subsections[1]["count"], 0);
+
+        let advanced = &data["guides/advanced/_index.md"];
+        assert_eq!(advanced["pages"][0]["title"], "Deep dive");
+        assert_eq!(advanced["subsections"], json!([]));
+    }
+}
@@ -0,0 +1,152 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::page;
+    use serde_json::{json, Value};
+    use ssg::models::data::{LanguageConfig, Series, SiteConfig};
+    use ssg::modules::frontmatter::extract;
+    use ssg::modules::permalinks::resolve_routes;
+    use ssg::modules::series::{
+        collect_series, generate_overview_files, overview_name,
+        series_data,
+    };
+
+    fn entries() -> Vec<Value> {
+        vec![
+            json!({"name": "b.md", "url": "/b/", "title": "Two",
+                "series": "Rust Basics", "series_order": "2"}),
+            json!({"name": "a.md", "url": "/a/", "title": "One",
+                "series": "Rust Basics", "series_order": "1"}),
+            json!({"name": "c.md", "url": "/c/", "title": "Three",
+                "series": "rust basics"}),
+            json!({"name": "d.md", "url": "/d/", "title": "Alone"}),
+            json!({"name": "series/rust-basics.md",
+                "url": "/series/rust-basics/"}),
+        ]
+    }
+
+    #[test]
+    fn test_collect_series() {
+        let series = collect_series(&entries(), &SiteConfig::default());
+
+        assert_eq!(
+            series,
+            vec![Series {
+                name: "Rust Basics".to_string(),
+                slug: "rust-basics".to_string(),
+                language: String::new(),
+                parts: vec![
+                    "a.md".to_string(),
+                    "b.md".to_string(),
+                    "c.md".to_string(),
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_collect_series_orders_by_date() {
+        let entries = vec![
+            json!({"name": "x.md", "series": "S", "date": "2024-03-01"}),
+            json!({"name": "y.md", "series": "S", "date": "2024-01-01"}),
+            json!({"name": "z.md", "series": "S"}),
+        ];
+        let series = collect_series(&entries, &SiteConfig::default());
+
+        assert_eq!(series[0].parts, vec!["y.md", "x.md", "z.md"]);
+    }
+
+    #[test]
+    fn test_overview_name() {
+        let mut config = SiteConfig::default();
+        let mut series = Series {
+            slug: "rust".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(overview_name(&series, &config), "series/rust.md");
+
+        let _ = config
+            .languages
+            .insert("en".to_string(), LanguageConfig::default());
+        let _ = config.languages.insert(
+            "fr".to_string(),
+            LanguageConfig {
+                weight: 1,
+                ..Default::default()
+            },
+        );
+        series.language = "fr".to_string();
+        assert_eq!(
+            overview_name(&series, &config),
+            "fr/series/rust.md"
+        );
+        series.language = "en".to_string();
+        assert_eq!(overview_name(&series, &config), "series/rust.md");
+    }
+
+    #[test]
+    fn test_series_data() {
+        let config = SiteConfig::default();
+        let entries = entries();
+        let series = collect_series(&entries, &config);
+        let data = series_data(&series, &entries, &config);
+
+        let second = &data["b.md"];
+        assert_eq!(second["name"], "Rust Basics");
+        assert_eq!(second["url"], "/series/rust-basics/");
+        assert_eq!(second["index"], 2);
+        assert_eq!(second["total"], 3);
+        assert_eq!(second["previous"]["name"], "a.md");
+        assert_eq!(second["next"]["name"], "c.md");
+        assert_eq!(second["parts"][2]["title"], "Three");
+
+        assert!(data["a.md"]["previous"].is_null());
+        assert!(data["c.md"]["next"].is_null());
+        assert_eq!(data["series/rust-basics.md"]["index"], 0);
+        assert!(data["series/rust-basics.md"]["next"].is_null());
+        assert!(!data.contains_key("d.md"));
+    }
+
+    #[test]
+    fn test_generate_overview_files() {
+        let files = vec![
+            page("two.md", "title: Second\nseries: Guide\nseries_order: 2"),
+            page(
+                "one.md",
+                "title: First\ndescription: Start here\nseries: Guide\nseries_order: 1",
+            ),
+            page("other.md", "title: Other"),
+        ];
+        let config = SiteConfig::default();
+        let routes =
+            resolve_routes(&files, &config.permalinks).unwrap();
+        let overviews =
+            generate_overview_files(&files, &routes, &config);
+
+        assert_eq!(overviews.len(), 1);
+        assert_eq!(overviews[0].name, "series/guide.md");
+        let front_matter = extract(&overviews[0].content);
+        assert_eq!(front_matter["title"], "Guide");
+        assert_eq!(front_matter["layout"], "page");
+        assert_eq!(
+            front_matter["description"],
+            "The 2 parts of the Guide series."
+        );
+        assert!(overviews[0].content.ends_with("---\n\n\n"));
+    }
+
+    #[test]
+    fn test_generate_overview_files_keeps_existing_page() {
+        let files = vec![
+            page("one.md", "title: First\nseries: Guide"),
+            page("series/guide.md", "title: My guide"),
+        ];
+        let config = SiteConfig::default();
+        let routes =
+            resolve_routes(&files, &config.permalinks).unwrap();
+
+        assert!(generate_overview_files(&files, &routes, &config)
+            .is_empty());
+    }
+}
@@ -1,9 +1,9 @@
 #[cfg(test)]
 mod tests {
-    use ssg::utilities::serve::start;
+    use ssg::utilities::serve::{serve, start};
     use std::fs::File;
     use std::io::{Read, Write};
-    use std::net::TcpStream;
+    use std::net::{TcpListener, TcpStream};
     use std::thread;
     use tempfile::TempDir;
 
@@ -41,4 +41,32 @@ mod tests {
         assert!(response.contains("Content-Type: text/html"));
         assert!(response.contains("Hello, world!"));
     }
+
+    #[test]
+    fn test_handle_connection_redirects() {
+        let tmp_dir = TempDir::new().unwrap();
+        let mut redirects =
+            File::create(tmp_dir.path().join(".redirects")).unwrap();
+        writeln!(redirects, "/old/ /new/index.html 301").unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let server_addr = listener.local_addr().unwrap();
+        let document_root =
+            tmp_dir.path().to_str().unwrap().to_string();
+        thread::spawn(move || {
+            serve(listener, &document_root).unwrap();
+        });
+
+        let mut stream = TcpStream::connect(server_addr).unwrap();
+        stream
+            .write_all(b"GET /old/index.html HTTP/1.1\r\n\r\n")
+            .unwrap();
+
+        let mut buffer = Vec::new();
+        stream.read_to_end(&mut buffer).unwrap();
+        let response = String::from_utf8(buffer).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 301 Moved Permanently"));
+        assert!(response.contains("Location: /new/index.html"));
+    }
 }
@@ -25,6 +25,34 @@ mod tests {
         assert_eq!("https://example.com", site_map_data.loc);
     }
 
+    /// Converts an RFC 2822 last build date to the W3C format.
+    #[test]
+    fn create_site_map_data_converts_rfc2822_dates() {
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "last_build_date".to_string(),
+            "Thu, 11 Apr 2024 06:06:06 +0000".to_string(),
+        );
+
+        let site_map_data = create_site_map_data(&metadata);
+
+        assert_eq!("2024-04-11T06:06:06+00:00", site_map_data.lastmod);
+    }
+
+    /// Leaves out a last build date that cannot be parsed.
+    #[test]
+    fn create_site_map_data_drops_invalid_dates() {
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "last_build_date".to_string(),
+            "last Tuesday".to_string(),
+        );
+
+        let site_map_data = create_site_map_data(&metadata);
+
+        assert_eq!("", site_map_data.lastmod);
+    }
+
     /// Verifies that missing metadata fields result in default SiteMapData values.
     #[test]
     fn create_site_map_data_with_incomplete_metadata() {
@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod tests {
+    use ssg::models::data::{ContentSummary, SummaryConfig};
+    use ssg::modules::plaintext::generate_plain_text;
+    use ssg::modules::summary::{
+        apply_summary, reading_time, summarize, word_count,
+    };
+    use std::collections::HashMap;
+
+    fn plain(content: &str) -> String {
+        generate_plain_text(content, "", "", "", "", "").unwrap().0
+    }
+
+    #[test]
+    fn test_word_count() {
+        assert_eq!(word_count(""), 0);
+        assert_eq!(word_count("  one\ntwo\tthree "), 3);
+        assert_eq!(word_count("東京タワー"), 5);
+    }
+
+    #[test]
+    fn test_reading_time() {
+        assert_eq!(reading_time(0, 200), 0);
+        assert_eq!(reading_time(1, 200), 1);
+        assert_eq!(reading_time(200, 200), 1);
+        assert_eq!(reading_time(201, 200), 2);
+        assert_eq!(reading_time(10, 0), 10);
+    }
+
+    #[test]
+    fn test_summarize_first_words() {
+        let content = "---\ntitle: Post\n---\nOne two three four five.\n\nSix seven.";
+        let config = SummaryConfig {
+            words: 3,
+            words_per_minute: 2,
+        };
+
+        let summary = summarize(content, &plain(content), &config);
+
+        assert_eq!(
+            summary,
+            ContentSummary {
+                word_count: 7,
+                reading_time: 4,
+                summary: "One two three…".to_string(),
+                truncated: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_summarize_short_page() {
+        let content = "---\ntitle: Post\n---\nShort page.";
+
+        let summary = summarize(
+            content,
+            &plain(content),
+            &SummaryConfig::default(),
+        );
+
+        assert_eq!(summary.summary, "Short page.");
+        assert!(!summary.truncated);
+    }
+
+    #[test]
+    fn test_summarize_more_marker() {
+        let content = "---\ntitle: Post\n---\nThe intro text.\n\n<!-- more -->\n\nThe rest of the post.";
+
+        let summary = summarize(
+            content,
+            &plain(content),
+            &SummaryConfig::default(),
+        );
+
+        assert_eq!(summary.summary, "The intro text.");
+        assert!(summary.truncated);
+        assert_eq!(summary.word_count, 8);
+    }
+
+    #[test]
+    fn test_summarize_ignores_marker_in_code() {
+        let content = "Use the marker:\n\n```html\n<!-- more -->\n```\n\nEnd.";
+
+        let summary = summarize(
+            content,
+            "Use the marker: End.",
+            &SummaryConfig::default(),
+        );
+
+        assert_eq!(summary.summary, "Use the marker: End.");
+        assert!(!summary.truncated);
+    }
+
+    #[test]
+    fn test_apply_summary_keeps_front_matter() {
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "description".to_string(),
+            "Written by hand".to_string(),
+        );
+        let summary = ContentSummary {
+            word_count: 420,
+            reading_time: 3,
+            summary: "A summary…".to_string(),
+            truncated: true,
+        };
+
+        apply_summary(&mut metadata, &summary);
+
+        assert_eq!(metadata["description"], "Written by hand");
+        assert_eq!(metadata["item_description"], "A summary…");
+        assert_eq!(metadata["summary"], "A summary…");
+        assert_eq!(metadata["word_count"], "420");
+        assert_eq!(metadata["reading_time"], "3");
+    }
+}
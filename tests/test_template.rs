@@ -1,9 +1,12 @@
 #[cfg(test)]
 mod tests {
+    use serde_json::json;
     use ssg::utilities::template::{
-        render_page, render_template, PageOptions,
+        layout_file, render_page, render_template,
+        render_template_with_data,
+        render_template_with_shared_data, PageOptions,
     };
-    use std::{collections::HashMap, error::Error};
+    use std::{collections::HashMap, error::Error, sync::Arc};
 
     #[test]
     fn test_render_template() -> Result<(), Box<dyn Error>> {
@@ -103,4 +106,146 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_render_template_with_data_each_and_if() {
+        let template = "{{#each menus.main}}<li{{#if this.active}} class=\"active\"{{/if}}>{{this.title}}{{#if this.children}}<ul>{{#each this.children}}<li>{{@index}}:{{this.title}}</li>{{/each}}</ul>{{/if}}</li>{{/each}}";
+        let data = json!({
+            "menus": {
+                "main": [
+                    { "title": "Home", "active": true, "children": [] },
+                    {
+                        "title": "Guides",
+                        "active": false,
+                        "children": [{ "title": "Install" }]
+                    }
+                ]
+            }
+        });
+
+        let result = render_template_with_data(
+            template,
+            &HashMap::new(),
+            data.as_object().unwrap(),
+        );
+
+        assert_eq!(
+            result.unwrap(),
+            "<li class=\"active\">Home</li><li>Guides<ul><li>0:Install</li></ul></li>"
+        );
+    }
+
+    #[test]
+    fn test_render_template_with_data_else_and_flat_keys() {
+        let template = "{{title}}: {{#if menus.footer}}footer{{else}}no footer{{/if}} {{site.name}}";
+        let mut context = HashMap::new();
+        context.insert("title", "Page");
+        let data = json!({ "menus": {}, "site": { "name": "Kaishi" } });
+
+        let result = render_template_with_data(
+            template,
+            &context,
+            data.as_object().unwrap(),
+        );
+
+        assert_eq!(result.unwrap(), "Page: no footer Kaishi");
+    }
+
+    #[test]
+    fn test_render_template_with_data_unresolved_tags() {
+        let data = json!({ "menus": {} });
+        let result = render_template_with_data(
+            "{{#each menus.main}}{{this.title}}{{/each}}{{missing}}",
+            &HashMap::new(),
+            data.as_object().unwrap(),
+        );
+        assert!(result.is_err());
+
+        let unclosed = render_template_with_data(
+            "{{#each menus.main}}",
+            &HashMap::new(),
+            data.as_object().unwrap(),
+        );
+        assert_eq!(
+            unclosed,
+            Err("Failed to render template, unclosed {{#each}} block"
+                .to_owned())
+        );
+    }
+
+    #[test]
+    fn test_render_template_with_data_single_pass() {
+        let mut context = HashMap::new();
+        context.insert("title", "{{ not a tag }}");
+        context.insert("series", "Rust");
+        let data = json!({
+            "quote": "{{missing}}",
+            "series": { "parts": [{ "title": "One" }] }
+        });
+
+        let result = render_template_with_data(
+            "{{title}} {{quote}} {{series}}:{{#each series.parts}}{{this.title}}{{/each}}",
+            &context,
+            data.as_object().unwrap(),
+        );
+
+        assert_eq!(
+            result,
+            Ok("{{ not a tag }} {{missing}} Rust:One".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_page_options_set_data() {
+        let mut options = PageOptions::new();
+        options.set_data("menus", json!({ "main": [] }));
+        assert_eq!(
+            options.get_data("menus"),
+            Some(&json!({ "main": [] }))
+        );
+        assert_eq!(options.get_data("missing"), None);
+    }
+
+    #[test]
+    fn test_render_template_with_shared_data() {
+        let site = Arc::new(json!({ "pages": [{ "title": "Home" }] }));
+        let shared = HashMap::from([("site".to_string(), site)]);
+        let data = json!({ "title": "Page" });
+
+        let result = render_template_with_shared_data(
+            "{{title}}:{{#each site.pages}}{{this.title}}{{/each}}",
+            &HashMap::new(),
+            data.as_object().unwrap(),
+            &shared,
+        );
+        assert_eq!(result, Ok("Page:Home".to_owned()));
+
+        let mut options = PageOptions::new();
+        options.set_shared_data("site", Arc::clone(&shared["site"]));
+        options.set_data("title", json!("Page"));
+        assert_eq!(
+            options.get_data("site"),
+            Some(&json!({ "pages": [{ "title": "Home" }] }))
+        );
+        assert_eq!(options.get_data("title"), Some(&json!("Page")));
+    }
+
+    #[test]
+    fn test_layout_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("post.html"), "").unwrap();
+
+        assert_eq!(
+            layout_file(dir.path(), "post"),
+            dir.path().join("post.html")
+        );
+        assert_eq!(
+            layout_file(dir.path(), "section"),
+            dir.path().join("page.html")
+        );
+        assert_eq!(
+            layout_file(dir.path(), "unknown"),
+            dir.path().join("index.html")
+        );
+    }
 }
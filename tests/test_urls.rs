@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod tests {
+    use ssg::models::data::SiteConfig;
+    use ssg::modules::urls::{
+        absolute_url, path_prefix, prefix_url, relative_url,
+        rewrite_links, rewrite_site_links, site_root,
+    };
+    use std::fs;
+
+    fn config(base_url: &str, relative_links: bool) -> SiteConfig {
+        SiteConfig {
+            base_url: base_url.to_string(),
+            relative_links,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_path_prefix() {
+        assert_eq!(
+            path_prefix("https://example.com/product/docs/"),
+            "/product/docs"
+        );
+        assert_eq!(path_prefix("https://example.com/"), "");
+        assert_eq!(path_prefix(""), "");
+    }
+
+    #[test]
+    fn test_absolute_url() {
+        assert_eq!(
+            absolute_url("https://example.com/docs", "/a/index.html"),
+            "https://example.com/docs/a/index.html"
+        );
+    }
+
+    #[test]
+    fn test_prefix_url() {
+        assert_eq!(prefix_url("/docs", "/a/"), "/docs/a/");
+        assert_eq!(prefix_url("/docs", "/docs/a/"), "/docs/docs/a/");
+        assert_eq!(prefix_url("/docs", "/docsets/"), "/docs/docsets/");
+        assert_eq!(prefix_url("", "/a/"), "/a/");
+    }
+
+    #[test]
+    fn test_relative_url() {
+        assert_eq!(relative_url("", "/a/index.html"), "a/index.html");
+        assert_eq!(relative_url("a/b", "/"), "../../index.html");
+        assert_eq!(
+            relative_url("a", "/tags/?page=2#top"),
+            "../tags/index.html?page=2#top"
+        );
+    }
+
+    #[test]
+    fn test_site_root() {
+        assert_eq!(site_root(&config("", false)), "/");
+        assert_eq!(
+            site_root(&config("https://example.com/docs/", false)),
+            "/docs/"
+        );
+        assert_eq!(site_root(&config("", true)), "./");
+    }
+
+    #[test]
+    fn test_rewrite_links_with_prefix() {
+        let html = r#"<a href="/about/index.html">A</a><img src=/logo.svg><a href="https://x.org/">X</a><script src="//cdn.example/x.js"></script><meta http-equiv="refresh" content="0; url=/new/index.html">"#;
+
+        let rewritten = rewrite_links(
+            html,
+            "about",
+            &config("https://example.com/product/docs/", false),
+        );
+
+        assert!(rewritten
+            .contains(r#"href="/product/docs/about/index.html""#));
+        assert!(rewritten.contains("src=/product/docs/logo.svg"));
+        assert!(rewritten.contains(r#"href="https://x.org/""#));
+        assert!(rewritten.contains(r#"src="//cdn.example/x.js""#));
+        assert!(rewritten.contains("url=/product/docs/new/index.html"));
+    }
+
+    #[test]
+    fn test_rewrite_links_relative() {
+        let html = r#"<a href="/">Home</a><a href="/tags/index.html">Tags</a>"#;
+
+        let rewritten = rewrite_links(
+            html,
+            "guides/install",
+            &config("/docs", true),
+        );
+
+        assert_eq!(
+            rewritten,
+            r#"<a href="../../index.html">Home</a><a href="../../tags/index.html">Tags</a>"#
+        );
+    }
+
+    #[test]
+    fn test_rewrite_links_srcset_and_css() {
+        let html = r#"<img srcset="/a.png 1x, /b.png 2x, https://x.org/c.png 3x"><div style="background: url('/bg.png')"></div><style>p { background: url(/p.png); }</style>"#;
+
+        let rewritten = rewrite_links(
+            html,
+            "",
+            &config("https://example.com/docs/", false),
+        );
+
+        assert!(rewritten.contains(
+            r#"srcset="/docs/a.png 1x, /docs/b.png 2x, https://x.org/c.png 3x""#
+        ));
+        assert!(rewritten.contains("url('/docs/bg.png')"));
+        assert!(rewritten.contains("url(/docs/p.png)"));
+    }
+
+    #[test]
+    fn test_rewrite_links_without_config_is_unchanged() {
+        let html = r#"<a href="/about/index.html">A</a>"#;
+
+        assert_eq!(
+            rewrite_links(html, "", &SiteConfig::default()),
+            html
+        );
+    }
+
+    #[test]
+    fn test_rewrite_site_links() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("about")).unwrap();
+        fs::write(
+            dir.path().join("about/index.html"),
+            r#"<a href="/">Home</a>"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("rss.xml"), "<link>/</link>")
+            .unwrap();
+
+        rewrite_site_links(dir.path(), &config("", true)).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.path().join("about/index.html"))
+                .unwrap(),
+            r#"<a href="../index.html">Home</a>"#
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("rss.xml")).unwrap(),
+            "<link>/</link>"
+        );
+    }
+}